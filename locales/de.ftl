@@ -0,0 +1,524 @@
+# German UI strings, see en.ftl
+
+## header
+
+run = Ausführen
+engine-loading = Engine wird geladen…
+run-on-selection = Auf Auswahl ausführen
+    .title = Nur im ausgewählten Text des Subject-Editors suchen
+count-only = Nur zählen
+    .title = Treffer nur pro Regel zählen, ohne sie hervorzuheben
+inline-captures = Captures inline
+    .title = Erfasste Werte hinter jedem Treffer anzeigen
+capture-ranges = Capture-Bereiche
+    .title = Jedes Capture innerhalb seines Treffers markieren, beschriftet mit seinem Namen
+overlap-select-title = Wie überlappende Treffer hervorgehoben werden
+overlap-all = Überlappungen: alle zeigen
+overlap-longest = Überlappungen: längster gewinnt
+overlap-first-rule = Überlappungen: erste Regel gewinnt
+clear = Leeren
+undo = ↶
+    .title = Das letzte Laden, Formatieren, Regel-Bearbeiten oder den Sprachwechsel rückgängig machen (Strg+Alt+Z)
+redo = ↷
+    .title = Wiederherstellen (Strg+Alt+Umschalt+Z)
+format = Formatieren
+    .title = Das Pattern-YAML normalisieren
+copy-share-link = Link kopieren
+shorten = Kürzen
+    .title = Die Konfiguration beim Link-Kürzer speichern und einen kurzen Link kopieren
+save-as = Speichern unter…
+my-snippets = Meine Snippets ▾
+recently-replaced = Zuletzt ersetzt ▾
+    .title = Was Links, Snippets und Importe in diesem Tab ersetzt haben. Ein Klick stellt es wieder her
+recently-replaced-item = { $time } — vor { $by }
+    .title = { $rules ->
+        [one] 1 Regel
+       *[other] { $rules } Regeln
+    }, { $files ->
+        [one] 1 Datei
+       *[other] { $files } Dateien
+    }
+replaced-by-link = einem geteilten Link
+replaced-by-snippet = dem Snippet „{ $name }“
+replaced-by-preset = der Vorgabe des Deployments
+replaced-by-workspace = einem Workspace-Import
+replaced-by-tutorial = dem Tutorial
+replaced-by-restore = einer Wiederherstellung
+ab-compare = A/B-Vergleich
+    .title = Zwei Pattern-Sets auf das Subject anwenden und ihre Treffer vergleichen
+diff-mode = Diff
+    .title = Zwei Versionen des Subjects vergleichen und die Treffer markieren, die nur die geänderte Version hat, wie ein Diff-Scan in CI sie melden würde
+diff-mode-hint = Eine der Seiten bearbeiten, dann ausführen, um die Versionen zu vergleichen
+diff-mode-summary = { $new ->
+        [one] 1 neuer Treffer
+       *[other] { $new } neue Treffer
+    }, { $gone } nur im Original
+swap = ⇄ Tauschen
+    .title = Pattern- und Subject-Bereich tauschen
+restore = Wiederherstellen
+    .title = Beide Bereiche wiederherstellen (Esc)
+maximize-patterns = ⤢ Patterns
+    .title = Den Pattern-Editor maximieren
+maximize-subject = ⤢ Subject
+    .title = Den Subject-Editor maximieren
+docs = Doku
+tutorial = Tutorial
+    .title = Eine geführte Tour zur ersten Regel. Ersetzt den Inhalt der Editoren
+modified-title = Seit dem letzten Laden oder Teilen geändert
+settings-title = Einstellungen
+error-banner = Fehler: { $error }
+close = Schließen
+
+## patterns pane
+
+pattern-set-a = Pattern-Set A
+pattern-set-b = Pattern-Set B
+add-rule = + Regel
+    .title = Ein Regelgerüst an die Patterns anhängen
+rule-from-selection = + Aus Auswahl
+    .title = Eine Regel erstellen, deren Pattern aus dem im Subject ausgewählten Code erzeugt wird
+duplicate-rule = Duplizieren ▾
+no-rules = Keine Regeln
+open-scratchpad = Regex…
+    .title = Eine Transform-Regex an einem Beispielwert entwickeln
+rule-capped =
+    .title = Die Regel hat ihr max_matches erreicht, weitere Treffer wurden verworfen
+rule-enabled-title = Abgewählte Regeln bleiben im YAML, werden beim Ausführen aber übersprungen
+unnamed = (unbenannt)
+rule-packs = Regelpakete ▾
+    .title = Ein kuratiertes Regelpaket in die Patterns übernehmen. Regeln mit schon vergebenem Namen werden übersprungen
+rule-packs-loading = Regelpakete werden geladen…
+no-rule-packs = Der Index enthält keine Regelpakete
+rule-pack-loading = „{ $name }“ wird geladen…
+rule-pack-merged = { $count ->
+        [one] 1 Regel aus „{ $name }“ hinzugefügt
+       *[other] { $count } Regeln aus „{ $name }“ hinzugefügt
+    }
+rule-pack-merged-skipped = { $count ->
+        [one] 1 Regel aus „{ $name }“ hinzugefügt, vorhandene übersprungen: { $skipped }
+       *[other] { $count } Regeln aus „{ $name }“ hinzugefügt, vorhandene übersprungen: { $skipped }
+    }
+batch-decode = Links dekodieren…
+    .title = Mehrere geteilte Links auf einmal dekodieren und ausgewählte Regeln in die aktuellen übernehmen
+batch-decode-placeholder = Geteilte Links einfügen, durch Leerzeichen oder Zeilenumbrüche getrennt
+batch-decode-run = Dekodieren
+batch-decode-link = Link { $number }
+batch-decode-merge = { $count ->
+        [one] 1 Regel übernehmen
+       *[other] { $count } Regeln übernehmen
+    }
+batch-decode-merged = { $count ->
+        [one] 1 Regel hinzugefügt
+       *[other] { $count } Regeln hinzugefügt
+    }
+batch-decode-merged-skipped = { $count ->
+        [one] 1 Regel hinzugefügt, vorhandene übersprungen: { $skipped }
+       *[other] { $count } Regeln hinzugefügt, vorhandene übersprungen: { $skipped }
+    }
+rule-diff = Regelsätze vergleichen…
+    .title = Die Regeln zweier geteilter Links oder eines Links und der aktuellen Regeln vergleichen
+rule-diff-old = Alte Regeln
+rule-diff-new = Neue Regeln
+rule-diff-placeholder = Geteilter Link, leer für die aktuellen Regeln
+rule-diff-compare = Vergleichen
+rule-diff-added = hinzugefügt
+rule-diff-removed = entfernt
+rule-diff-changed = geändert
+rule-diff-patterns = Patterns
+rule-diff-transforms = Transforms
+rule-diff-other = Weitere Felder
+rule-diff-unchanged = { $count ->
+        [one] 1 weitere Regel ist unverändert
+       *[other] { $count } weitere Regeln sind unverändert
+    }
+rule-diff-same = { $count ->
+        [one] Die Regelsätze sind gleich (1 Regel).
+       *[other] Die Regelsätze sind gleich ({ $count } Regeln).
+    }
+check-patterns = Patterns prüfen
+    .title = Die Patterns kompilieren, ohne zu suchen, und jedes fehlerhafte melden. Ausführen verwendet das Ergebnis weiter
+patterns-compile = { $count ->
+        [one] ✓ 1 Pattern kompiliert
+       *[other] ✓ alle { $count } Patterns kompilieren
+    }
+patterns-fail = ✗ { $failed } von { $count } Patterns fehlerhaft
+tag-filter = Tags:
+    .title = Sind Tags ausgewählt, laufen nur die Regeln, die einen davon tragen
+tag-filter-label = Nur Regeln mit diesen Tags ausführen
+clear-tag-filter = Wieder alle Regeln ausführen
+rule-problems = { $count ->
+        [one] 1 Problem in den Regeln
+       *[other] { $count } Probleme in den Regeln
+    }
+    .title = Ohne Ausführen gefunden. Klicken zeigt die Stelle im Editor
+
+## subject pane
+
+add-file = + Datei
+    .title = Eine Subject-Datei hinzufügen
+remove-file-title = Datei entfernen
+files-summary = { $matches ->
+        [one] 1 Treffer
+       *[other] { $matches } Treffer
+    } in { $files ->
+        [one] 1 Datei
+       *[other] { $files } Dateien
+    }
+peek-match = Treffer
+peek-others = { $capture } = { $value } — { $count ->
+        [one] 1 weiterer Treffer
+       *[other] { $count } weitere Treffer
+    }
+highlights-capped = { $shown } von { $total } Treffern in dieser Datei hervorgehoben
+show-more-highlights = Nächste { $count } zeigen
+language-suggestion = Sieht nach { $language } aus — Sprache wechseln?
+language-suggestion-switch = Wechseln
+preset-offer = Das { $language }-Beispiel laden?
+preset-offer-load = Laden
+compare-both = A & B
+compare-a-only = Nur A: { $text }
+compare-b-only = Nur B: { $text }
+
+## explanation
+
+explain-title = Erklärung: { $rule }
+explain-pattern = Pattern
+explain-no-pattern = Kein einzelnes Pattern dieser Regel reproduziert den Treffer allein.
+explain-match = Erklären
+    .title = Zeigen, welches Pattern diesen Treffer gefunden hat und wie seine Tokens zugeordnet sind
+explain-matched-text = Getroffener Text
+explain-tokens = Tokens
+explain-nothing = (nichts)
+explain-captures = Captures
+explain-no-captures = keine
+explain-no-match = Erklären
+    .title = Zeigen, wie weit jedes Pattern dieser Regel im Subject kommt
+near-miss-title = Warum kein Treffer: { $rule }
+near-miss-failed-at = { $matched } von { $total } Tokens passen, dann scheitert es an `{ $token }`
+near-miss-matches = Das ganze Pattern passt. Wird die Regel durch einen Tag herausgefiltert?
+near-miss-nothing = Schon das erste Token passt nicht
+
+## opened links
+
+incoming-link = { $count ->
+        [one] Der geöffnete Link enthält 1 Regel.
+       *[other] Der geöffnete Link enthält { $count } Regeln.
+    }
+incoming-replace = Ersetzen
+    .title = Regeln und Dateien des Links laden und die aktuellen ersetzen
+incoming-merge = Regeln zusammenführen
+    .title = Die Regeln des Links an die aktuellen anhängen und die aktuellen Dateien behalten. Regeln mit schon vergebenem Namen werden übersprungen
+
+## snapshots
+
+snapshot-diff = Snapshot-Vergleich
+snapshot-unchanged = Snapshot-Vergleich: der aktuelle Lauf stimmt mit dem Snapshot überein
+snapshot-missing = Fehlend (Regressionen)
+snapshot-new = Neu
+export-snapshot = Snapshot exportieren
+    .title = Alle Treffer als sortiertes JSON herunterladen, z. B. um es als Golden File einzuchecken
+compare-snapshot = Snapshot vergleichen…
+    .title = Einen zuvor exportierten Snapshot mit dem aktuellen Lauf vergleichen
+
+## workspace archives
+
+export-workspace = Workspace exportieren
+    .title = Die Patterns und alle Dateien als Zip herunterladen, für Workspaces, die zu groß für einen Link sind
+import-workspace = Workspace importieren…
+    .title = Patterns und Dateien durch einen exportierten Workspace-Zip ersetzen
+
+## memory
+
+memory-usage = { $used } MB
+    .title = Belegter Heap. Der WebAssembly-Speicher ist auf { $total } MB gewachsen; der Browser gibt ihn nicht zurück, freigegebener Platz wird aber wiederverwendet
+free-memory = Speicher freigeben
+    .title = Treffer, Hervorhebungen und kompilierte Patterns verwerfen. Der nächste Lauf kompiliert neu
+
+## tutorial
+
+tutorial-confirm = Das Tutorial ersetzt Patterns und Dateien. Fortfahren?
+tutorial-progress = Schritt { $step } von { $total }
+tutorial-next = Prüfen und weiter
+tutorial-finish = Fertig
+tutorial-rule = Eine Regel schreiben
+tutorial-rule-help = Im Pattern-Editor einen Listeneintrag mit Name und Pattern anlegen, zum Beispiel:
+
+    - name: cipher
+      patterns:
+        - Cipher::new(...)
+tutorial-capture = Einen Wert erfassen
+tutorial-capture-help = Einen Teil des Patterns durch eine Metavariable wie $_ALG ersetzen. Sie passt auf ein Token und erfasst es, z. B. Cipher::new($_ALG, ...)
+tutorial-transform = Einen Transform hinzufügen
+tutorial-transform-help = Ein Transform behält nur Treffer, deren Capture auf einen Regex passt. Einen für die Metavariable anlegen, ohne das $:
+
+      transform:
+        _ALG: des|rc4
+tutorial-run = Ausführen
+tutorial-run-help = Auf Ausführen klicken. Die Hervorhebungen und die Trefferliste zeigen, was die Regel gefunden hat.
+tutorial-done = Geschafft
+tutorial-done-help = Das ist eine vollständige Regel. Die Doku erklärt den Rest der Pattern-Sprache.
+tutorial-invalid-yaml = Die Patterns sind noch kein gültiges YAML: { $error }
+tutorial-rule-missing = Noch keine Regel mit Name und Pattern.
+tutorial-capture-missing = Noch kein Pattern enthält eine Metavariable wie $_ALG.
+tutorial-transform-missing = Noch keine Regel hat einen Transform für eine ihrer Metavariablen.
+tutorial-run-missing = Der letzte Lauf hat für die Regel nichts gefunden. Ausführen oder den Transform lockern.
+
+## print view
+
+print-view = Druckansicht
+    .title = Die Dateien mit ihren Treffern und eine Treffertabelle, zum Drucken oder Speichern als PDF
+print = Drucken…
+close-print-view = Schließen
+print-heading = LexerSearch-Ergebnisse
+print-appendix = Treffer ({ $count })
+print-location = Stelle
+print-rule = Regel
+print-captures = Captures und Ausgaben
+print-no-results = Keine Treffer. Zuerst die Patterns ausführen.
+print-more-lines = { $count } weitere Zeilen nicht angezeigt
+
+## usage
+
+usage = Nutzung
+    .title = Diagramme der bisherigen Läufe, nur in diesem Browser gespeichert
+usage-heading = Bisherige Nutzung
+usage-local-only = Nur in diesem Browser gespeichert und nie übertragen.
+usage-empty = Noch keine Läufe aufgezeichnet.
+usage-totals = { $runs } Läufe, { $matches } Treffer, im Schnitt { $ms } ms pro Lauf
+usage-runs-by-day = Läufe der letzten { $days } Tage
+usage-day-runs = Tag { $day }: { $count } Läufe
+usage-runs-by-language = Läufe nach Sprache
+usage-matches-by-rule = Treffer nach Regel
+usage-clear = Protokoll löschen
+usage-clear-confirm = Die Aufzeichnung aller Läufe löschen?
+
+## large files
+
+scan-file = Große Datei durchsuchen…
+    .title = Eine lokale Datei stückweise durchsuchen, ohne sie im Editor zu öffnen. Treffer können nicht über eine Stückgrenze (alle 8 MB) reichen
+scan-progress = Durchsuche… { $percent }%
+cancel-scan =
+    .title = Die Suche anhalten, bisherige Treffer bleiben erhalten
+run-progress = Suche… { $percent }%
+    .title = Den Lauf anhalten und die bisherigen Treffer zeigen
+
+## snippets
+
+no-snippets = Keine gespeicherten Snippets
+rename = Umbenennen
+duplicate = Duplizieren
+delete = Löschen
+
+## status bar
+
+results-toggle = Ergebnisse ({ $count })
+    .title = Die Ergebnisliste ein- oder ausblenden. F8 und Umschalt+F8 springen zum nächsten und vorherigen Treffer
+summary-matches = { $count ->
+        [one] 1 Treffer
+       *[other] { $count } Treffer
+    }
+summary-capped = (durch max_matches begrenzt)
+summary-count-only = (nur gezählt)
+summary-timed-out = — nach { $secs } s abgebrochen, die Ergebnisse sind unvollständig. Für einen vollständigen Lauf „Zeitlimit pro Lauf“ in den Einstellungen erhöhen
+cursor-position = Z. { $line }, Sp. { $col }
+link-stats = 🔗 { $chars } Zeichen ({ $ratio }×)
+    .title = { $raw } Bytes auf { $compressed } komprimiert ({ $ratio }×) in { $ms } ms. Klicken, um den Link zu kopieren
+link-audit = ⚠ Link kann beschädigt werden
+    .title = { $findings }
+        Klicken kopiert ihn nur aus Buchstaben und Ziffern
+link-audit-reserved = Proxys kodieren oder verwerfen womöglich
+link-audit-markdown = Markdown beendet oder formatiert Links bei
+link-audit-trailing = Chat-Programme lassen das letzte Zeichen weg
+rule-docs-link = Dokumentation der Regel
+link-scrolling = Verknüpftes Scrollen
+    .title = Beim Scrollen der Ergebnisse die Datei auf den sichtbaren Treffern zentriert halten
+sort-results = Sortieren nach
+order-position = Position
+order-unit = Regelname
+order-group = Gruppe
+order-length = Trefferlänge
+tab-matches = Treffer
+tab-aggregate = Aggregation
+aggregate-by = Gruppieren nach Capture
+aggregate-distinct = { $count ->
+        [one] 1 verschiedener Wert
+       *[other] { $count } verschiedene Werte
+    }
+aggregate-filter =
+    .title = Die Treffer mit diesem Wert auflisten
+aggregate-more = … und { $count } weitere
+aggregate-empty = Kein Treffer hat etwas erfasst. Captures wie $_VAR in den Patterns werden hier gezählt
+results-filter = { $name } = { $value }
+clear-results-filter = Alle Treffer anzeigen
+copy-outputs = Ausgaben kopieren
+    .title = Die aufgelösten `out`-Werte der aufgelisteten Treffer kopieren, einer pro Zeile
+export-outputs = Ausgaben exportieren
+    .title = Die aufgelösten `out`-Werte der aufgelisteten Treffer als Textdatei herunterladen
+
+## accessibility
+
+matches-found = { $count ->
+        [one] 1 Treffer gefunden
+       *[other] { $count } Treffer gefunden
+    }
+results-list-label = { $count ->
+        [one] 1 Treffer, Enter springt dorthin
+       *[other] { $count } Treffer, mit den Pfeiltasten wählen und mit Enter hinspringen
+    }
+splitter-label = Bereiche anpassen, die Pfeiltasten verschieben den Trenner
+
+## dialogs
+
+save-snippet-prompt = Snippet speichern als:
+overwrite-snippet-confirm = Das vorhandene Snippet „{ $name }“ überschreiben?
+load-snippet-confirm = Ungespeicherte Änderungen verwerfen und dieses Snippet laden?
+load-workspace-confirm = Ungespeicherte Änderungen verwerfen und diesen Workspace importieren?
+rename-snippet-prompt = Snippet umbenennen in:
+delete-snippet-confirm = Snippet „{ $name }“ löschen?
+format-confirm = Beim Formatieren gehen YAML-Kommentare verloren. Fortfahren?
+transform-confirm = Das Schreiben des Transforms formatiert das YAML neu und entfernt Kommentare. Fortfahren?
+new-file-prompt = Neuer Dateiname:
+rename-file-prompt = Datei umbenennen in:
+remove-file-confirm = „{ $name }“ aus dem Projekt entfernen?
+
+sessions = Sitzungen
+session-name = Arbeitsbereich { $number }
+session-tab.title = Klicken zum Wechseln, Doppelklick zum Umbenennen
+new-session.title = Eine neue Sitzung in einem eigenen Tab öffnen
+close-session = Diese Sitzung schließen
+close-session-confirm = „{ $name }“ schließen? Regeln und Subjects werden gelöscht.
+rename-session-prompt = Sitzung umbenennen in:
+
+share-results = Ergebnisse teilen
+    .title = Einen Link kopieren, der die Treffer des letzten Laufs enthält und sie ohne Ausführen zeigt
+shared-results = { $count ->
+        [one] 1 Treffer aus dem Link, so wie beim Teilen gefunden.
+       *[other] { $count } Treffer aus dem Link, so wie beim Teilen gefunden.
+    }
+shared-results-rerun = Hier ausführen
+
+open-live-file = Von der Festplatte öffnen…
+    .title = Eine lokale Datei als Subject öffnen, um sie nach Änderungen in einem anderen Editor neu zu laden
+live-file.title = Von der Festplatte geöffnet
+reload-live-files = Von der Festplatte neu laden
+    .title = Die von der Festplatte geöffneten Dateien erneut lesen und ihren Text hier ersetzen
+live-file-polling = Automatisch neu laden
+    .title = Die von der Festplatte geöffneten Dateien bei jeder Änderung neu laden
+
+## errors
+
+hint-yaml-lhs = prüfe das YAML der Regeln um Zeile { $line }, Spalte { $col }
+hint-yaml-lhs-unplaced = prüfe, ob die Regeln eine YAML-Liste von Regeln sind
+hint-yaml-language = wähle die Sprache des Subjekts erneut im Sprachmenü
+hint-templates = prüfe die `templates` der Regel „{ $rule }“
+hint-transform = prüfe die `transform`-Regex in Regel „{ $rule }“
+hint-pattern = prüfe Pattern { $pattern } der Regel „{ $rule }“
+hint-limits = ein Subjekt hat eine Grenze der Engine erreicht, teile sehr lange Tokens oder schränke Patterns ein, die fast alles treffen
+live-file-failed = die Datei konnte nicht geöffnet werden: { $error }
+live-file-read-failed = „{ $name }“ konnte nicht von der Festplatte gelesen werden: { $error }
+share-results-stale = zuerst die Regeln auf alle Subjects anwenden, die geteilten Ergebnisse müssen zu ihnen passen
+scrub-failed = die Subjects ließen sich nicht bereinigen, daher wurde nichts geteilt: { $error }
+share-results-scrubbed = Ergebnisse können nicht geteilt werden, während Subjects bereinigt werden, ihre Captures würden den Code verraten
+session-save-failed = die Sitzung konnte nicht gespeichert werden: { $error }
+session-name-taken = eine Sitzung namens „{ $name }“ existiert bereits
+sessions-diff-mode = zum Wechseln der Sitzung den Diff-Modus verlassen
+snippet-error = Snippet „{ $name }“: { $error }
+file-exists = eine Datei namens „{ $name }“ existiert bereits
+shorten-failed = der Link konnte nicht gekürzt werden: { $error }
+short-link-no-endpoint = das ist ein Kurzlink, aber in den Einstellungen ist kein Link-Kürzer konfiguriert
+short-link-invalid-id = die ID dieses Kurzlinks darf nur Buchstaben, Ziffern, _ und - enthalten
+short-link-failed = der kurze Link konnte nicht geladen werden: { $error }
+batch-decode-invalid = kein geteilter Link: { $error }
+result-hook-error = Ergebnis-Hook: { $error }
+rule-diff-old-invalid = alte Regeln: { $error }
+rule-diff-new-invalid = neue Regeln: { $error }
+rule-pack-error = Regelpakete konnten nicht geladen werden: { $error }
+workspace-error = Workspace-Archiv: { $error }
+snapshot-needs-run = vor dem Export eines Snapshots die Patterns ausführen (ohne „Nur zählen“)
+patterns-failed = { $count ->
+        [one] 1 Pattern ließ sich nicht kompilieren, siehe Liste unten
+       *[other] { $count } Patterns ließen sich nicht kompilieren, siehe Liste unten
+    }
+pattern-errors = { $count ->
+        [one] 1 Pattern-Fehler
+       *[other] { $count } Pattern-Fehler
+    }
+patterns-skipped = { $count ->
+        [one] 1 Pattern übersprungen, die übrigen Regeln liefen
+       *[other] { $count } Patterns übersprungen, die übrigen Regeln liefen
+    }
+select-code-first = zuerst Code im Subject-Editor auswählen
+recovered-after-crash = die Engine ist in der letzten Sitzung abgestürzt. Patterns und alle Dateien wurden wiederhergestellt
+    { $error }
+crash-banner = die Engine ist abgestürzt: { $error }
+    Patterns und geöffnete Datei sind gesichert und kommen nach dem Neuladen zurück
+deploy-preset-invalid = die Voreinstellung dieser Installation konnte nicht geladen werden: { $error }
+reload-engine = Engine neu laden
+
+## settings
+
+shortener-endpoint = Endpunkt des Link-Kürzers
+rule-pack-index = Index der Regelpakete (JSON)
+shortener-placeholder = https://example.com/api/links (optional)
+link-encoding = Kodierung geteilter Links
+link-encoding-basex = Kompakt (Standard)
+link-encoding-base64url = base64url (sichere Zeichen)
+link-encoding-strict-safe = Streng sicher (nur Buchstaben und Ziffern)
+link-encoding-json = Reines JSON (Debugging)
+scrub-shared-links = Subjects vor dem Teilen anonymisieren
+    .title = Bezeichner und Strings in geteilten Links durch Platzhalter ersetzen, Schlüsselwörter und alles, was die Patterns erwähnen, bleiben erhalten
+link-compression = Kompression geteilter Links
+compression-fast = Schnell (längere Links)
+compression-normal = Normal
+compression-max = Maximal (kürzeste Links, langsam bei großen Subjects)
+ui-language = Sprache der Oberfläche
+max-highlights = Gleichzeitig angewendete Hervorhebungen
+run-timeout = Zeitlimit pro Lauf in Sekunden (0 für keines)
+run-slice-tokens = Tokens pro Frame, hält die Seite bedienbar; Treffer über 200 Zeilen können abgeschnitten werden (0 für alles auf einmal)
+result-hook = Ergebnis-Hook (JS)
+    .title = Rumpf einer JavaScript-Funktion, die jeden Treffer als `match` erhält. false verwirft ihn, ein String wird seine Hervorhebungsklasse, oder {"{"} className, tooltip {"}"}
+result-hook-placeholder = if (match.name === "noisy") return false;
+context-lines = Kontextzeilen in den Ergebnissen
+    .title = Zeilen vor und nach der Zeile jedes Treffers in der Trefferliste
+skip-invalid-patterns = Bei Pattern-Fehlern fortfahren
+    .title = Die kompilierbaren Patterns ausführen und die fehlerhaften auflisten, statt den Lauf abzubrechen
+auto-run = Automatisch ausführen
+    .title = Die Patterns kurz nach Änderungen an Regeln, Dateien oder Sprache erneut ausführen. Sonst verwirft ein Sprachwechsel nur die alten Ergebnisse
+ignore-trailing-whitespace = Leerraum am Zeilenende ignorieren
+    .title = Nicht automatisch ausführen, wenn eine Änderung nur Leerraum am Zeilenende hinzufügt oder entfernt
+
+## language picker
+
+filter-languages = Sprachen filtern…
+recent-language = zuletzt
+lexer-c-like = C-artiger Lexer
+lexer-python-like = Python-artiger Lexer
+lexer-rust-like = Rust-artiger Lexer
+lexer-family =
+    .title = Der Tokenizer, durch den Subjekt und Muster für diese Sprache laufen
+lexer-unknown = Kein Lexer
+    .title = Diese Sprache kann nicht durchsucht werden: { $error }
+
+## regex scratchpad
+
+scratchpad-title = Regex-Notizblock
+scratchpad-sample = Beispielwert eines Captures
+scratchpad-regex = Regex
+scratchpad-match = Treffer: { $value }
+scratchpad-group = Gruppe { $name }: { $value }
+scratchpad-unset = (nicht gesetzt)
+scratchpad-no-match = kein Treffer
+scratchpad-rule = Regel { $n }
+scratchpad-capture-placeholder = Capture, z. B. _FMT
+scratchpad-apply = Übernehmen
+    .title = Die Regex in die Transform-Map der Regel schreiben
+scratchpad-note = Vorschau mit der Regex-Engine des Browsers; exotische Syntax kann sich im Matcher anders verhalten.
+
+## self-test
+
+self-test = Selbsttest
+self-test-run = Erneut ausführen
+self-test-summary = { $passed } von { $total } Fällen bestanden
+self-test-count = { $expected } Treffer erwartet, { $got } gefunden: { $matches }
+self-test-mismatch = { $expected } erwartet, { $got } gefunden
+self-test-report = Bericht, zum Einfügen in einen Fehlerbericht:
@@ -0,0 +1,525 @@
+# English UI strings. every message here must also exist in the other locales,
+# missing ones fall back to this file
+
+## header
+
+run = Run
+engine-loading = Engine loading…
+run-on-selection = Run on selection
+    .title = Only match within the text selected in the subject editor
+count-only = Count only
+    .title = Only count matches per rule, without highlighting them
+inline-captures = Inline captures
+    .title = Show captured values inline after each match
+capture-ranges = Capture ranges
+    .title = Mark each capture within its match, labelled with the capture's name
+overlap-select-title = How overlapping matches are highlighted
+overlap-all = Overlaps: show all
+overlap-longest = Overlaps: longest wins
+overlap-first-rule = Overlaps: first rule wins
+clear = Clear
+undo = ↶
+    .title = Undo the last load, format, rule edit or language switch (Ctrl+Alt+Z)
+redo = ↷
+    .title = Redo (Ctrl+Alt+Shift+Z)
+format = Format
+    .title = Normalize the pattern YAML
+copy-share-link = Copy Share Link
+shorten = Shorten
+    .title = Store the config with the link shortener and copy a short link
+save-as = Save as…
+my-snippets = My snippets ▾
+recently-replaced = Recently replaced ▾
+    .title = What links, snippets and imports replaced in this tab. Picking one brings it back
+recently-replaced-item = { $time } — before { $by }
+    .title = { $rules ->
+        [one] 1 rule
+       *[other] { $rules } rules
+    }, { $files ->
+        [one] 1 file
+       *[other] { $files } files
+    }
+replaced-by-link = a share link
+replaced-by-snippet = the snippet "{ $name }"
+replaced-by-preset = the deployment's preset
+replaced-by-workspace = a workspace import
+replaced-by-tutorial = the tutorial
+replaced-by-restore = a restore
+ab-compare = A/B compare
+    .title = Run two pattern sets against the subject and compare their matches
+diff-mode = Diff
+    .title = Compare two versions of the subject and mark the matches only the modified version has, like a diff scan in CI would flag them
+diff-mode-hint = Edit either side, then run to compare the versions
+diff-mode-summary = { $new ->
+        [one] 1 new match
+       *[other] { $new } new matches
+    }, { $gone } only in the original
+swap = ⇄ Swap
+    .title = Swap the pattern and subject panes
+restore = Restore
+    .title = Restore both panes (Esc)
+maximize-patterns = ⤢ Patterns
+    .title = Maximize the pattern editor
+maximize-subject = ⤢ Subject
+    .title = Maximize the subject editor
+docs = Docs
+tutorial = Tutorial
+    .title = A guided tour through writing a first rule. Replaces the editors' content
+modified-title = Modified since last load or share
+settings-title = Settings
+error-banner = Error: { $error }
+close = Close
+
+## patterns pane
+
+pattern-set-a = Pattern set A
+pattern-set-b = Pattern set B
+add-rule = + Add rule
+    .title = Append a rule scaffold to the patterns
+rule-from-selection = + From selection
+    .title = Create a rule whose pattern is generated from the code selected in the subject
+duplicate-rule = Duplicate ▾
+no-rules = No rules
+open-scratchpad = Regex…
+    .title = Develop a transform regex against a sample value
+rule-capped =
+    .title = The rule reached its max_matches, further matches were dropped
+rule-enabled-title = Unchecked rules stay in the YAML but are skipped when running
+unnamed = (unnamed)
+rule-packs = Rule packs ▾
+    .title = Merge a curated rule pack into the patterns. Rules whose name is taken are skipped
+rule-packs-loading = Loading rule packs…
+no-rule-packs = The index lists no rule packs
+rule-pack-loading = Loading "{ $name }"…
+rule-pack-merged = { $count ->
+        [one] Added 1 rule from "{ $name }"
+       *[other] Added { $count } rules from "{ $name }"
+    }
+rule-pack-merged-skipped = { $count ->
+        [one] Added 1 rule from "{ $name }", skipped existing: { $skipped }
+       *[other] Added { $count } rules from "{ $name }", skipped existing: { $skipped }
+    }
+batch-decode = Decode links…
+    .title = Decode several share links at once and merge picked rules into the current ones
+batch-decode-placeholder = Paste share links, separated by spaces or line breaks
+batch-decode-run = Decode
+batch-decode-link = Link { $number }
+batch-decode-merge = { $count ->
+        [one] Merge 1 rule
+       *[other] Merge { $count } rules
+    }
+batch-decode-merged = { $count ->
+        [one] Added 1 rule
+       *[other] Added { $count } rules
+    }
+batch-decode-merged-skipped = { $count ->
+        [one] Added 1 rule, skipped existing: { $skipped }
+       *[other] Added { $count } rules, skipped existing: { $skipped }
+    }
+rule-diff = Diff rule sets…
+    .title = Compare the rules of two share links, or of a link and the current rules
+rule-diff-old = Old rules
+rule-diff-new = New rules
+rule-diff-placeholder = Share link, empty for the current rules
+rule-diff-compare = Compare
+rule-diff-added = added
+rule-diff-removed = removed
+rule-diff-changed = changed
+rule-diff-patterns = Patterns
+rule-diff-transforms = Transforms
+rule-diff-other = Other fields
+rule-diff-unchanged = { $count ->
+        [one] 1 more rule is unchanged
+       *[other] { $count } more rules are unchanged
+    }
+rule-diff-same = { $count ->
+        [one] The rule sets are the same (1 rule).
+       *[other] The rule sets are the same ({ $count } rules).
+    }
+check-patterns = Check patterns
+    .title = Compile the patterns without matching, reporting every one that fails. Run reuses the result
+patterns-compile = { $count ->
+        [one] ✓ 1 pattern compiles
+       *[other] ✓ all { $count } patterns compile
+    }
+patterns-fail = ✗ { $failed } of { $count } patterns fail
+tag-filter = Tags:
+    .title = With tags selected, only the rules carrying one of them are run
+tag-filter-label = Run only rules with these tags
+clear-tag-filter = Run all rules again
+rule-problems = { $count ->
+        [one] 1 problem in the rules
+       *[other] { $count } problems in the rules
+    }
+    .title = Found without running. Click one to show it in the editor
+
+## subject pane
+
+add-file = + File
+    .title = Add a subject file
+remove-file-title = Remove file
+files-summary = { $matches ->
+        [one] 1 match
+       *[other] { $matches } matches
+    } in { $files ->
+        [one] 1 file
+       *[other] { $files } files
+    }
+peek-match = match
+peek-others = { $capture } = { $value } — { $count ->
+        [one] 1 other match
+       *[other] { $count } other matches
+    }
+highlights-capped = Highlighting { $shown } of { $total } matches in this file
+show-more-highlights = Show next { $count }
+language-suggestion = Looks like { $language } — switch language?
+language-suggestion-switch = Switch
+preset-offer = Load the { $language } example?
+preset-offer-load = Load
+compare-both = A & B
+compare-a-only = A only: { $text }
+compare-b-only = B only: { $text }
+
+## explanation
+
+explain-title = Explain: { $rule }
+explain-pattern = Pattern
+explain-no-pattern = No single pattern of this rule reproduces the match on its own.
+explain-match = Explain
+    .title = Show which pattern found this match and how its tokens line up
+explain-matched-text = Matched text
+explain-tokens = Tokens
+explain-nothing = (nothing)
+explain-captures = Captures
+explain-no-captures = none
+explain-no-match = Explain
+    .title = Show how far each pattern of this rule gets in the subject
+near-miss-title = Why no match: { $rule }
+near-miss-failed-at = { $matched } of { $total } tokens match, then it fails at `{ $token }`
+near-miss-matches = The whole pattern matches. Is the rule filtered out by a tag?
+near-miss-nothing = Not even the first token matches
+
+## opened links
+
+incoming-link = { $count ->
+        [one] The opened link has 1 rule.
+       *[other] The opened link has { $count } rules.
+    }
+incoming-replace = Replace
+    .title = Load the link's rules and files, replacing the current ones
+incoming-merge = Merge rules
+    .title = Append the link's rules to the current ones and keep the current files. Rules whose name is taken are skipped
+
+## snapshots
+
+snapshot-diff = Snapshot comparison
+snapshot-unchanged = Snapshot comparison: the current run matches the snapshot
+snapshot-missing = Missing (regressions)
+snapshot-new = New
+export-snapshot = Export snapshot
+    .title = Download every match as sorted JSON, e.g. to commit as a golden file
+compare-snapshot = Compare snapshot…
+    .title = Compare a previously exported snapshot with the current run
+
+## workspace archives
+
+export-workspace = Export workspace
+    .title = Download the patterns and every subject file as a zip, for workspaces too large for a share link
+import-workspace = Import workspace…
+    .title = Replace the patterns and files with an exported workspace zip
+
+## memory
+
+memory-usage = { $used } MB
+    .title = Heap in use. The WebAssembly memory has grown to { $total } MB; the browser doesn't take it back, but freed space is reused
+free-memory = Free memory
+    .title = Drop the results, highlights and compiled patterns. The next run compiles again
+
+## tutorial
+
+tutorial-confirm = The tutorial replaces the patterns and files. Continue?
+tutorial-progress = Step { $step } of { $total }
+tutorial-next = Check and continue
+tutorial-finish = Finish
+tutorial-rule = Write a rule
+tutorial-rule-help = In the pattern editor, add a list item with a name and a pattern, for example:
+
+    - name: cipher
+      patterns:
+        - Cipher::new(...)
+tutorial-capture = Capture a value
+tutorial-capture-help = Replace part of the pattern with a metavariable such as $_ALG. It matches one token and captures it, e.g. Cipher::new($_ALG, ...)
+tutorial-transform = Add a transform
+tutorial-transform-help = A transform keeps only matches whose capture fits a regex. Add one for your metavariable, without the $:
+
+      transform:
+        _ALG: des|rc4
+tutorial-run = Run it
+tutorial-run-help = Press Run. The highlighted code and the results list show what your rule found.
+tutorial-done = Done
+tutorial-done-help = That's a complete rule. The Docs button explains the rest of the pattern language.
+tutorial-invalid-yaml = The patterns aren't valid YAML yet: { $error }
+tutorial-rule-missing = No rule with a name and a pattern yet.
+tutorial-capture-missing = None of the patterns has a metavariable like $_ALG yet.
+tutorial-transform-missing = No rule has a transform for one of its metavariables yet.
+tutorial-run-missing = The last run found nothing for your rule. Run it, or loosen the transform.
+
+## print view
+
+print-view = Print view
+    .title = The subjects with their matches and a table of the matches, laid out for printing or saving as PDF
+print = Print…
+close-print-view = Close
+print-heading = LexerSearch results
+print-appendix = Matches ({ $count })
+print-location = Location
+print-rule = Rule
+print-captures = Captures and outputs
+print-no-results = No matches. Run the patterns first.
+print-more-lines = { $count } more lines not shown
+
+## usage
+
+usage = Usage
+    .title = Charts of your past runs, kept only in this browser
+usage-heading = Your usage
+usage-local-only = Stored in this browser only, never sent anywhere.
+usage-empty = No runs recorded yet.
+usage-totals = { $runs } runs, { $matches } matches, { $ms } ms per run on average
+usage-runs-by-day = Runs in the last { $days } days
+usage-day-runs = Day { $day }: { $count } runs
+usage-runs-by-language = Runs by language
+usage-matches-by-rule = Matches by rule
+usage-clear = Clear the log
+usage-clear-confirm = Delete the record of all your runs?
+
+## large files
+
+scan-file = Scan large file…
+    .title = Match a local file chunk by chunk without opening it in the editor. Matches can't span a chunk boundary (every 8 MB)
+scan-progress = Scanning… { $percent }%
+cancel-scan =
+    .title = Stop the scan, keeping the matches so far
+run-progress = Matching… { $percent }%
+    .title = Stop the run, showing the matches so far
+
+## snippets
+
+no-snippets = No saved snippets
+rename = Rename
+duplicate = Duplicate
+delete = Delete
+
+## status bar
+
+results-toggle = Results ({ $count })
+    .title = Show or hide the results list. F8 and Shift+F8 jump to the next and previous match
+summary-matches = { $count ->
+        [one] 1 match
+       *[other] { $count } matches
+    }
+summary-count-only = (count only)
+summary-capped = (capped by max_matches)
+summary-timed-out = — stopped after { $secs } s, these are partial results. Raise "Run timeout" in the settings to scan everything
+cursor-position = Ln { $line }, Col { $col }
+link-stats = 🔗 { $chars } chars ({ $ratio }×)
+    .title = { $raw } bytes compressed to { $compressed } ({ $ratio }×) in { $ms } ms. Click to copy the share link
+link-audit = ⚠ link may break
+    .title = { $findings }
+        Click to copy it in letters and digits only
+link-audit-reserved = Proxies may encode or reject
+link-audit-markdown = Markdown ends or restyles links at
+link-audit-trailing = Chat tools leave out the last character
+rule-docs-link = Rule documentation
+link-scrolling = Link scrolling
+    .title = Keep the subject centered on the matches in view while scrolling the results
+sort-results = Sort by
+order-position = Position
+order-unit = Rule name
+order-group = Group
+order-length = Match length
+tab-matches = Matches
+tab-aggregate = Aggregate
+aggregate-by = Group by capture
+aggregate-distinct = { $count ->
+        [one] 1 distinct value
+       *[other] { $count } distinct values
+    }
+aggregate-filter =
+    .title = List the matches with this value
+aggregate-more = … and { $count } more
+aggregate-empty = No match captured anything. Captures like $_VAR in the patterns are counted here
+results-filter = { $name } = { $value }
+clear-results-filter = Show all matches
+copy-outputs = Copy outputs
+    .title = Copy the expanded `out` values of the listed matches, one per line
+export-outputs = Export outputs
+    .title = Download the expanded `out` values of the listed matches as a text file
+
+## accessibility
+
+matches-found = { $count ->
+        [one] 1 match found
+       *[other] { $count } matches found
+    }
+results-list-label = { $count ->
+        [one] 1 match, Enter jumps to it
+       *[other] { $count } matches, use the arrow keys and Enter to jump to one
+    }
+splitter-label = Resize the panes, arrow keys move the divider
+
+## dialogs
+
+save-snippet-prompt = Save snippet as:
+overwrite-snippet-confirm = Overwrite the existing snippet "{ $name }"?
+load-snippet-confirm = Discard unsaved changes and load this snippet?
+load-workspace-confirm = Discard unsaved changes and import this workspace?
+rename-snippet-prompt = Rename snippet to:
+delete-snippet-confirm = Delete snippet "{ $name }"?
+format-confirm = Formatting drops YAML comments. Continue?
+transform-confirm = Writing the transform reformats the YAML and drops comments. Continue?
+new-file-prompt = New file name:
+rename-file-prompt = Rename file to:
+remove-file-confirm = Remove "{ $name }" from the project?
+
+sessions = Sessions
+session-name = Workspace { $number }
+session-tab.title = Click to switch, double-click to rename
+new-session.title = Open a new session in its own tab
+close-session = Close this session
+close-session-confirm = Close "{ $name }"? Its rules and subjects are deleted.
+rename-session-prompt = Rename session to:
+
+share-results = Share results
+    .title = Copy a link with the matches of the last run baked in, shown without running
+shared-results = { $count ->
+        [one] Showing 1 match from the link, as found by whoever shared it.
+       *[other] Showing { $count } matches from the link, as found by whoever shared it.
+    }
+shared-results-rerun = Run here
+
+open-live-file = Open from disk…
+    .title = Open a local file as a subject, so it can be reloaded after another editor changed it
+live-file.title = Opened from disk
+reload-live-files = Reload from disk
+    .title = Read the files opened from disk again, replacing their text here
+live-file-polling = Auto-reload
+    .title = Reload the files opened from disk whenever they change
+
+## errors
+
+hint-yaml-lhs = check the rules' YAML around line { $line }, column { $col }
+hint-yaml-lhs-unplaced = check that the rules are a YAML list of rules
+hint-yaml-language = pick the subject's language again from the language menu
+hint-templates = check the `templates` of rule '{ $rule }'
+hint-transform = check your `transform` regex in rule '{ $rule }'
+hint-pattern = check pattern { $pattern } of rule '{ $rule }'
+hint-limits = a subject hit one of the engine's limits, split very long tokens or narrow patterns that match almost everything
+live-file-failed = the file couldn't be opened: { $error }
+live-file-read-failed = "{ $name }" couldn't be read from disk: { $error }
+share-results-stale = run the rules on the whole subjects first, the shared results must match them
+scrub-failed = the subjects couldn't be scrubbed, so nothing was shared: { $error }
+share-results-scrubbed = results can't be shared while subjects are scrubbed, their captures would give the code away
+session-save-failed = the session couldn't be saved: { $error }
+session-name-taken = a session named "{ $name }" already exists
+sessions-diff-mode = leave the diff mode to switch sessions
+snippet-error = snippet "{ $name }": { $error }
+file-exists = a file named "{ $name }" already exists
+shorten-failed = could not shorten the link: { $error }
+short-link-no-endpoint = this is a short link, but no link shortener endpoint is configured in the settings
+short-link-invalid-id = this short link's id may only contain letters, digits, _ and -
+short-link-failed = could not load the short link: { $error }
+batch-decode-invalid = not a share link: { $error }
+result-hook-error = result hook: { $error }
+rule-diff-old-invalid = old rules: { $error }
+rule-diff-new-invalid = new rules: { $error }
+rule-pack-error = could not load the rule packs: { $error }
+workspace-error = workspace archive: { $error }
+snapshot-needs-run = run the patterns (without "Count only") before exporting a snapshot
+patterns-failed = { $count ->
+        [one] 1 pattern failed to compile, see the list below
+       *[other] { $count } patterns failed to compile, see the list below
+    }
+pattern-errors = { $count ->
+        [one] 1 pattern error
+       *[other] { $count } pattern errors
+    }
+patterns-skipped = { $count ->
+        [one] 1 pattern skipped, the other rules ran
+       *[other] { $count } patterns skipped, the other rules ran
+    }
+select-code-first = select some code in the subject editor first
+recovered-after-crash = the engine crashed in the last session. the patterns and every subject file were restored
+    { $error }
+crash-banner = the engine crashed: { $error }
+    the patterns and the open subject are saved and come back after a reload
+deploy-preset-invalid = the deployment's preset couldn't be loaded: { $error }
+reload-engine = Reload engine
+
+## settings
+
+shortener-endpoint = Link shortener endpoint
+rule-pack-index = Rule pack index (JSON)
+shortener-placeholder = https://example.com/api/links (optional)
+link-encoding = Share link encoding
+link-encoding-basex = Compact (default)
+link-encoding-base64url = base64url (safe characters)
+link-encoding-strict-safe = Strict-safe (letters and digits only)
+link-encoding-json = Plain JSON (debugging)
+scrub-shared-links = Scrub subjects before sharing
+    .title = Replace identifiers and strings in shared links with placeholders, keeping keywords and whatever the patterns mention
+link-compression = Share link compression
+compression-fast = Fast (larger links)
+compression-normal = Normal
+compression-max = Max (smallest links, slow for big subjects)
+ui-language = Interface language
+max-highlights = Highlights applied at once
+run-timeout = Run timeout in seconds (0 for none)
+run-slice-tokens = Tokens matched per frame, keeps the page responsive; matches over 200 lines may be cut (0 matches at once)
+result-hook = Result hook (JS)
+    .title = Body of a JavaScript function called with each match as `match`. Return false to drop it, a string to use as its highlight class, or {"{"} className, tooltip {"}"}
+result-hook-placeholder = if (match.name === "noisy") return false;
+context-lines = Context lines in the results
+    .title = Subject lines shown before and after the line of each match in the results list
+skip-invalid-patterns = Continue on pattern errors
+    .title = Run the patterns that compile and list the broken ones, instead of failing the run
+auto-run = Run automatically
+    .title = Run the patterns again shortly after the rules, the subjects or the language change. Otherwise a language change only clears the old results
+ignore-trailing-whitespace = Ignore trailing whitespace
+    .title = Don't run automatically for edits that only add or remove whitespace at the end of lines
+
+## language picker
+
+filter-languages = Filter languages…
+recent-language = recent
+lexer-c-like = C-like lexer
+lexer-python-like = Python-like lexer
+lexer-rust-like = Rust-like lexer
+lexer-family =
+    .title = The tokenizer the subject and the patterns go through for this language
+lexer-unknown = No lexer
+    .title = This language can't be matched: { $error }
+
+## regex scratchpad
+
+scratchpad-title = Regex scratchpad
+scratchpad-sample = Sample capture value
+scratchpad-regex = Regex
+scratchpad-match = match: { $value }
+scratchpad-group = group { $name }: { $value }
+scratchpad-unset = (unset)
+scratchpad-no-match = no match
+scratchpad-rule = rule { $n }
+scratchpad-capture-placeholder = capture, e.g. _FMT
+scratchpad-apply = Apply
+    .title = Write the regex into the rule's transform map
+scratchpad-note = Previewed with the browser's regex engine; exotic syntax may behave differently in the matcher.
+
+## self-test
+
+self-test = Self-test
+self-test-run = Run again
+self-test-summary = { $passed } of { $total } cases passed
+self-test-count = expected { $expected } matches, got { $got }: { $matches }
+self-test-mismatch = expected { $expected }, got { $got }
+self-test-report = Report, to paste into a bug report:
@@ -0,0 +1,108 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use lexer_search_lib::{engine::matcher::FullMatch, io::final_postprocess};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::io::{CompiledPatterns, Deadline, PatternError, PlaygroundConfig, PlaygroundError};
+
+/// one match, as returned to JS callers and used by the UI
+#[derive(Serialize, Deserialize, bincode::Encode, bincode::Decode, Clone, Debug)]
+pub struct SearchMatch {
+    /// index of the subject file, 0 being `PlaygroundConfig::subject`
+    pub file: usize,
+    pub name: String,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+    pub captures: BTreeMap<String, String>,
+}
+
+impl SearchMatch {
+    /// postprocess a match of the engine. `None` if the postprocessing drops it
+    pub fn from_engine(file: usize, result: FullMatch) -> Option<Self> {
+        final_postprocess(result).map(|result| Self::from_postprocessed(file, result))
+    }
+
+    /// `from_engine` for a match that went through `final_postprocess` already
+    pub fn from_postprocessed(file: usize, result: FullMatch) -> Self {
+        SearchMatch {
+            file,
+            name: result.name.clone(),
+            start_line: result.start.line,
+            start_col: result.start.column,
+            end_line: result.end.line,
+            end_col: result.end.column,
+            captures: result
+                .captures
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        String::from_utf8_lossy(k).to_string(),
+                        String::from_utf8_lossy(v).to_string(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// run the config, collecting every postprocessed match
+pub fn search(cfg: PlaygroundConfig) -> Result<Vec<SearchMatch>, PlaygroundError> {
+    search_with(cfg, false).map(|(matches, _)| matches)
+}
+
+/// `search`, optionally leaving out the patterns that don't compile. those are returned
+/// alongside the matches
+pub fn search_with(
+    cfg: PlaygroundConfig,
+    skip_invalid: bool,
+) -> Result<(Vec<SearchMatch>, Vec<PatternError>), PlaygroundError> {
+    let compiled = cfg.compile(skip_invalid)?;
+    let (matches, _) = search_compiled(cfg, &compiled, &Deadline::none())?;
+    Ok((matches, compiled.skipped))
+}
+
+/// `search` with patterns compiled earlier, see `PlaygroundConfig::compile`. also returns
+/// the units cut off by their `max_matches`. stops with what was found when `deadline`
+/// passes
+pub fn search_compiled(
+    cfg: PlaygroundConfig,
+    compiled: &CompiledPatterns,
+    deadline: &Deadline,
+) -> Result<(Vec<SearchMatch>, BTreeSet<String>), PlaygroundError> {
+    let mut matches = Vec::new();
+    let truncated = cfg.run_compiled(compiled, deadline, |file, result| {
+        matches.push(SearchMatch::from_postprocessed(file, result));
+    })?;
+    Ok((matches, truncated))
+}
+
+/// run the config with patterns compiled earlier, only counting the matches of each unit
+/// by name. nothing per match is retained, which keeps huge subjects cheap. also returns
+/// the units cut off by their `max_matches`. stops with the counts so far when `deadline`
+/// passes
+pub fn count(
+    cfg: PlaygroundConfig,
+    compiled: &CompiledPatterns,
+    deadline: &Deadline,
+) -> Result<(BTreeMap<String, usize>, BTreeSet<String>), PlaygroundError> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let truncated = cfg.run_compiled(compiled, deadline, |_, result| {
+        *counts.entry(result.name.clone()).or_default() += 1;
+    })?;
+    Ok((counts, truncated))
+}
+
+/// JS entry point: `config_json` is a `PlaygroundConfig` in JSON (`language`, `lhs` and
+/// optionally `files`), scanned against `subject`. resolves to an array of matches or
+/// throws the error message
+#[wasm_bindgen]
+pub fn run_search(config_json: &str, subject: &str) -> Result<JsValue, JsValue> {
+    let mut cfg: PlaygroundConfig =
+        serde_json::from_str(config_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    cfg.subject = subject.to_owned();
+    let matches = search(cfg).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    serde_wasm_bindgen::to_value(&matches).map_err(|e| JsValue::from_str(&e.to_string()))
+}
@@ -0,0 +1,217 @@
+//! decode several share links at once, e.g. rules collected from issues and chat messages.
+//! their rule sets are shown side by side as YAML, and picked units can be merged into the
+//! current rules
+
+use std::collections::BTreeSet;
+
+use web_sys::HtmlTextAreaElement;
+use yew::{
+    Callback, Component, Context, Html, InputEvent, MouseEvent, Properties, TargetCast, html,
+};
+
+use crate::{i18n::tr, io::MatchingUnit, rule_diff::config_from_link, rules::merge_rules};
+
+/// the share links in `text`: every word containing `#/`. pasted text without any is taken
+/// as bare fragments, one per word
+fn links(text: &str) -> Vec<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let linked: Vec<&str> = words.iter().copied().filter(|w| w.contains("#/")).collect();
+    let picked = if linked.is_empty() { words } else { linked };
+    picked.into_iter().map(str::to_string).collect()
+}
+
+/// one decoded link
+pub struct Decoded {
+    pub link: String,
+    /// the rules and their YAML
+    pub rules: Result<(Vec<MatchingUnit>, String), String>,
+}
+
+fn decode(link: String) -> Decoded {
+    let rules = config_from_link(&link).and_then(|cfg| {
+        let yaml = serde_yml::to_string(&cfg.lhs).map_err(|e| e.to_string())?;
+        Ok((cfg.lhs, yaml))
+    });
+    Decoded { link, rules }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct BatchDecodeDialogProps {
+    /// the patterns editor, which picked units are merged into
+    pub current_lhs: String,
+    /// the merged rules and the 1-based line of the first added one
+    pub on_merge: Callback<(String, Option<usize>)>,
+    pub on_close: Callback<()>,
+}
+
+pub enum BatchDecodeMsg {
+    SetText(String),
+    Decode,
+    /// link and unit index
+    ToggleUnit(usize, usize),
+    Merge,
+}
+
+/// a box for the links and a column per decoded link
+pub struct BatchDecodeDialog {
+    text: String,
+    decoded: Vec<Decoded>,
+    picked: BTreeSet<(usize, usize)>,
+    status: Option<Result<String, String>>,
+}
+
+impl BatchDecodeDialog {
+    fn view_column(&self, ctx: &Context<Self>, index: usize, decoded: &Decoded) -> Html {
+        let body = match &decoded.rules {
+            Err(e) => html! {
+                <div role="alert" style="color:#f48771;">{ tr!("batch-decode-invalid", error = e.as_str()) }</div>
+            },
+            Ok((units, yaml)) => html! {
+                <>
+                    { for units.iter().enumerate().map(|(unit, u)| {
+                        let name = if u.name.is_empty() { tr!("unnamed") } else { u.name.clone() };
+                        html! {
+                            <label style="display:flex; gap:4px; align-items:center;">
+                                <input type="checkbox" checked={self.picked.contains(&(index, unit))}
+                                    onchange={ctx.link().callback(move |_| BatchDecodeMsg::ToggleUnit(index, unit))} />
+                                { name }
+                            </label>
+                        }
+                    })}
+                    <pre style="margin:6px 0 0; white-space:pre-wrap; word-break:break-all; color:#ce9178;">{ yaml }</pre>
+                </>
+            },
+        };
+        html! {
+            <div style="flex:1; min-width:240px; border:1px solid #444; border-radius:4px; padding:6px;">
+                <div style="color:#888; overflow:hidden; text-overflow:ellipsis; white-space:nowrap; margin-bottom:4px;"
+                    title={decoded.link.clone()}>
+                    { tr!("batch-decode-link", number = index + 1) }
+                </div>
+                { body }
+            </div>
+        }
+    }
+}
+
+impl Component for BatchDecodeDialog {
+    type Message = BatchDecodeMsg;
+    type Properties = BatchDecodeDialogProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            text: String::new(),
+            decoded: Vec::new(),
+            picked: BTreeSet::new(),
+            status: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            BatchDecodeMsg::SetText(text) => {
+                self.text = text;
+                false
+            }
+            BatchDecodeMsg::Decode => {
+                self.decoded = links(&self.text).into_iter().map(decode).collect();
+                self.picked.clear();
+                self.status = None;
+                true
+            }
+            BatchDecodeMsg::ToggleUnit(link, unit) => {
+                if !self.picked.remove(&(link, unit)) {
+                    self.picked.insert((link, unit));
+                }
+                true
+            }
+            BatchDecodeMsg::Merge => {
+                let units: Vec<MatchingUnit> = self
+                    .picked
+                    .iter()
+                    .filter_map(|&(link, unit)| {
+                        let (units, _) = self.decoded.get(link)?.rules.as_ref().ok()?;
+                        units.get(unit).cloned()
+                    })
+                    .collect();
+                self.status = Some(match merge_rules(&ctx.props().current_lhs, units) {
+                    Ok(merged) => {
+                        let status = if merged.skipped.is_empty() {
+                            tr!("batch-decode-merged", count = merged.added)
+                        } else {
+                            tr!(
+                                "batch-decode-merged-skipped",
+                                count = merged.added,
+                                skipped = merged.skipped.join(", ")
+                            )
+                        };
+                        if merged.added > 0 {
+                            ctx.props().on_merge.emit((merged.lhs, merged.line));
+                        }
+                        self.picked.clear();
+                        Ok(status)
+                    }
+                    Err(e) => Err(e),
+                });
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! {
+            <div role="dialog" aria-label={tr!("batch-decode")} style="
+                position:fixed;
+                top:5vh;
+                left:5vw;
+                right:5vw;
+                max-height:85vh;
+                overflow-y:auto;
+                z-index:30;
+                background:#252526;
+                color:#ccc;
+                border:1px solid #444;
+                border-radius:4px;
+                padding:10px 12px;
+                font-size:12px;
+                box-shadow:0 4px 16px rgba(0,0,0,0.5);
+            ">
+                <div style="display:flex;">
+                    <span style="font-weight:bold;">{ tr!("batch-decode") }</span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={props.on_close.reform(|_: MouseEvent| ())}>{"×"}</span>
+                </div>
+                <textarea rows="4" style="width:100%; box-sizing:border-box; margin-top:8px;"
+                    placeholder={tr!("batch-decode-placeholder")}
+                    value={self.text.clone()}
+                    oninput={ctx.link().callback(|e: InputEvent| {
+                        BatchDecodeMsg::SetText(e.target_unchecked_into::<HtmlTextAreaElement>().value())
+                    })} />
+                <div style="display:flex; gap:8px; align-items:center; margin-top:4px;">
+                    <button onclick={ctx.link().callback(|_| BatchDecodeMsg::Decode)}>
+                        { tr!("batch-decode-run") }
+                    </button>
+                    <button disabled={self.picked.is_empty()}
+                        onclick={ctx.link().callback(|_| BatchDecodeMsg::Merge)}>
+                        { tr!("batch-decode-merge", count = self.picked.len()) }
+                    </button>
+                    { match &self.status {
+                        Some(Ok(status)) => html! { <span style="color:#8fd18f;">{ status }</span> },
+                        Some(Err(e)) => html! { <span role="alert" style="color:#f48771;">{ e }</span> },
+                        None => html! {},
+                    }}
+                </div>
+                { if self.decoded.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <div style="display:flex; gap:8px; margin-top:8px; overflow-x:auto; align-items:flex-start;">
+                            { for self.decoded.iter().enumerate().map(|(i, d)| self.view_column(ctx, i, d)) }
+                        </div>
+                    }
+                }}
+            </div>
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! defaults set by a deployment in `playground-config.json` next to `index.html`, fetched
+//! once at startup. every field may be left out; without the file the built-in defaults
+//! stay, so a fork only needs it for what it changes
+
+use std::cell::Cell;
+
+use gloo::net::http::Request;
+use monaco::sys::editor::BuiltinTheme;
+use serde::Deserialize;
+
+use crate::{io::PUBLIC_URL, settings::Settings};
+
+const CONFIG_FILE: &str = "playground-config.json";
+
+#[derive(Deserialize, Default, Clone, PartialEq, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeployConfig {
+    /// monaco language ids offered by the language picker. empty offers all of them
+    pub languages: Vec<String>,
+    /// monaco theme of the editors: "vs", "vs-dark" or "hc-black"
+    pub theme: Option<String>,
+    /// encoded config, the part of a share link after `#/`, loaded when the page is opened
+    /// without a link
+    pub preset: Option<String>,
+    /// settings for users who haven't saved their own, e.g. `max_highlights` or
+    /// `run_timeout_secs`. settings left out keep the built-in default, and the interface
+    /// language still follows the browser
+    pub settings: Option<Settings>,
+}
+
+thread_local! {
+    /// the theme new editors are created with
+    static THEME: Cell<BuiltinTheme> = const { Cell::new(BuiltinTheme::VsDark) };
+}
+
+/// the deployment's theme, dark by default
+pub fn theme() -> BuiltinTheme {
+    THEME.with(|t| t.get())
+}
+
+impl DeployConfig {
+    /// make `theme` the one new editors get. the editors already open are switched by
+    /// the caller, through monaco
+    pub fn apply_theme(&self) -> Result<Option<&str>, String> {
+        let Some(name) = &self.theme else {
+            return Ok(None);
+        };
+        let theme = BuiltinTheme::from_str(name)
+            .ok_or_else(|| format!("{}: unknown theme {:?}", CONFIG_FILE, name))?;
+        THEME.with(|t| t.set(theme));
+        Ok(Some(name))
+    }
+}
+
+/// the deployment's config, none if it has no `playground-config.json`
+pub async fn fetch() -> Result<Option<DeployConfig>, String> {
+    let base = PUBLIC_URL.trim_end_matches("#/");
+    let url = format!("/{}{}", base, CONFIG_FILE);
+    let response = match Request::get(&url).send().await {
+        Ok(v) => v,
+        Err(_) => return Ok(None),
+    };
+    if !response.ok() {
+        return Ok(None);
+    }
+    let text = response.text().await.map_err(|e| e.to_string())?;
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|e| format!("{}: {}", CONFIG_FILE, e))
+}
@@ -0,0 +1,139 @@
+/// pastes shorter than this aren't worth guessing at
+pub const MIN_PASTE_CHARS: usize = 200;
+/// a guess needs at least this many distinct signals
+const MIN_SCORE: usize = 3;
+
+/// monaco language id, fragments that are typical for it. each fragment found scores once
+const SIGNALS: &[(&str, &[&str])] = &[
+    (
+        "cpp",
+        &[
+            "#include",
+            "std::",
+            "int main(",
+            "printf(",
+            "nullptr",
+            "template<",
+            "malloc(",
+            "#define ",
+        ],
+    ),
+    (
+        "csharp",
+        &[
+            "using System",
+            "namespace ",
+            "Console.Write",
+            "{ get; set; }",
+            "async Task",
+            "string[] args",
+            "var ",
+        ],
+    ),
+    (
+        "go",
+        &[
+            "package ",
+            "func ",
+            ":= ",
+            "fmt.",
+            "err != nil",
+            "go func",
+            "chan ",
+            "import (",
+        ],
+    ),
+    (
+        "java",
+        &[
+            "public class ",
+            "public static void main",
+            "System.out",
+            "import java.",
+            "@Override",
+            "private final ",
+            "throws ",
+        ],
+    ),
+    (
+        "javascript",
+        &[
+            "function ",
+            "console.log",
+            "require(",
+            "=== ",
+            "undefined",
+            "module.exports",
+            "document.",
+            "const ",
+        ],
+    ),
+    (
+        "kotlin",
+        &[
+            "fun ",
+            "val ",
+            "println(",
+            "data class",
+            "companion object",
+            "?.let",
+            "when (",
+        ],
+    ),
+    (
+        "python",
+        &[
+            "def ",
+            "elif ",
+            "self.",
+            "None",
+            "__init__",
+            "import ",
+            "print(",
+            "    return",
+            "lambda ",
+        ],
+    ),
+    (
+        "rust",
+        &[
+            "fn ",
+            "let mut ",
+            "impl ",
+            "pub fn ",
+            "&mut ",
+            "#[derive",
+            "-> ",
+            "use std::",
+            "match ",
+            "Some(",
+        ],
+    ),
+    (
+        "typescript",
+        &[
+            "interface ",
+            ": string",
+            ": number",
+            "export ",
+            "readonly ",
+            "as const",
+            ": boolean",
+            "const ",
+        ],
+    ),
+];
+
+/// cheap guess at the language of a pasted snippet, as a monaco id. `None` unless one
+/// language clearly stands out
+pub fn guess_language(text: &str) -> Option<&'static str> {
+    let mut scores: Vec<(&str, usize)> = SIGNALS
+        .iter()
+        .map(|(lang, signals)| (*lang, signals.iter().filter(|s| text.contains(*s)).count()))
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+    match scores.as_slice() {
+        [(lang, best), (_, second), ..] if *best >= MIN_SCORE && best > second => Some(*lang),
+        _ => None,
+    }
+}
@@ -0,0 +1,83 @@
+// the Monaco diff editor of the subject diff mode. the monaco crate only wraps the plain
+// editor, so this goes through the global namespace
+
+export function create_diff_editor_js(container, original, modified, language) {
+    const monaco = globalThis.monaco;
+    if (!monaco || !container) return null;
+
+    const editor = monaco.editor.createDiffEditor(container, {
+        automaticLayout: true,
+        originalEditable: true,
+        renderSideBySide: true,
+    });
+    editor.setModel({
+        original: monaco.editor.createModel(original, language),
+        modified: monaco.editor.createModel(modified, language),
+    });
+    editor.lexerSearchDecorations = { original: [], modified: [] };
+    return editor;
+}
+
+export function dispose_diff_editor_js(editor) {
+    if (!editor) return;
+
+    const model = editor.getModel();
+    editor.dispose();
+    if (model) {
+        model.original.dispose();
+        model.modified.dispose();
+    }
+}
+
+export function set_diff_language_js(editor, language) {
+    const monaco = globalThis.monaco;
+    if (!monaco || !editor) return;
+
+    const model = editor.getModel();
+    monaco.editor.setModelLanguage(model.original, language);
+    monaco.editor.setModelLanguage(model.modified, language);
+}
+
+export function diff_editor_value_js(editor, modified) {
+    if (!editor) return "";
+
+    const model = editor.getModel();
+    return (modified ? model.modified : model.original).getValue();
+}
+
+export function set_diff_highlights_js(editor, modified, elems) {
+    if (!editor) return;
+
+    const side = modified ? editor.getModifiedEditor() : editor.getOriginalEditor();
+    const key = modified ? "modified" : "original";
+    editor.lexerSearchDecorations[key] = side.deltaDecorations(
+        editor.lexerSearchDecorations[key],
+        elems.map(e => ({
+            range: {
+                startLineNumber: e.start_line,
+                startColumn: e.start_col,
+                endLineNumber: e.end_line,
+                endColumn: e.end_col,
+            },
+            options: {
+                inlineClassName: e.class_name,
+                hoverMessage: e.text ? [{ value: e.text }] : undefined,
+            },
+        })),
+    );
+}
+
+export function diff_reveal_js(editor, start_line, start_col, end_line, end_col) {
+    if (!editor) return;
+
+    const side = editor.getModifiedEditor();
+    const range = {
+        startLineNumber: start_line,
+        startColumn: start_col,
+        endLineNumber: end_line,
+        endColumn: end_col,
+    };
+    side.revealRangeInCenter(range);
+    side.setSelection(range);
+    side.focus();
+}
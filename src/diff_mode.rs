@@ -0,0 +1,178 @@
+//! the subject diff mode: the subject pane becomes a Monaco diff editor, both versions are
+//! scanned and the matches found in only one of them are marked, like a diff scan in CI
+//! would flag them. the original version is file 0 of the run, the modified one file 1
+
+use std::collections::BTreeMap;
+
+use wasm_bindgen::{JsValue, prelude::*};
+use web_sys::Element;
+use yew::{Callback, Component, Context, Html, NodeRef, Properties, html};
+
+use crate::{api::SearchMatch, positions::char_index};
+
+#[wasm_bindgen(module = "/src/diff_helper.js")]
+extern "C" {
+    fn create_diff_editor_js(
+        container: &Element,
+        original: &str,
+        modified: &str,
+        language: &str,
+    ) -> JsValue;
+    fn dispose_diff_editor_js(editor: &JsValue);
+    fn set_diff_language_js(editor: &JsValue, language: &str);
+    fn diff_editor_value_js(editor: &JsValue, modified: bool) -> String;
+    fn set_diff_highlights_js(editor: &JsValue, modified: bool, elements: &JsValue);
+    fn diff_reveal_js(
+        editor: &JsValue,
+        start_line: usize,
+        start_col: usize,
+        end_line: usize,
+        end_col: usize,
+    );
+}
+
+/// `SearchMatch::file` of the original version
+pub const ORIGINAL: usize = 0;
+/// `SearchMatch::file` of the modified version
+pub const MODIFIED: usize = 1;
+
+/// the original and the modified text
+pub fn texts(editor: &JsValue) -> (String, String) {
+    (
+        diff_editor_value_js(editor, false),
+        diff_editor_value_js(editor, true),
+    )
+}
+
+/// replace the highlights of one side with serialized `HighlightElement`s
+pub fn set_highlights(editor: &JsValue, modified: bool, elements: &JsValue) {
+    set_diff_highlights_js(editor, modified, elements);
+}
+
+/// scroll the modified side to a match and select it
+pub fn reveal(editor: &JsValue, m: &SearchMatch) {
+    diff_reveal_js(editor, m.start_line, m.start_col, m.end_line, m.end_col);
+}
+
+/// the text a match covers
+fn matched_text(lines: &[&str], m: &SearchMatch) -> String {
+    let line = |n: usize| lines.get(n.max(1) - 1).copied().unwrap_or("");
+    let mut out = String::new();
+    for n in m.start_line..=m.end_line {
+        let text = line(n);
+        let from = if n == m.start_line {
+            char_index(text, m.start_col)
+        } else {
+            0
+        };
+        let to = if n == m.end_line {
+            char_index(text, m.end_col)
+        } else {
+            usize::MAX
+        };
+        out.extend(text.chars().skip(from).take(to.saturating_sub(from)));
+        if n != m.end_line {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// for each match, whether the other version has no match of the same rule with the same
+/// captures and text. positions don't count, so code that only moved isn't flagged.
+/// repeated matches are paired up one by one
+pub fn one_sided(matches: &[SearchMatch], original: &str, modified: &str) -> Vec<bool> {
+    let lines = [
+        original.lines().collect::<Vec<_>>(),
+        modified.lines().collect::<Vec<_>>(),
+    ];
+    let keys: Vec<_> = matches
+        .iter()
+        .map(|m| {
+            let side = usize::from(m.file == MODIFIED);
+            (side, (&m.name, &m.captures, matched_text(&lines[side], m)))
+        })
+        .collect();
+    // of each key, how many the other side still has to pair with
+    let mut unpaired: [BTreeMap<_, usize>; 2] = Default::default();
+    for (side, key) in &keys {
+        *unpaired[*side].entry(key.clone()).or_default() += 1;
+    }
+    keys.into_iter()
+        .map(|(side, key)| match unpaired[1 - side].get_mut(&key) {
+            Some(n) if *n > 0 => {
+                *n -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DiffSubjectEditorProps {
+    /// initial texts, later edits stay in the editor
+    pub original: String,
+    pub modified: String,
+    pub language: String,
+    pub on_created: Callback<JsValue>,
+}
+
+/// Monaco diff editor, created once the container is in the DOM
+pub struct DiffSubjectEditor {
+    container: NodeRef,
+    editor: Option<JsValue>,
+}
+
+impl Component for DiffSubjectEditor {
+    type Message = ();
+    type Properties = DiffSubjectEditorProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            container: NodeRef::default(),
+            editor: None,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().language != old_props.language {
+            if let Some(editor) = &self.editor {
+                set_diff_language_js(editor, &ctx.props().language);
+            }
+        }
+        false
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        let Some(container) = self.container.cast::<Element>() else {
+            return;
+        };
+        let props = ctx.props();
+        let editor = create_diff_editor_js(
+            &container,
+            &props.original,
+            &props.modified,
+            &props.language,
+        );
+        if !editor.is_null() {
+            props.on_created.emit(editor.clone());
+            self.editor = Some(editor);
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(editor) = self.editor.take() {
+            dispose_diff_editor_js(&editor);
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {
+            <div ref={self.container.clone()} class="full-height" />
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::{BTreeMap, HashMap};
+
+use lexer_search_lib::io::Language;
+
+use crate::{
+    api::SearchMatch,
+    io::{CompiledPatterns, Deadline, MatchingUnit, PlaygroundConfig, engine_tokens},
+    positions::{ColumnMap, char_index},
+};
+
+/// above this many subject tokens the token mapping isn't attempted
+const MAX_ALIGN_TOKENS: usize = 400;
+/// longer patterns are only tried up to this many tokens
+const MAX_PREFIX_TOKENS: usize = 64;
+
+/// how a match came about, reconstructed by re-running its rule one pattern at a time
+pub struct Explanation {
+    pub unit: String,
+    /// the pattern, as written in the rule, that reproduces the match. `None` if no single
+    /// pattern does on its own
+    pub pattern: Option<String>,
+    pub matched_text: String,
+    pub subject_tokens: Vec<String>,
+    /// each pattern token with the subject tokens it consumed
+    pub alignment: Option<Vec<(String, Vec<String>)>>,
+    pub captures: BTreeMap<String, String>,
+}
+
+/// how far one pattern of a rule without matches got
+pub struct NearMiss {
+    pub pattern: String,
+    /// tokens of the longest prefix of the pattern that matches somewhere
+    pub matched_tokens: usize,
+    pub total_tokens: usize,
+    /// the token after that prefix, the one the pattern fails at
+    pub failed_at: Option<String>,
+    /// the first match of that prefix, in UTF-16 columns. it ends where the pattern got stuck
+    pub reached: Option<SearchMatch>,
+}
+
+/// the rules and subject languages of a run. its matches are explained against these
+/// rather than the editors, which may have changed since. the single patterns and prefixes
+/// compiled along the way are kept for the next explanation
+pub struct RunSource {
+    units: Vec<MatchingUnit>,
+    /// monaco ids, indexed like `SearchMatch::file`
+    languages: Vec<&'static str>,
+    /// by unit index, language and pattern. `None` for a pattern that doesn't compile
+    graphs: HashMap<(usize, &'static str, String), Option<CompiledPatterns>>,
+}
+
+impl RunSource {
+    /// `cfg` as it is run, before its subjects are consumed
+    pub fn new(cfg: &PlaygroundConfig) -> Self {
+        Self {
+            units: cfg.lhs.clone(),
+            languages: cfg.subject_languages(),
+            graphs: HashMap::new(),
+        }
+    }
+
+    fn language(&self, file: usize) -> Result<(&'static str, Language), String> {
+        let id = *self
+            .languages
+            .get(file)
+            .ok_or("the file of this match wasn't part of the run")?;
+        Ok((id, serde_yml::from_str(id).map_err(|e| e.to_string())?))
+    }
+
+    /// the unit at `unit_index` cut down to `pattern`, compiled for `language`
+    fn graph(
+        &mut self,
+        unit_index: usize,
+        language: &'static str,
+        pattern: &str,
+    ) -> Option<&CompiledPatterns> {
+        let unit = self.units.get(unit_index)?;
+        self.graphs
+            .entry((unit_index, language, pattern.to_string()))
+            .or_insert_with(|| {
+                PlaygroundConfig {
+                    subject: String::new(),
+                    language: serde_yml::from_str(language).ok()?,
+                    lhs: vec![MatchingUnit {
+                        patterns: vec![pattern.to_string()],
+                        ..unit.clone()
+                    }],
+                    files: Vec::new(),
+                }
+                .compile(false)
+                .ok()
+            })
+            .as_ref()
+    }
+
+    /// the matches of the unit at `unit_index` cut down to `pattern` in the subject `file`,
+    /// in UTF-16 columns. none if the pattern doesn't compile
+    fn scan(
+        &mut self,
+        unit_index: usize,
+        pattern: &str,
+        file: usize,
+        subject: &str,
+    ) -> Result<Vec<SearchMatch>, String> {
+        let (id, language) = self.language(file)?;
+        let Some(compiled) = self.graph(unit_index, id, pattern) else {
+            return Ok(Vec::new());
+        };
+        let mut matches = Vec::new();
+        compiled.scan(&language, subject.to_string(), &Deadline::none(), |_, m| {
+            matches.extend(SearchMatch::from_engine(file, m))
+        })?;
+        let columns = ColumnMap::new(subject);
+        matches.iter_mut().for_each(|m| columns.convert(m));
+        Ok(matches)
+    }
+
+    /// explain `m`, which the run found in `subject`. a rule with several patterns is
+    /// re-run one pattern at a time over that subject alone, to find the one that matched
+    pub fn explain(&mut self, m: &SearchMatch, subject: &str) -> Result<Explanation, String> {
+        let (_, language) = self.language(m.file)?;
+        let matched_text = slice_range(subject, m.start_line, m.start_col, m.end_line, m.end_col);
+        let candidates: Vec<(usize, String)> = self
+            .units
+            .iter()
+            .enumerate()
+            .filter(|(_, unit)| unit.enabled && unit.name == m.name)
+            .flat_map(|(i, unit)| unit.patterns.iter().map(move |p| (i, p.clone())))
+            .collect();
+
+        let mut pattern = None;
+        if candidates.len() == 1 {
+            // the run found the match, so its only pattern did
+            pattern = candidates.into_iter().next().map(|(_, p)| p);
+        } else {
+            for (unit_index, candidate) in candidates {
+                let reproduced = self
+                    .scan(unit_index, &candidate, m.file, subject)?
+                    .iter()
+                    .any(|other| {
+                        (
+                            other.start_line,
+                            other.start_col,
+                            other.end_line,
+                            other.end_col,
+                        ) == (m.start_line, m.start_col, m.end_line, m.end_col)
+                            && other.captures == m.captures
+                    });
+                if reproduced {
+                    pattern = Some(candidate);
+                    break;
+                }
+            }
+        }
+
+        let subject_tokens = tokens(&language, &matched_text, false)?;
+        let alignment = match &pattern {
+            Some(p) if subject_tokens.len() <= MAX_ALIGN_TOKENS => {
+                align(&tokens(&language, p, true)?, &subject_tokens, &m.captures)
+            }
+            _ => None,
+        };
+
+        Ok(Explanation {
+            unit: m.name.clone(),
+            pattern,
+            matched_text,
+            subject_tokens,
+            alignment,
+            captures: m.captures.clone(),
+        })
+    }
+
+    /// the first match of the unit at `unit_index` cut down to `pattern` in any of `subjects`
+    fn first_match(
+        &mut self,
+        unit_index: usize,
+        pattern: &str,
+        subjects: &[&str],
+    ) -> Result<Option<SearchMatch>, String> {
+        for (file, subject) in subjects.iter().enumerate() {
+            if let Some(m) = self
+                .scan(unit_index, pattern, file, subject)?
+                .into_iter()
+                .next()
+            {
+                return Ok(Some(m));
+            }
+        }
+        Ok(None)
+    }
+
+    /// for each pattern of the unit at `unit_index`, named `name` when the run had it, the
+    /// longest prefix that matches one of the run's `subjects`. prefixes are cut on the
+    /// engine's tokens of the main subject's language. a prefix that doesn't compile, e.g.
+    /// one cutting a group in half, counts as no match
+    pub fn explain_no_match(
+        &mut self,
+        unit_index: usize,
+        name: &str,
+        subjects: &[&str],
+    ) -> Result<Vec<NearMiss>, String> {
+        let patterns = self
+            .units
+            .get(unit_index)
+            .filter(|u| u.name == name)
+            .ok_or("the rules were edited since the run")?
+            .patterns
+            .clone();
+        let (_, language) = self.language(0)?;
+
+        let mut misses = Vec::with_capacity(patterns.len());
+        for pattern in patterns {
+            let spans = engine_tokens(&language, &pattern, true)?;
+            let token = |i: usize| {
+                spans
+                    .get(i)
+                    .and_then(|&(s, e)| pattern.get(s..e))
+                    .map(str::to_string)
+            };
+            // a trailing `...` only adds ambiguity
+            let cuts: Vec<usize> = (1..=spans.len().min(MAX_PREFIX_TOKENS))
+                .filter(|&n| token(n - 1).as_deref() != Some("..."))
+                .collect();
+            // a prefix that matches means the shorter ones do too, so the longest one is
+            // found with a binary search. `cuts[..lo]` match, `cuts[hi..]` don't
+            let (mut lo, mut hi) = (0, cuts.len());
+            let mut reached = None;
+            let mut matched_tokens = 0;
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let n = cuts[mid];
+                let found = match pattern.get(..spans[n - 1].1) {
+                    Some(prefix) => self.first_match(unit_index, prefix, subjects)?,
+                    None => None,
+                };
+                match found {
+                    Some(m) => {
+                        reached = Some(m);
+                        matched_tokens = n;
+                        lo = mid + 1;
+                    }
+                    None => hi = mid,
+                }
+            }
+            misses.push(NearMiss {
+                failed_at: token(matched_tokens),
+                total_tokens: spans.len(),
+                matched_tokens,
+                reached,
+                pattern,
+            });
+        }
+        Ok(misses)
+    }
+}
+
+/// the engine's tokens of `text`, lexed as a pattern or a subject of `language`
+fn tokens(language: &Language, text: &str, pattern: bool) -> Result<Vec<String>, String> {
+    Ok(engine_tokens(language, text, pattern)?
+        .into_iter()
+        .filter_map(|(s, e)| text.get(s..e))
+        .map(str::to_string)
+        .collect())
+}
+
+/// the text between two 1-based (line, UTF-16 column) positions, end exclusive
+fn slice_range(
+    text: &str,
+    start_line: usize,
+    start_col: usize,
+    end_line: usize,
+    end_col: usize,
+) -> String {
+    let mut out = String::new();
+    for (i, line) in text.lines().enumerate().skip(start_line.saturating_sub(1)) {
+        let line_no = i + 1;
+        if line_no > end_line {
+            break;
+        }
+        let from = if line_no == start_line {
+            char_index(line, start_col)
+        } else {
+            0
+        };
+        let to = if line_no == end_line {
+            char_index(line, end_col)
+        } else {
+            usize::MAX
+        };
+        if line_no != start_line {
+            out.push('\n');
+        }
+        out.extend(line.chars().skip(from).take(to.saturating_sub(from)));
+    }
+    out
+}
+
+/// match pattern tokens to subject tokens: literals consume an equal token, `$NAME` consumes
+/// the tokens spelling its captured value and `...` consumes as few tokens as possible
+fn align(
+    pattern: &[String],
+    subject: &[String],
+    captures: &BTreeMap<String, String>,
+) -> Option<Vec<(String, Vec<String>)>> {
+    // fits[p][s]: `pattern[p..]` consumes exactly `subject[s..]`, filled from the back so
+    // every state is settled once
+    let (np, ns) = (pattern.len(), subject.len());
+    let mut fits = vec![vec![false; ns + 1]; np + 1];
+    fits[np][ns] = true;
+    for p in (0..np).rev() {
+        for s in (0..=ns).rev() {
+            fits[p][s] = steps(&pattern[p], &subject[s..], captures)
+                .into_iter()
+                .any(|n| fits[p + 1][s + n]);
+        }
+    }
+    if !fits[0][0] {
+        return None;
+    }
+    let mut out = Vec::with_capacity(np);
+    let mut s = 0;
+    for (p, head) in pattern.iter().enumerate() {
+        let n = steps(head, &subject[s..], captures)
+            .into_iter()
+            .find(|&n| fits[p + 1][s + n])?;
+        out.push((head.clone(), subject[s..s + n].to_vec()));
+        s += n;
+    }
+    Some(out)
+}
+
+/// how many of the leading `subject` tokens the pattern token `head` can consume, fewest
+/// first
+fn steps(head: &str, subject: &[String], captures: &BTreeMap<String, String>) -> Vec<usize> {
+    if head == "..." {
+        return (0..=subject.len()).collect();
+    }
+    if head.len() > 1 && head.starts_with('$') {
+        let Some(value) = captures.get(head).or_else(|| captures.get(&head[1..])) else {
+            return (1..=subject.len()).collect();
+        };
+        let value: String = value.split_whitespace().collect();
+        let mut spelled = String::new();
+        for (i, token) in subject.iter().enumerate() {
+            spelled.push_str(token);
+            if !value.starts_with(&spelled) {
+                break;
+            }
+            if spelled.len() == value.len() {
+                return vec![i + 1];
+            }
+        }
+        return Vec::new();
+    }
+    if subject.first().is_some_and(|t| t == head) {
+        vec![1]
+    } else {
+        Vec::new()
+    }
+}
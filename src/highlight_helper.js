@@ -1,8 +1,15 @@
 let currentDecorations = [];
+let highlightGeneration = 0;
+
+// decorations are applied a page per animation frame so huge result sets don't freeze the tab
+const HIGHLIGHT_PAGE_SIZE = 2000;
 
 export function highlight_ranges_js(editor, elems) {
     if (!editor) return;
 
+    const generation = ++highlightGeneration;
+    currentDecorations = editor.deltaDecorations(currentDecorations, []);
+
     const newDecorations = elems.map(e => ({
         range: {
             startLineNumber: e.start_line,
@@ -13,9 +20,316 @@ export function highlight_ranges_js(editor, elems) {
         options: {
             inlineClassName: e.class_name,
             hoverMessage: e.text ? [{ value: e.text }] : undefined,
+            before: e.label ? { content: e.label, inlineClassName: "capture-label" } : undefined,
         }
     }));
 
-    currentDecorations =
-        editor.deltaDecorations(currentDecorations, newDecorations);
+    const applyPage = start => {
+        // a newer call replaced these highlights
+        if (generation !== highlightGeneration) return;
+
+        const page = newDecorations.slice(start, start + HIGHLIGHT_PAGE_SIZE);
+        currentDecorations = currentDecorations.concat(editor.deltaDecorations([], page));
+        if (start + HIGHLIGHT_PAGE_SIZE < newDecorations.length) {
+            requestAnimationFrame(() => applyPage(start + HIGHLIGHT_PAGE_SIZE));
+        }
+    };
+    applyPage(0);
+}
+
+// inline capture annotations. uses monaco's inlay hints when the global namespace is
+// available, otherwise falls back to injected text decorations
+let inlayHints = new Map();
+let inlayHintsChanged = null;
+let inlayDecorations = [];
+
+function ensureInlayHintsProvider() {
+    const monaco = globalThis.monaco;
+    if (!monaco || !monaco.languages.registerInlayHintsProvider) return false;
+    if (inlayHintsChanged) return true;
+
+    inlayHintsChanged = new monaco.Emitter();
+    monaco.languages.registerInlayHintsProvider("*", {
+        onDidChangeInlayHints: inlayHintsChanged.event,
+        provideInlayHints(model, range) {
+            const hints = (inlayHints.get(model.uri.toString()) || [])
+                .filter(h => h.line >= range.startLineNumber && h.line <= range.endLineNumber)
+                .map(h => ({
+                    position: { lineNumber: h.line, column: h.col },
+                    label: h.label,
+                    paddingLeft: true,
+                }));
+            return { hints, dispose() {} };
+        },
+    });
+    return true;
+}
+
+export function set_inlay_hints_js(editor, hints) {
+    if (!editor) return;
+    const model = editor.getModel();
+    if (!model) return;
+
+    if (ensureInlayHintsProvider()) {
+        inlayHints = new Map([[model.uri.toString(), hints]]);
+        inlayHintsChanged.fire();
+        return;
+    }
+
+    inlayDecorations = editor.deltaDecorations(inlayDecorations, hints.map(h => ({
+        range: {
+            startLineNumber: h.line,
+            startColumn: h.col,
+            endLineNumber: h.line,
+            endColumn: h.col,
+        },
+        options: {
+            after: { content: h.label, inlineClassName: "capture-inlay-hint" },
+        },
+    })));
+}
+
+// lhs schema problems, as monaco markers when the global namespace is available, otherwise
+// as squiggly decorations
+const markerDecorations = new WeakMap();
+
+export function set_markers_js(editor, markers) {
+    if (!editor) return;
+    const model = editor.getModel();
+    if (!model) return;
+
+    const toRange = m => {
+        const line = Math.min(m.line, model.getLineCount());
+        return {
+            startLineNumber: line,
+            startColumn: m.col,
+            endLineNumber: line,
+            endColumn: model.getLineMaxColumn(line),
+        };
+    };
+
+    const monaco = globalThis.monaco;
+    if (monaco && monaco.editor.setModelMarkers) {
+        monaco.editor.setModelMarkers(model, "lexer-search-schema", markers.map(m => ({
+            ...toRange(m),
+            message: `${m.path}: ${m.message}`,
+            severity: m.warning ? monaco.MarkerSeverity.Warning : monaco.MarkerSeverity.Error,
+        })));
+        return;
+    }
+
+    const previous = markerDecorations.get(editor) || [];
+    markerDecorations.set(editor, editor.deltaDecorations(previous, markers.map(m => ({
+        range: toRange(m),
+        options: {
+            inlineClassName: m.warning ? "schema-warning" : "schema-error",
+            hoverMessage: [{ value: `${m.path}: ${m.message}` }],
+        },
+    }))));
+}
+
+// hover docs and snippets for the pattern language in the yaml (lhs) editor
+// left borders in the pattern editor, one per unit block
+const unitDecorations = new WeakMap();
+
+export function set_unit_borders_js(editor, blocks) {
+    if (!editor) return;
+
+    const previous = unitDecorations.get(editor) || [];
+    unitDecorations.set(editor, editor.deltaDecorations(previous, blocks.map(b => ({
+        range: {
+            startLineNumber: b.start_line,
+            startColumn: 1,
+            endLineNumber: b.end_line,
+            endColumn: 1,
+        },
+        options: {
+            isWholeLine: true,
+            linesDecorationsClassName: b.class_name,
+        },
+    }))));
+}
+
+let patternDocsRegistered = false;
+
+export function register_pattern_docs_js(docs, guide_url) {
+    const monaco = globalThis.monaco;
+    if (!monaco || patternDocsRegistered) return;
+    patternDocsRegistered = true;
+
+    const footer = `[Pattern guide](${guide_url})`;
+
+    monaco.languages.registerHoverProvider("yaml", {
+        provideHover(model, position) {
+            const line = model.getLineContent(position.lineNumber);
+            for (const doc of docs) {
+                const regex = new RegExp(doc.token_regex, "g");
+                let m;
+                while ((m = regex.exec(line)) !== null) {
+                    const start = m.index + 1;
+                    const end = start + m[0].length;
+                    if (position.column >= start && position.column <= end) {
+                        return {
+                            range: {
+                                startLineNumber: position.lineNumber,
+                                startColumn: start,
+                                endLineNumber: position.lineNumber,
+                                endColumn: end,
+                            },
+                            contents: [{ value: `\`${m[0]}\`` }, { value: doc.doc }, { value: footer }],
+                        };
+                    }
+                }
+            }
+            return null;
+        },
+    });
+
+    monaco.languages.registerCompletionItemProvider("yaml", {
+        triggerCharacters: ["$", "&", "."],
+        provideCompletionItems(model, position) {
+            const word = model.getWordUntilPosition(position);
+            const range = {
+                startLineNumber: position.lineNumber,
+                startColumn: word.startColumn,
+                endLineNumber: position.lineNumber,
+                endColumn: word.endColumn,
+            };
+            return {
+                suggestions: docs.map(doc => ({
+                    label: doc.label,
+                    kind: monaco.languages.CompletionItemKind.Snippet,
+                    insertText: doc.snippet,
+                    insertTextRules: monaco.languages.CompletionItemInsertTextRule.InsertAsSnippet,
+                    documentation: { value: `${doc.doc}\n\n${footer}` },
+                    range,
+                })),
+            };
+        },
+    });
+}
+
+// first match of `pattern` in `sample`, with its groups, for the regex scratchpad
+export function regex_preview_js(pattern, sample) {
+    let regex;
+    try {
+        regex = new RegExp(pattern);
+    } catch (e) {
+        return { error: e.message, matched: null, groups: [] };
+    }
+    const m = regex.exec(sample);
+    if (!m) return { error: null, matched: null, groups: [] };
+
+    const groups = m.slice(1).map((value, i) => [String(i + 1), value === undefined ? null : value]);
+    for (const [name, value] of Object.entries(m.groups || {})) {
+        groups.push([name, value === undefined ? null : value]);
+    }
+    return { error: null, matched: m[0], groups };
+}
+
+export function on_content_change_js(editor, callback) {
+    if (!editor) return;
+
+    editor.onDidChangeModelContent(() => callback());
+}
+
+export function on_mouse_down_js(editor, callback) {
+    if (!editor) return;
+
+    editor.onMouseDown(e => {
+        const position = e.target && e.target.position;
+        if (position) {
+            callback(position.lineNumber, position.column);
+        }
+    });
+}
+
+export function on_cursor_change_js(editor, callback) {
+    if (!editor) return;
+
+    editor.onDidChangeCursorPosition(e => {
+        callback(e.position.lineNumber, e.position.column);
+    });
+}
+
+// calls back with the pasted text
+export function on_paste_js(editor, callback) {
+    if (!editor) return;
+
+    editor.onDidPaste(e => {
+        const model = editor.getModel();
+        if (model) callback(model.getValueInRange(e.range));
+    });
+}
+
+// the primary selection, or null when nothing is selected
+export function selection_js(editor) {
+    if (!editor) return null;
+    const model = editor.getModel();
+    const selection = editor.getSelection();
+    if (!model || !selection || selection.isEmpty()) return null;
+
+    return {
+        start_line: selection.startLineNumber,
+        start_col: selection.startColumn,
+        end_line: selection.endLineNumber,
+        end_col: selection.endColumn,
+        text: model.getValueInRange(selection),
+    };
+}
+
+export function reveal_range_js(editor, start_line, start_col, end_line, end_col) {
+    if (!editor) return;
+
+    const range = {
+        startLineNumber: start_line,
+        startColumn: start_col,
+        endLineNumber: end_line,
+        endColumn: end_col,
+    };
+    editor.revealRangeInCenter(range);
+    editor.setSelection(range);
+    editor.focus();
+}
+
+// keeps the cursor and focus where they are, for following the results list
+export function center_range_js(editor, start_line, start_col, end_line, end_col) {
+    if (!editor) return;
+
+    editor.revealRangeInCenter(
+        {
+            startLineNumber: start_line,
+            startColumn: start_col,
+            endLineNumber: end_line,
+            endColumn: end_col,
+        },
+        1, // ScrollType.Immediate
+    );
+}
+
+// the theme is global to monaco, so this switches every editor
+export function set_theme_js(theme) {
+    if (monaco && monaco.editor.setTheme) {
+        monaco.editor.setTheme(theme);
+    }
+}
+
+const styleClasses = new Set();
+let styleSheet = null;
+
+export function ensure_style_class_js(class_name, properties) {
+    if (styleClasses.has(class_name)) return;
+
+    if (!styleSheet) {
+        const element = document.createElement("style");
+        document.head.appendChild(element);
+        styleSheet = element.sheet;
+    }
+
+    const index = styleSheet.insertRule(`.${class_name} {}`, styleSheet.cssRules.length);
+    const style = styleSheet.cssRules[index].style;
+    for (const [property, value] of Object.entries(properties)) {
+        style.setProperty(property, value);
+    }
+    styleClasses.add(class_name);
 }
@@ -0,0 +1,107 @@
+//! the result hook: a JS function body from the settings, called with each match of a run.
+//! it can return `false` to drop the match, a string to use as its highlight class, or an
+//! object `{ className, tooltip }`. anything else keeps the match as it is. meant for
+//! deployments that need their own presentation without forking the UI
+
+use serde::Serialize;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::{HighlightElement, api::SearchMatch, i18n::tr};
+
+/// what the hook made of a match
+#[derive(Default, PartialEq, Debug)]
+pub struct HookStyle {
+    pub class_name: Option<String>,
+    pub tooltip: Option<String>,
+}
+
+impl HookStyle {
+    pub fn apply(&self, elem: &mut HighlightElement) {
+        if let Some(class_name) = &self.class_name {
+            elem.class_name = class_name.clone();
+        }
+        if let Some(tooltip) = &self.tooltip {
+            elem.text = Some(tooltip.clone());
+        }
+    }
+}
+
+pub struct ResultHook {
+    function: js_sys::Function,
+}
+
+/// a thrown JS value as text
+fn describe(e: JsValue) -> String {
+    e.dyn_ref::<js_sys::Error>()
+        .map(|e| String::from(e.message()))
+        .or_else(|| e.as_string())
+        .unwrap_or_else(|| format!("{:?}", e))
+}
+
+impl ResultHook {
+    /// compile the function body, none for an empty one. the match is its `match` argument
+    pub fn new(source: &str) -> Result<Option<Self>, String> {
+        if source.trim().is_empty() {
+            return Ok(None);
+        }
+        // `Function::new_with_args` can't report a syntax error, eval can
+        let function = js_sys::eval(&format!("(function (match) {{\n{}\n}})", source))
+            .map_err(|e| tr!("result-hook-error", error = describe(e)))?;
+        function
+            .dyn_into::<js_sys::Function>()
+            .map(|function| Some(Self { function }))
+            .map_err(|_| tr!("result-hook-error", error = "not a function"))
+    }
+
+    /// the hook's verdict on `m`: `None` drops it
+    pub fn call(&self, m: &SearchMatch) -> Result<Option<HookStyle>, String> {
+        // plain objects rather than `Map`s, so `match.captures.x` works
+        let arg = m
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(|e| e.to_string())?;
+        let out = self
+            .function
+            .call1(&JsValue::NULL, &arg)
+            .map_err(|e| tr!("result-hook-error", error = describe(e)))?;
+        if out == JsValue::FALSE {
+            return Ok(None);
+        }
+        if let Some(class_name) = out.as_string() {
+            return Ok(Some(HookStyle {
+                class_name: Some(class_name),
+                tooltip: None,
+            }));
+        }
+        if !out.is_object() {
+            return Ok(Some(HookStyle::default()));
+        }
+        let field = |name: &str| {
+            js_sys::Reflect::get(&out, &JsValue::from_str(name))
+                .ok()
+                .and_then(|v| v.as_string())
+        };
+        Ok(Some(HookStyle {
+            class_name: field("className"),
+            tooltip: field("tooltip"),
+        }))
+    }
+
+    /// run the hook over `matches`, dropping the ones it rejects. returns each kept match's
+    /// style, in order
+    pub fn filter(&self, matches: &mut Vec<SearchMatch>) -> Result<Vec<HookStyle>, String> {
+        let mut styles = Vec::with_capacity(matches.len());
+        let mut verdicts = Vec::with_capacity(matches.len());
+        for m in matches.iter() {
+            verdicts.push(self.call(m)?);
+        }
+        let mut verdicts = verdicts.into_iter();
+        matches.retain(|_| match verdicts.next().flatten() {
+            Some(style) => {
+                styles.push(style);
+                true
+            }
+            None => false,
+        });
+        Ok(styles)
+    }
+}
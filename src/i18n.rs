@@ -0,0 +1,131 @@
+//! UI strings. each locale is a fluent file under `locales/`, compiled into the binary.
+//! messages missing from the active locale fall back to english, then to the message id
+
+use std::cell::RefCell;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    /// the locale's own name for itself, so it can be found without reading the current one
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.id() == id)
+    }
+
+    /// first of the browser's preferred languages that has a translation
+    pub fn detect() -> Self {
+        let languages = match web_sys::window() {
+            Some(window) => window.navigator().languages(),
+            None => return Self::default(),
+        };
+        languages
+            .iter()
+            .filter_map(|lang| lang.as_string())
+            .find_map(|lang| Self::from_id(lang.split('-').next().unwrap_or_default()))
+            .unwrap_or_default()
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.ftl"),
+            Locale::De => include_str!("../locales/de.ftl"),
+        }
+    }
+}
+
+fn bundle(locale: Locale) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(locale.source().to_string())
+        .unwrap_or_else(|(_, errors)| panic!("invalid {}.ftl: {:?}", locale.id(), errors));
+    let lang: LanguageIdentifier = locale.id().parse().expect("invalid locale id");
+    let mut bundle = FluentBundle::new(vec![lang]);
+    // the bidi isolation marks show up as boxes in native dialogs and tooltips
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("invalid {}.ftl: {:?}", locale.id(), errors));
+    bundle
+}
+
+thread_local! {
+    static FALLBACK: FluentBundle<FluentResource> = bundle(Locale::En);
+    static ACTIVE: RefCell<(Locale, FluentBundle<FluentResource>)> =
+        RefCell::new((Locale::En, bundle(Locale::En)));
+}
+
+/// switch the locale used by `tr!`. components pick it up on their next render
+pub fn set_locale(locale: Locale) {
+    ACTIVE.with(|active| {
+        if active.borrow().0 != locale {
+            *active.borrow_mut() = (locale, bundle(locale));
+        }
+    });
+}
+
+/// `id` is a message id, or `message.attribute`
+fn format(
+    bundle: &FluentBundle<FluentResource>,
+    id: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let (message, attribute) = match id.split_once('.') {
+        Some((message, attribute)) => (message, Some(attribute)),
+        None => (id, None),
+    };
+    let message = bundle.get_message(message)?;
+    let pattern = match attribute {
+        Some(attribute) => message.get_attribute(attribute)?.value(),
+        None => message.value()?,
+    };
+    let mut errors = Vec::new();
+    Some(
+        bundle
+            .format_pattern(pattern, args, &mut errors)
+            .into_owned(),
+    )
+}
+
+pub fn translate(id: &str, args: Option<&FluentArgs>) -> String {
+    ACTIVE
+        .with(|active| format(&active.borrow().1, id, args))
+        .or_else(|| FALLBACK.with(|fallback| format(fallback, id, args)))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// translated UI string: `tr!("run")`, `tr!("run-on-selection.title")`,
+/// `tr!("file-exists", name = name)`
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::translate($id, None)
+    };
+    ($id:expr, $($key:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $( args.set(stringify!($key), $value); )+
+        $crate::i18n::translate($id, Some(&args))
+    }};
+}
+
+pub(crate) use tr;
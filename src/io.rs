@@ -1,13 +1,21 @@
-use std::collections::BTreeMap;
+mod compact;
+mod legacy;
+
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    io::{BufReader, Read},
+};
 
 use base_x::{DecodeError, decode, encode};
 use lexer_search_lib::{
     engine::{
-        graph::{GraphBuilder, GroupInfo},
+        graph::{Graph, GraphBuilder, GroupInfo},
         matcher::{FullMatch, Matcher},
         matchers::{make_c_like_lexer, make_python_like_lexer, make_rust_like_lexer},
     },
-    io::Language,
+    io::{Language, final_postprocess},
     lexer::{
         DEFAULT_MAX_CONCURRENT_MATCHES, DEFAULT_MAX_DISTINCT_GROUPS, DEFAULT_MAX_EXPANSIONS,
         DEFAULT_MAX_GROUP_MEMORY, DEFAULT_MAX_TOKEN_LENGTH, EnumLexer,
@@ -15,6 +23,8 @@ use lexer_search_lib::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::{api::SearchMatch, i18n::tr};
+
 const ALPHABET: &str =
     "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_.~/:@!$&()*+,;='";
 
@@ -26,6 +36,95 @@ pub fn decode_bytes(s: &str) -> Result<Vec<u8>, DecodeError> {
     decode(ALPHABET, s)
 }
 
+/// letters and digits only, which no tool between the sender and the receiver changes
+const STRICT_ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// RFC 4648 base64url, without padding
+pub fn encode_base64url(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL_ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+        }
+    }
+    out
+}
+
+pub fn decode_base64url(s: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim_end_matches('=');
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return Err("truncated base64url".to_string());
+        }
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let digit = BASE64URL_ALPHABET
+                .iter()
+                .position(|a| a == c)
+                .ok_or_else(|| format!("invalid base64url character {:?}", *c as char))?;
+            n |= (digit as u32) << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(out)
+}
+
+/// how a config is written into a link. everything but `BaseX` starts with a marker
+/// character, links without one are `BaseX`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum LinkEncoding {
+    /// compact, but some tools mangle characters of its alphabet like `'` or `(`
+    #[default]
+    BaseX,
+    /// `~` + base64url of the same compressed payload
+    Base64Url,
+    /// `?cfg=` + uri encoded JSON, readable for debugging
+    Json,
+    /// `-` + the compressed payload in letters and digits, for links that pass through
+    /// proxies, markdown and chat tools
+    StrictSafe,
+}
+
+const BASE64URL_MARKER: char = '~';
+const JSON_MARKER: &str = "?cfg=";
+const STRICT_MARKER: char = '-';
+
+impl LinkEncoding {
+    pub const ALL: [LinkEncoding; 4] = [
+        LinkEncoding::BaseX,
+        LinkEncoding::Base64Url,
+        LinkEncoding::StrictSafe,
+        LinkEncoding::Json,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            LinkEncoding::BaseX => "base-x",
+            LinkEncoding::Base64Url => "base64url",
+            LinkEncoding::Json => "json",
+            LinkEncoding::StrictSafe => "strict-safe",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("link-encoding-{}", self.id()))
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.id() == id)
+    }
+}
+
 type Playgroundlhs = Vec<MatchingUnit>;
 
 /// the DTO that is used to serialize and deserialize from the url part
@@ -39,9 +138,57 @@ pub struct PlaygroundConfig {
     pub language: Language,
 
     pub lhs: Playgroundlhs,
+
+    /// additional subjects, scanned after `subject`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<SubjectFile>,
+}
+
+/// a json link with the matches of a run baked in. older versions ignore `results`
+#[derive(Serialize)]
+struct WithResults<'a> {
+    #[serde(flatten)]
+    config: &'a PlaygroundConfig,
+    results: &'a [SearchMatch],
+}
+
+#[derive(Deserialize)]
+struct WithResultsOwned {
+    #[serde(flatten)]
+    config: PlaygroundConfig,
+    #[serde(default)]
+    results: Option<Vec<SearchMatch>>,
 }
 
+/// first byte of a versioned payload. untagged payloads from older links start with a
+/// bincode varint, for which 0xFF is never a valid first byte
+const FORMAT_TAG: u8 = 0xFF;
+
+/// bumped whenever the encoded layout of `PlaygroundConfig` changes. the current one is
+/// `compact`, the untagged layout before it is decoded by `legacy`
+const FORMAT_VERSION: u8 = 1;
+
+/// a named subject scanned alongside the main one
 #[derive(Serialize, Deserialize, bincode::Encode, bincode::Decode, Debug)]
+pub struct SubjectFile {
+    pub name: String,
+    pub language: Language,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub content: String,
+}
+
+/// a subject as shown in the ui: name, monaco language, content
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct EditorFile {
+    pub name: String,
+    pub language: String,
+    pub content: String,
+}
+
+/// display name of `PlaygroundConfig::subject`
+pub const MAIN_FILE_NAME: &str = "main";
+
+#[derive(Serialize, Deserialize, bincode::Encode, bincode::Decode, Debug, Clone)]
 pub struct MatchingUnit {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub patterns: Vec<String>,
@@ -55,6 +202,89 @@ pub struct MatchingUnit {
     pub transform: BTreeMap<String, String>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub templates: BTreeMap<String, Vec<String>>,
+    /// how this unit's matches are highlighted
+    #[serde(default, skip_serializing_if = "HighlightStyle::is_default")]
+    pub style: HighlightStyle,
+    /// disabled units are kept in the YAML but skipped by `run`
+    #[serde(default = "enabled_default", skip_serializing_if = "is_enabled")]
+    pub enabled: bool,
+    /// what the rule looks for, shown with its matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// further reading on the rule. only http(s) links are shown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// stop reporting the unit's matches after this many, so a noisy rule can't drown out
+    /// the rest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_matches: Option<usize>,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+fn is_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+/// whether a rule's `docs_url` may be linked to. anything else, e.g. `javascript:`, could
+/// run code from a shared link
+pub fn is_web_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+impl Default for MatchingUnit {
+    fn default() -> Self {
+        MatchingUnit {
+            patterns: Vec::new(),
+            name: String::new(),
+            group: GroupInfo::default(),
+            out: BTreeMap::new(),
+            transform: BTreeMap::new(),
+            templates: BTreeMap::new(),
+            style: HighlightStyle::default(),
+            enabled: true,
+            description: None,
+            docs_url: None,
+            tags: Vec::new(),
+            max_matches: None,
+        }
+    }
+}
+
+/// css values applied to a unit's highlights. empty fields keep the default look
+#[derive(
+    Serialize, Deserialize, bincode::Encode, bincode::Decode, Debug, Default, Clone, PartialEq, Hash,
+)]
+pub struct HighlightStyle {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub background: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub border: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text_decoration: String,
+}
+
+impl HighlightStyle {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// css property, value pairs for the non-empty fields
+    pub fn properties(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("background-color", &self.background),
+            ("border", &self.border),
+            ("text-decoration", &self.text_decoration),
+        ]
+        .into_iter()
+        .filter(|(_, v)| !v.is_empty())
+        .map(|(k, v)| (k, v.as_str()))
+        .collect()
+    }
 }
 
 impl Default for PlaygroundConfig {
@@ -65,81 +295,417 @@ impl Default for PlaygroundConfig {
             lhs: vec![MatchingUnit {
                 patterns: vec!["hello_world(... $CAPTURE ...)".to_string()],
                 name: "hi".to_string(),
-                group: Default::default(),
-                out: Default::default(),
-                transform: Default::default(),
-                templates: Default::default(),
+                ..Default::default()
             }],
+            files: Vec::new(),
+        }
+    }
+}
+
+/// the monaco id of `language`
+pub fn monaco_language(language: &Language) -> &'static str {
+    match language {
+        Language::C => "cpp",
+        Language::CSharp => "csharp",
+        Language::Go => "go",
+        Language::Java => "java",
+        Language::Js => "javascript",
+        Language::Kotlin => "kotlin",
+        Language::Py => "python",
+        Language::Rust => "rust",
+        Language::Ts => "typescript",
+    }
+}
+
+/// the tokenizer a language's subjects and patterns go through, see `make_lexer`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LexerFamily {
+    CLike,
+    PythonLike,
+    RustLike,
+}
+
+impl LexerFamily {
+    pub fn of(language: &Language) -> Self {
+        match language {
+            Language::C
+            | Language::CSharp
+            | Language::Java
+            | Language::Go
+            | Language::Js
+            | Language::Ts
+            | Language::Kotlin => LexerFamily::CLike,
+            Language::Py => LexerFamily::PythonLike,
+            Language::Rust => LexerFamily::RustLike,
         }
     }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            LexerFamily::CLike => "c-like",
+            LexerFamily::PythonLike => "python-like",
+            LexerFamily::RustLike => "rust-like",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("lexer-{}", self.id()))
+    }
+}
+
+/// the monaco id a config stores for the language picked as `monaco_id`, and the lexer
+/// family it will be matched with
+pub fn resolve_language(monaco_id: &str) -> Result<(&'static str, LexerFamily), String> {
+    let language: Language = serde_yml::from_str(monaco_id).map_err(|e| e.to_string())?;
+    Ok((monaco_language(&language), LexerFamily::of(&language)))
 }
 
 pub const PUBLIC_URL: &'static str = include_str!("../target/lexer-search-ui-public-url");
 
+/// the id of a `#s=<id>` short link
+pub fn short_id_from_url_str(s: &str) -> Option<&str> {
+    let (_, fragment) = s.split_once('#')?;
+    let id = fragment
+        .strip_prefix('/')
+        .unwrap_or(fragment)
+        .strip_prefix("s=")?;
+    (!id.is_empty()).then_some(id)
+}
+
+/// sizes of an encoded share link
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LinkStats {
+    /// bincode payload before compression
+    pub raw_len: usize,
+    pub compressed_len: usize,
+    /// length of the encoded fragment
+    pub encoded_len: usize,
+    /// time spent compressing and encoding
+    pub encode_ms: f64,
+}
+
+impl LinkStats {
+    pub fn compression_ratio(&self) -> f64 {
+        self.raw_len as f64 / self.compressed_len.max(1) as f64
+    }
+}
+
+/// zstd effort for share links. decoding doesn't depend on it
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum CompressionLevel {
+    Fast,
+    Normal,
+    #[default]
+    Max,
+}
+
+impl CompressionLevel {
+    pub const ALL: [CompressionLevel; 3] = [
+        CompressionLevel::Fast,
+        CompressionLevel::Normal,
+        CompressionLevel::Max,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            CompressionLevel::Fast => "fast",
+            CompressionLevel::Normal => "normal",
+            CompressionLevel::Max => "max",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("compression-{}", self.id()))
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.id() == id)
+    }
+
+    fn zstd_level(self) -> i32 {
+        match self {
+            CompressionLevel::Fast => 3,
+            CompressionLevel::Normal => 12,
+            CompressionLevel::Max => 22,
+        }
+    }
+}
+
+/// a serialized config waiting to be compressed and encoded into a link
+pub struct PendingLink {
+    encoding: LinkEncoding,
+    /// json, or the tagged bincode payload
+    payload: Vec<u8>,
+}
+
+impl PendingLink {
+    pub fn encode(self, level: CompressionLevel) -> (String, LinkStats) {
+        let started = js_sys::Date::now();
+        let raw_len = self.payload.len();
+        let (encoded, compressed_len) = self.encode_payload(level);
+        let stats = LinkStats {
+            raw_len,
+            compressed_len,
+            encoded_len: encoded.len(),
+            encode_ms: js_sys::Date::now() - started,
+        };
+        (encoded, stats)
+    }
+
+    /// the link fragment, and the size of the payload once compressed
+    fn encode_payload(self, level: CompressionLevel) -> (String, usize) {
+        match self.encoding {
+            LinkEncoding::Json => {
+                let json = String::from_utf8(self.payload).unwrap();
+                let encoded = format!(
+                    "{}{}",
+                    JSON_MARKER,
+                    String::from(js_sys::encode_uri_component(&json))
+                );
+                (encoded, raw_len)
+            }
+            encoding => {
+                let compressed = zstd::encode_all(&self.payload[..], level.zstd_level()).unwrap();
+                // the payload and the compressed bytes can be as large as the subjects
+                drop(self.payload);
+                let encoded = match encoding {
+                    LinkEncoding::Base64Url => {
+                        format!("{}{}", BASE64URL_MARKER, encode_base64url(&compressed))
+                    }
+                    LinkEncoding::StrictSafe => {
+                        format!("{}{}", STRICT_MARKER, encode(STRICT_ALPHABET, &compressed))
+                    }
+                    _ => encode_bytes(&compressed),
+                };
+                let compressed_len = compressed.len();
+                drop(compressed);
+                (encoded, compressed_len)
+            }
+        }
+    }
+}
+
 impl PlaygroundConfig {
     pub fn to_url_str(&self) -> String {
-        let bin = bincode::encode_to_vec(self, bincode::config::standard()).unwrap();
-        let compressed = zstd::encode_all(&bin[..], 22).unwrap();
+        self.to_url_str_as(LinkEncoding::BaseX).0
+    }
 
-        encode_bytes(&compressed)
+    pub fn to_url_str_as(&self, encoding: LinkEncoding) -> (String, LinkStats) {
+        self.pending_link(encoding).encode(CompressionLevel::Max)
     }
 
-    pub fn from_url_str(mut s: &str) -> Result<Self, String> {
+    /// serialize for a link, leaving the slow compression to `PendingLink::encode`
+    pub fn pending_link(&self, encoding: LinkEncoding) -> PendingLink {
+        let payload = match encoding {
+            LinkEncoding::Json => serde_json::to_vec(self).unwrap(),
+            _ => {
+                let mut bin = vec![FORMAT_TAG, FORMAT_VERSION];
+                bincode::encode_into_std_write(
+                    compact::Compact(self),
+                    &mut bin,
+                    bincode::config::standard(),
+                )
+                .unwrap();
+                bin
+            }
+        };
+        PendingLink { encoding, payload }
+    }
+
+    /// `pending_link` with the matches of a run baked in, so the link shows them without
+    /// running. in the binary encodings they follow the config, where older versions stop
+    /// reading
+    pub fn pending_link_with_results(
+        &self,
+        encoding: LinkEncoding,
+        matches: &[SearchMatch],
+    ) -> PendingLink {
+        let mut link = match encoding {
+            LinkEncoding::Json => {
+                let with_results = WithResults {
+                    config: self,
+                    results: matches,
+                };
+                let payload = serde_json::to_vec(&with_results).unwrap();
+                return PendingLink { encoding, payload };
+            }
+            _ => self.pending_link(encoding),
+        };
+        bincode::encode_into_std_write(matches, &mut link.payload, bincode::config::standard())
+            .unwrap();
+        link
+    }
+
+    pub fn from_url_str(s: &str) -> Result<Self, String> {
+        Self::from_url_str_with_results(s).map(|(cfg, _)| cfg)
+    }
+
+    /// `from_url_str`, also returning the matches baked into the link, if any
+    pub fn from_url_str_with_results(
+        mut s: &str,
+    ) -> Result<(Self, Option<Vec<SearchMatch>>), String> {
         if s.len() <= PUBLIC_URL.len() {
-            return Ok(Default::default());
+            return Ok((Default::default(), None));
         }
         if s.starts_with(PUBLIC_URL) {
             s = &s[PUBLIC_URL.len()..];
         }
+        Self::from_encoded_with_results(s)
+    }
+
+    /// inverse of `to_url_str_as`, detecting the encoding from the marker character
+    pub fn from_encoded(s: &str) -> Result<Self, String> {
+        Self::from_encoded_with_results(s).map(|(cfg, _)| cfg)
+    }
+
+    /// `from_encoded`, also returning the matches baked into the link, if any
+    pub fn from_encoded_with_results(s: &str) -> Result<(Self, Option<Vec<SearchMatch>>), String> {
+        if let Some(json) = s.strip_prefix(JSON_MARKER) {
+            let json = js_sys::decode_uri_component(json)
+                .map_err(|_| "malformed uri encoding".to_string())?;
+            let shared: WithResultsOwned =
+                serde_json::from_str(&String::from(json)).map_err(|e| e.to_string())?;
+            return Ok((shared.config, shared.results));
+        }
+        if let Some(encoded) = s.strip_prefix(BASE64URL_MARKER) {
+            // the marker is also a base-x digit, so an older link may start with it
+            let decoded = decode_base64url(encoded).and_then(|c| Self::from_compressed(&c));
+            if decoded.is_ok() {
+                return decoded;
+            }
+        }
+        if let Some(encoded) = s.strip_prefix(STRICT_MARKER) {
+            // likewise
+            let decoded = decode(STRICT_ALPHABET, encoded)
+                .map_err(|e| e.to_string())
+                .and_then(|c| Self::from_compressed(&c));
+            if decoded.is_ok() {
+                return decoded;
+            }
+        }
+
         let compressed = match decode_bytes(s) {
             Ok(v) => v,
             Err(e) => return Err(e.to_string()),
         };
+        Self::from_compressed(&compressed)
+    }
 
+    fn from_compressed(compressed: &[u8]) -> Result<(Self, Option<Vec<SearchMatch>>), String> {
         let decompressed = match zstd::decode_all(&compressed[..]) {
             Ok(v) => v,
             Err(e) => return Err(e.to_string()),
         };
 
-        let cfg = match bincode::decode_from_slice(&decompressed, bincode::config::standard()) {
+        let payload = match &decompressed[..] {
+            [FORMAT_TAG, FORMAT_VERSION, payload @ ..] => payload,
+            [FORMAT_TAG, version, ..] if *version < FORMAT_VERSION => {
+                return Err(format!("unknown link format {}", version));
+            }
+            [FORMAT_TAG, version, ..] => {
+                return Err(format!(
+                    "this link was created by a newer version of the playground (format {})",
+                    version
+                ));
+            }
+            untagged => return legacy::decode_untagged(untagged).map(|cfg| (cfg, None)),
+        };
+
+        let (cfg, read) = match bincode::decode_from_slice::<compact::Decoded, _>(
+            payload,
+            bincode::config::standard(),
+        ) {
             Ok(v) => v,
             Err(e) => return Err(e.to_string()),
         };
-        Ok(cfg.0)
+        // the matches of a results link
+        let rest = &payload[read..];
+        if rest.is_empty() {
+            return Ok((cfg.0, None));
+        }
+        let (results, _) = match bincode::decode_from_slice(rest, bincode::config::standard()) {
+            Ok(v) => v,
+            Err(e) => return Err(e.to_string()),
+        };
+        Ok((cfg.0, Some(results)))
     }
 
     pub fn from_editor_parts(
         subject: &str,
         language: &str,
         editor_lhs: &str,
-    ) -> Result<Self, String> {
-        let lhs = serde_yml::from_str(editor_lhs).map_err(|e| e.to_string())?;
-        let lang = serde_yml::from_str(language).map_err(|e| e.to_string())?;
+    ) -> Result<Self, PlaygroundError> {
+        let lhs = serde_yml::from_str(editor_lhs).map_err(|e| PlaygroundError::YamlLhs {
+            span: e.location().map(|l| (l.line(), l.column())),
+            message: e.to_string(),
+        })?;
+        let lang = serde_yml::from_str(language)
+            .map_err(|e| PlaygroundError::YamlLanguage(e.to_string()))?;
         Ok(Self {
             subject: subject.to_owned(),
             language: lang,
             lhs: lhs,
+            files: Vec::new(),
         })
     }
 
+    /// disable the units carrying none of `tags` so they aren't compiled. an empty set
+    /// keeps every unit
+    pub fn retain_tagged(&mut self, tags: &BTreeSet<String>) {
+        if tags.is_empty() {
+            return;
+        }
+        for unit in &mut self.lhs {
+            if !unit.tags.iter().any(|t| tags.contains(t)) {
+                unit.enabled = false;
+            }
+        }
+    }
+
+    /// append an additional subject file
+    pub fn add_file(
+        &mut self,
+        name: &str,
+        language: &str,
+        content: &str,
+    ) -> Result<(), PlaygroundError> {
+        let language = serde_yml::from_str(language)
+            .map_err(|e| PlaygroundError::YamlLanguage(format!("file \"{}\": {}", name, e)))?;
+        self.files.push(SubjectFile {
+            name: name.to_owned(),
+            language,
+            content: content.to_owned(),
+        });
+        Ok(())
+    }
+
     /// lhs, rhs, lang
     pub fn to_editor_parts(self) -> (String, String, String) {
-        let lang = self.monaco_language().to_string();
+        let lang = monaco_language(&self.language).to_string();
         (self.editor_lhs(), self.subject, lang)
     }
 
-    fn monaco_language(&self) -> &'static str {
-        match self.language {
-            Language::C => "cpp",
-            Language::CSharp => "csharp",
-            Language::Go => "go",
-            Language::Java => "java",
-            Language::Js => "javascript",
-            Language::Kotlin => "kotlin",
-            Language::Py => "python",
-            Language::Rust => "rust",
-            Language::Ts => "typescript",
-        }
+    /// the monaco id of each subject's language, indexed like `SearchMatch::file`
+    pub fn subject_languages(&self) -> Vec<&'static str> {
+        std::iter::once(&self.language)
+            .chain(self.files.iter().map(|f| &f.language))
+            .map(monaco_language)
+            .collect()
+    }
+
+    /// every subject, the main one first
+    pub fn editor_files(&self) -> Vec<EditorFile> {
+        std::iter::once(EditorFile {
+            name: MAIN_FILE_NAME.to_string(),
+            language: monaco_language(&self.language).to_string(),
+            content: self.subject.clone(),
+        })
+        .chain(self.files.iter().map(|f| EditorFile {
+            name: f.name.clone(),
+            language: monaco_language(&f.language).to_string(),
+            content: f.content.clone(),
+        }))
+        .collect()
     }
 
     fn editor_lhs(&self) -> String {
@@ -147,7 +713,23 @@ impl PlaygroundConfig {
         s.to_string()
     }
 
-    pub fn run(self, out: impl FnMut(FullMatch)) -> Result<(), String> {
+    /// scan every subject, reporting matches with the index of their file (0 is `subject`).
+    /// see `compile` for `skip_invalid`; the patterns it left out are returned
+    pub fn run(
+        self,
+        skip_invalid: bool,
+        out: impl FnMut(usize, FullMatch),
+    ) -> Result<Vec<PatternError>, PlaygroundError> {
+        let compiled = self.compile(skip_invalid)?;
+        self.run_compiled(&compiled, &Deadline::none(), out)?;
+        Ok(compiled.skipped)
+    }
+
+    /// build the patterns into a graph for each language the subjects use, without
+    /// matching anything. all patterns are compiled before giving up, so a failure lists
+    /// every broken pattern rather than the first. with `skip_invalid` those patterns are
+    /// left out instead and kept in `CompiledPatterns::skipped`
+    pub fn compile(&self, skip_invalid: bool) -> Result<CompiledPatterns, PlaygroundError> {
         fn convert_out(input: BTreeMap<String, String>) -> BTreeMap<Box<[u8]>, Box<[u8]>> {
             input
                 .into_iter()
@@ -183,57 +765,242 @@ impl PlaygroundConfig {
                 .collect()
         }
 
-        let mut graph = GraphBuilder::default();
-        for unit in self.lhs {
-            for unexpanded_pattern in unit.patterns {
-                for pattern in lexer_search_lib::engine::template::expand(
-                    unexpanded_pattern.as_bytes(),
-                    &convert_templates(unit.templates.clone()),
-                    DEFAULT_MAX_EXPANSIONS,
-                )? {
-                    let mut reader = std::io::Cursor::new(pattern);
-                    let lexer: EnumLexer = match self.language {
-                        Language::C | Language::CSharp | Language::Java => {
-                            EnumLexer::CLike(make_c_like_lexer(
-                                false,
-                                true,
-                                DEFAULT_MAX_TOKEN_LENGTH,
-                            ))
+        // the pattern lexer depends on the subject's language, so each language gets its
+        // own graph
+        let mut languages: Vec<&Language> = Vec::new();
+        for language in
+            std::iter::once(&self.language).chain(self.files.iter().map(|f| &f.language))
+        {
+            if !languages
+                .iter()
+                .any(|l| monaco_language(l) == monaco_language(language))
+            {
+                languages.push(language);
+            }
+        }
+        let mut errors: Vec<PatternError> = Vec::new();
+        let mut builders = Vec::with_capacity(languages.len());
+        for language in &languages {
+            // (unit, pattern) indexes that failed for this language
+            let mut failed: Vec<(usize, usize)> = Vec::new();
+            let graph = loop {
+                let mut graph = GraphBuilder::default();
+                let mut newly_failed = false;
+                for (unit_index, unit) in self.lhs.iter().enumerate().filter(|(_, u)| u.enabled) {
+                    for (pattern_index, unexpanded_pattern) in unit.patterns.iter().enumerate() {
+                        if failed.contains(&(unit_index, pattern_index)) {
+                            continue;
                         }
-                        Language::Go | Language::Js | Language::Ts | Language::Kotlin => {
-                            EnumLexer::CLike(make_c_like_lexer(
-                                true,
-                                true,
-                                DEFAULT_MAX_TOKEN_LENGTH,
-                            ))
+                        let mut add = || -> Result<(), (PatternCause, String)> {
+                            for pattern in lexer_search_lib::engine::template::expand(
+                                unexpanded_pattern.as_bytes(),
+                                &convert_templates(unit.templates.clone()),
+                                DEFAULT_MAX_EXPANSIONS,
+                            )
+                            .map_err(|e| (PatternCause::Templates, String::from(e)))?
+                            {
+                                let mut reader = std::io::Cursor::new(pattern);
+                                graph
+                                    .add_pattern(
+                                        &mut reader,
+                                        &convert_out(unit.out.clone()),
+                                        // names needn't be unique, `scan` maps it back
+                                        unit_index.to_string(),
+                                        unit.group.clone(),
+                                        &convert_transform(unit.transform.clone()),
+                                        make_lexer(language, true),
+                                        DEFAULT_MAX_TOKEN_LENGTH,
+                                    )
+                                    .map_err(|e| {
+                                        let message = String::from(e);
+                                        // the engine compiles the transforms along with
+                                        // each pattern and only says so in the message
+                                        let cause = if !unit.transform.is_empty()
+                                            && message.to_lowercase().contains("regex")
+                                        {
+                                            PatternCause::Transform
+                                        } else {
+                                            PatternCause::Pattern
+                                        };
+                                        (cause, message)
+                                    })?;
+                            }
+                            Ok(())
+                        };
+                        if let Err((cause, message)) = add() {
+                            failed.push((unit_index, pattern_index));
+                            newly_failed = true;
+                            let error = PatternError {
+                                unit: unit_index,
+                                unit_name: unit.name.clone(),
+                                pattern_index,
+                                pattern: unexpanded_pattern.clone(),
+                                cause,
+                                message,
+                            };
+                            // languages sharing a lexer fail the same way
+                            if !errors.contains(&error) {
+                                errors.push(error);
+                            }
                         }
-                        Language::Py => EnumLexer::PythonLike(make_python_like_lexer(
-                            true,
-                            DEFAULT_MAX_TOKEN_LENGTH,
-                        )),
-                        Language::Rust => EnumLexer::RustLike(make_rust_like_lexer(
-                            true,
-                            DEFAULT_MAX_TOKEN_LENGTH,
-                        )),
-                    };
-
-                    graph.add_pattern(
-                        &mut reader,
-                        &convert_out(unit.out.clone()),
-                        unit.name.clone(),
-                        unit.group.clone(),
-                        &convert_transform(unit.transform.clone()),
-                        lexer,
-                        DEFAULT_MAX_TOKEN_LENGTH,
-                    )?;
+                    }
+                }
+                // a failing pattern may be half added, so the graph is rebuilt without it
+                if !newly_failed || !skip_invalid {
+                    break graph;
+                }
+            };
+            builders.push(graph);
+        }
+        errors.sort_by_key(|e| e.unit);
+        if !errors.is_empty() && !skip_invalid {
+            return Err(PlaygroundError::PatternCompile(errors));
+        }
+
+        let mut graphs = Vec::with_capacity(builders.len());
+        for (language, graph) in languages.into_iter().zip(builders) {
+            graphs.push((
+                monaco_language(language),
+                graph.build().map_err(String::from)?,
+            ));
+        }
+        Ok(CompiledPatterns {
+            key: self.compile_key(skip_invalid),
+            skip_invalid,
+            graphs,
+            unit_names: self.lhs.iter().map(|u| u.name.clone()).collect(),
+            pattern_count: self
+                .lhs
+                .iter()
+                .filter(|u| u.enabled)
+                .map(|u| u.patterns.len())
+                .sum(),
+            skipped: errors,
+        })
+    }
+
+    /// what the compiled graphs depend on besides the subjects' languages
+    fn compile_key(&self, skip_invalid: bool) -> String {
+        format!(
+            "{}:{}",
+            skip_invalid,
+            serde_json::to_string(&self.lhs).unwrap_or_default()
+        )
+    }
+
+    /// scan every subject with patterns from `compile`, reporting the postprocessed matches
+    /// with the index of their file (0 is `subject`). a unit stops being reported once it
+    /// reaches its `max_matches`; the names of the units that had more are returned.
+    /// subjects after `deadline` are skipped
+    pub fn run_compiled(
+        self,
+        compiled: &CompiledPatterns,
+        deadline: &Deadline,
+        mut out: impl FnMut(usize, FullMatch),
+    ) -> Result<BTreeSet<String>, PlaygroundError> {
+        let mut caps = MatchCaps::new(&self.lhs);
+        let subjects = std::iter::once((self.language, self.subject))
+            .chain(self.files.into_iter().map(|f| (f.language, f.content)));
+        for (file_index, (language, subject)) in subjects.enumerate() {
+            if deadline.expired() {
+                break;
+            }
+            compiled.scan(&language, subject, deadline, |unit, m| {
+                if let Some(m) = caps.admit(unit, m) {
+                    out(file_index, m)
                 }
+            })?;
+        }
+        Ok(caps.into_truncated())
+    }
+}
+
+/// `max_matches` over a run: the limit of each enabled unit by index, so units sharing a
+/// name don't share one, and how many matches each reported so far
+pub struct MatchCaps {
+    caps: BTreeMap<usize, (usize, usize)>,
+    truncated: BTreeSet<String>,
+}
+
+impl MatchCaps {
+    pub fn new(lhs: &[MatchingUnit]) -> Self {
+        let caps = lhs
+            .iter()
+            .enumerate()
+            .filter(|(_, u)| u.enabled)
+            .filter_map(|(i, u)| Some((i, (u.max_matches?, 0))))
+            .collect();
+        Self {
+            caps,
+            truncated: BTreeSet::new(),
+        }
+    }
+
+    /// postprocess a raw match of the unit at `unit`, keeping it if that unit is still under
+    /// its limit. matches postprocessing drops don't count towards it
+    pub fn admit(&mut self, unit: usize, m: FullMatch) -> Option<FullMatch> {
+        let m = final_postprocess(m)?;
+        if let Some((max, seen)) = self.caps.get_mut(&unit) {
+            if *seen >= *max {
+                self.truncated.insert(m.name.clone());
+                return None;
             }
+            *seen += 1;
         }
+        Some(m)
+    }
 
-        let graph = graph.build()?;
+    /// the names of the units that had more matches than their `max_matches`
+    pub fn truncated(&self) -> &BTreeSet<String> {
+        &self.truncated
+    }
+
+    pub fn into_truncated(self) -> BTreeSet<String> {
+        self.truncated
+    }
+}
+
+/// the lhs of a config built into a graph per subject language, reusable by later runs as
+/// long as the lhs doesn't change
+pub struct CompiledPatterns {
+    /// see `PlaygroundConfig::compile_key`
+    key: String,
+    skip_invalid: bool,
+    /// keyed by monaco language id
+    graphs: Vec<(&'static str, Graph)>,
+    /// the name of each unit. the graphs name matches by unit index instead
+    unit_names: Vec<String>,
+    /// patterns of the enabled units, as written
+    pub pattern_count: usize,
+    /// patterns left out with `skip_invalid`
+    pub skipped: Vec<PatternError>,
+}
+
+impl CompiledPatterns {
+    fn graph(&self, language: &Language) -> Option<&Graph> {
+        let id = monaco_language(language);
+        self.graphs.iter().find(|(l, _)| *l == id).map(|(_, g)| g)
+    }
+
+    /// match one subject, lexed as `language`, reporting the raw matches with the index of
+    /// their unit. `max_matches` isn't applied here. once `deadline` passes the rest of the
+    /// subject isn't read and no more matches are reported
+    pub fn scan(
+        &self,
+        language: &Language,
+        subject: String,
+        deadline: &Deadline,
+        mut out: impl FnMut(usize, FullMatch),
+    ) -> Result<(), PlaygroundError> {
+        let graph = self.graph(language).ok_or_else(|| {
+            format!(
+                "the patterns weren't compiled for {}",
+                monaco_language(language)
+            )
+        })?;
 
         let mut matcher = Matcher::new(
-            &graph,
+            graph,
             DEFAULT_MAX_CONCURRENT_MATCHES,
             DEFAULT_MAX_TOKEN_LENGTH,
             DEFAULT_MAX_DISTINCT_GROUPS,
@@ -241,24 +1008,289 @@ impl PlaygroundConfig {
             DEFAULT_MAX_EXPANSIONS,
         );
 
-        let mut reader = std::io::Cursor::new(self.subject);
-        let lexer: EnumLexer = match self.language {
-            Language::C | Language::CSharp | Language::Java => {
-                EnumLexer::CLike(make_c_like_lexer(false, false, DEFAULT_MAX_TOKEN_LENGTH))
-            }
-            Language::Go | Language::Js | Language::Ts | Language::Kotlin => {
-                EnumLexer::CLike(make_c_like_lexer(true, false, DEFAULT_MAX_TOKEN_LENGTH))
-            }
-            Language::Py => {
-                EnumLexer::PythonLike(make_python_like_lexer(false, DEFAULT_MAX_TOKEN_LENGTH))
+        let mut reader = BufReader::with_capacity(
+            DEADLINE_READ_BYTES,
+            DeadlineReader {
+                inner: std::io::Cursor::new(subject),
+                deadline,
+            },
+        );
+        matcher
+            .process_and_drain(&mut reader, make_lexer(language, false), |mut m| {
+                if deadline.check() {
+                    return;
+                }
+                let unit = m.name.parse::<usize>().ok();
+                if let Some((unit, name)) = unit.and_then(|u| Some((u, self.unit_names.get(u)?))) {
+                    m.name = name.clone();
+                    out(unit, m)
+                }
+            })
+            .map_err(|e| PlaygroundError::Limits(String::from(e)))?;
+        Ok(())
+    }
+
+    /// whether `cfg` can be run with these graphs instead of compiling its own. a compile
+    /// without errors serves either `skip_invalid` setting
+    pub fn fits(&self, cfg: &PlaygroundConfig, skip_invalid: bool) -> bool {
+        (self.skip_invalid == skip_invalid || self.skipped.is_empty())
+            && self.key == cfg.compile_key(self.skip_invalid)
+            && std::iter::once(&cfg.language)
+                .chain(cfg.files.iter().map(|f| &f.language))
+                .all(|l| self.graph(l).is_some())
+    }
+}
+
+/// the part of a unit a pattern failed on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PatternCause {
+    /// expanding the unit's `templates` into the pattern
+    Templates,
+    /// one of the unit's `transform` regexes
+    Transform,
+    /// the pattern itself
+    Pattern,
+}
+
+/// a pattern that failed to compile
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternError {
+    /// index of the unit in the lhs
+    pub unit: usize,
+    pub unit_name: String,
+    /// index of the pattern in the unit's `patterns`
+    pub pattern_index: usize,
+    /// the pattern as written, before template expansion
+    pub pattern: String,
+    pub cause: PatternCause,
+    pub message: String,
+}
+
+impl PatternError {
+    /// where to look, naming the rule and the field
+    pub fn hint(&self) -> String {
+        let rule = if self.unit_name.is_empty() {
+            format!("#{}", self.unit + 1)
+        } else {
+            self.unit_name.clone()
+        };
+        match self.cause {
+            PatternCause::Templates => tr!("hint-templates", rule = rule),
+            PatternCause::Transform => tr!("hint-transform", rule = rule),
+            PatternCause::Pattern => {
+                tr!(
+                    "hint-pattern",
+                    rule = rule,
+                    pattern = self.pattern_index + 1
+                )
             }
-            Language::Rust => {
-                EnumLexer::RustLike(make_rust_like_lexer(false, DEFAULT_MAX_TOKEN_LENGTH))
+        }
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule #{} {:?}, pattern {:?}: {}",
+            self.unit + 1,
+            self.unit_name,
+            self.pattern,
+            self.message
+        )
+    }
+}
+
+/// how much of a subject is read between looks at the clock
+const DEADLINE_READ_BYTES: usize = 64 * 1024;
+
+/// when a run has to stop, so a slow one can't hang the tab. the matcher can't be
+/// interrupted, so it is checked as the subject is read and as matches come out
+pub struct Deadline {
+    /// `Date::now()` to stop at, none for no limit
+    at: Option<f64>,
+    expired: Cell<bool>,
+}
+
+impl Deadline {
+    pub fn none() -> Self {
+        Self {
+            at: None,
+            expired: Cell::new(false),
+        }
+    }
+
+    /// `ms` from now, none if 0
+    pub fn after_ms(ms: u32) -> Self {
+        Self {
+            at: (ms > 0).then(|| js_sys::Date::now() + ms as f64),
+            expired: Cell::new(false),
+        }
+    }
+
+    /// whether the deadline has passed, looking at the clock
+    pub fn check(&self) -> bool {
+        if !self.expired.get() && self.at.is_some_and(|at| js_sys::Date::now() >= at) {
+            self.expired.set(true);
+        }
+        self.expired.get()
+    }
+
+    /// whether a check found the deadline passed, so the results are partial
+    pub fn expired(&self) -> bool {
+        self.expired.get()
+    }
+}
+
+/// ends the subject early once the deadline has passed. reads are capped, so the clock is
+/// looked at often however much the matcher asks for
+struct DeadlineReader<'a, R> {
+    inner: R,
+    deadline: &'a Deadline,
+}
+
+impl<R: Read> Read for DeadlineReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.deadline.check() {
+            return Ok(0);
+        }
+        let len = buf.len().min(DEADLINE_READ_BYTES);
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+/// why a config couldn't be built from the editors or run
+#[derive(Debug)]
+pub enum PlaygroundError {
+    /// the lhs YAML doesn't fit the rules, at the 1-based line and column if serde knows it
+    YamlLhs {
+        message: String,
+        span: Option<(usize, usize)>,
+    },
+    /// a subject's language isn't one the engine knows
+    YamlLanguage(String),
+    /// every pattern that failed to compile, in lhs order, each with its unit and index
+    PatternCompile(Vec<PatternError>),
+    /// building the graph failed
+    Engine(String),
+    /// the matcher gave up on a subject, which it does when one of the engine's limits
+    /// is hit
+    Limits(String),
+}
+
+impl PlaygroundError {
+    /// what to check, for the errors the user can fix
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            PlaygroundError::YamlLhs {
+                span: Some((line, col)),
+                ..
+            } => Some(tr!("hint-yaml-lhs", line = *line, col = *col)),
+            PlaygroundError::YamlLhs { span: None, .. } => Some(tr!("hint-yaml-lhs-unplaced")),
+            PlaygroundError::YamlLanguage(_) => Some(tr!("hint-yaml-language")),
+            PlaygroundError::PatternCompile(errors) => errors.first().map(PatternError::hint),
+            PlaygroundError::Engine(_) => None,
+            PlaygroundError::Limits(_) => Some(tr!("hint-limits")),
+        }
+    }
+}
+
+impl fmt::Display for PlaygroundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaygroundError::PatternCompile(errors) => {
+                let lines: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "{}", lines.join("\n"))
             }
-        };
+            PlaygroundError::YamlLhs { message, .. }
+            | PlaygroundError::YamlLanguage(message)
+            | PlaygroundError::Engine(message)
+            | PlaygroundError::Limits(message) => write!(f, "{}", message),
+        }
+    }
+}
 
-        matcher.process_and_drain(&mut reader, lexer, out)?;
+impl From<String> for PlaygroundError {
+    fn from(message: String) -> Self {
+        PlaygroundError::Engine(message)
+    }
+}
 
-        Ok(())
+impl From<PlaygroundError> for String {
+    fn from(e: PlaygroundError) -> Self {
+        e.to_string()
     }
 }
+
+/// re-serialize the lhs YAML canonically: fields in `MatchingUnit` order, defaults dropped,
+/// two space indentation and multi-line strings as block scalars
+pub fn format_lhs(editor_lhs: &str) -> Result<String, String> {
+    serde_yml::to_string(&parse_lhs(editor_lhs)?).map_err(|e| e.to_string())
+}
+
+pub fn parse_lhs(editor_lhs: &str) -> Result<Vec<MatchingUnit>, String> {
+    serde_yml::from_str(editor_lhs).map_err(|e| e.to_string())
+}
+
+/// set `transform[capture]` of the unit at `index`, returning the lhs in canonical form
+pub fn set_transform(
+    editor_lhs: &str,
+    index: usize,
+    capture: &str,
+    regex: &str,
+) -> Result<String, String> {
+    let mut lhs = parse_lhs(editor_lhs)?;
+    let unit = lhs
+        .get_mut(index)
+        .ok_or_else(|| format!("no rule #{}", index + 1))?;
+    unit.transform.insert(capture.to_owned(), regex.to_owned());
+    serde_yml::to_string(&lhs).map_err(|e| e.to_string())
+}
+
+/// the lexer for patterns (`pattern == true`) or subjects of the given language
+fn make_lexer(language: &Language, pattern: bool) -> EnumLexer {
+    match language {
+        Language::C | Language::CSharp | Language::Java => {
+            EnumLexer::CLike(make_c_like_lexer(false, pattern, DEFAULT_MAX_TOKEN_LENGTH))
+        }
+        Language::Go | Language::Js | Language::Ts | Language::Kotlin => {
+            EnumLexer::CLike(make_c_like_lexer(true, pattern, DEFAULT_MAX_TOKEN_LENGTH))
+        }
+        Language::Py => {
+            EnumLexer::PythonLike(make_python_like_lexer(pattern, DEFAULT_MAX_TOKEN_LENGTH))
+        }
+        Language::Rust => {
+            EnumLexer::RustLike(make_rust_like_lexer(pattern, DEFAULT_MAX_TOKEN_LENGTH))
+        }
+    }
+}
+
+/// the byte ranges of the tokens the engine reads from `text`, lexed as a pattern
+/// (`pattern == true`) or a subject of `language`. comments and whitespace aren't tokens
+pub fn engine_tokens(
+    language: &Language,
+    text: &str,
+    pattern: bool,
+) -> Result<Vec<(usize, usize)>, String> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    // the lexer reports 1-based lines and byte columns, like the matches
+    let offset = |line: usize, column: usize| {
+        line_starts
+            .get(line.saturating_sub(1))
+            .map_or(text.len(), |start| start + column.saturating_sub(1))
+            .min(text.len())
+    };
+    let mut spans = Vec::new();
+    let mut reader = std::io::Cursor::new(text.as_bytes());
+    make_lexer(language, pattern)
+        .tokenize(&mut reader, |token| {
+            spans.push((
+                offset(token.start.line, token.start.column),
+                offset(token.end.line, token.end.column),
+            ))
+        })
+        .map_err(String::from)?;
+    Ok(spans)
+}
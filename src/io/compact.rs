@@ -0,0 +1,312 @@
+//! the tagged link payload: only what differs from the defaults. the config and
+//! each unit start with a bitmask of their fields that aren't at their default, and only
+//! those fields follow. a typical unit sets a name and a pattern or two, so most of the
+//! fields a full encoding writes as empty are left out
+
+use bincode::{
+    Decode, Encode,
+    de::Decoder,
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+};
+use lexer_search_lib::io::Language;
+
+use super::{MatchingUnit, PlaygroundConfig, monaco_language};
+
+const SUBJECT: u32 = 1 << 0;
+const LANGUAGE: u32 = 1 << 1;
+const FILES: u32 = 1 << 2;
+const CONFIG_FIELDS: u32 = SUBJECT | LANGUAGE | FILES;
+
+const PATTERNS: u32 = 1 << 0;
+const NAME: u32 = 1 << 1;
+const GROUP: u32 = 1 << 2;
+const OUT: u32 = 1 << 3;
+const TRANSFORM: u32 = 1 << 4;
+const TEMPLATES: u32 = 1 << 5;
+const STYLE: u32 = 1 << 6;
+/// set for a disabled unit, nothing follows
+const DISABLED: u32 = 1 << 7;
+const DESCRIPTION: u32 = 1 << 8;
+const DOCS_URL: u32 = 1 << 9;
+const TAGS: u32 = 1 << 10;
+const MAX_MATCHES: u32 = 1 << 11;
+const UNIT_FIELDS: u32 = (MAX_MATCHES << 1) - 1;
+
+/// writes a config in the compact layout
+pub struct Compact<'a>(pub &'a PlaygroundConfig);
+
+/// reads a config written by `Compact`
+pub struct Decoded(pub PlaygroundConfig);
+
+fn encode_unit<E: Encoder>(unit: &MatchingUnit, encoder: &mut E) -> Result<(), EncodeError> {
+    let fields = [
+        (PATTERNS, !unit.patterns.is_empty()),
+        (NAME, !unit.name.is_empty()),
+        (GROUP, !unit.group.is_default()),
+        (OUT, !unit.out.is_empty()),
+        (TRANSFORM, !unit.transform.is_empty()),
+        (TEMPLATES, !unit.templates.is_empty()),
+        (STYLE, !unit.style.is_default()),
+        (DISABLED, !unit.enabled),
+        (DESCRIPTION, unit.description.is_some()),
+        (DOCS_URL, unit.docs_url.is_some()),
+        (TAGS, !unit.tags.is_empty()),
+        (MAX_MATCHES, unit.max_matches.is_some()),
+    ];
+    let mask = fields
+        .iter()
+        .filter(|(_, set)| *set)
+        .fold(0, |mask, (bit, _)| mask | bit);
+    mask.encode(encoder)?;
+    if mask & PATTERNS != 0 {
+        unit.patterns.encode(encoder)?;
+    }
+    if mask & NAME != 0 {
+        unit.name.encode(encoder)?;
+    }
+    if mask & GROUP != 0 {
+        unit.group.encode(encoder)?;
+    }
+    if mask & OUT != 0 {
+        unit.out.encode(encoder)?;
+    }
+    if mask & TRANSFORM != 0 {
+        unit.transform.encode(encoder)?;
+    }
+    if mask & TEMPLATES != 0 {
+        unit.templates.encode(encoder)?;
+    }
+    if mask & STYLE != 0 {
+        unit.style.encode(encoder)?;
+    }
+    if let Some(description) = &unit.description {
+        description.encode(encoder)?;
+    }
+    if let Some(docs_url) = &unit.docs_url {
+        docs_url.encode(encoder)?;
+    }
+    if mask & TAGS != 0 {
+        unit.tags.encode(encoder)?;
+    }
+    if let Some(max_matches) = unit.max_matches {
+        max_matches.encode(encoder)?;
+    }
+    Ok(())
+}
+
+fn decode_unit<Context, D: Decoder<Context = Context>>(
+    decoder: &mut D,
+) -> Result<MatchingUnit, DecodeError> {
+    let mask = u32::decode(decoder)?;
+    if mask & !UNIT_FIELDS != 0 {
+        return Err(DecodeError::Other("unknown rule fields"));
+    }
+    let mut unit = MatchingUnit::default();
+    if mask & PATTERNS != 0 {
+        unit.patterns = Decode::decode(decoder)?;
+    }
+    if mask & NAME != 0 {
+        unit.name = Decode::decode(decoder)?;
+    }
+    if mask & GROUP != 0 {
+        unit.group = Decode::decode(decoder)?;
+    }
+    if mask & OUT != 0 {
+        unit.out = Decode::decode(decoder)?;
+    }
+    if mask & TRANSFORM != 0 {
+        unit.transform = Decode::decode(decoder)?;
+    }
+    if mask & TEMPLATES != 0 {
+        unit.templates = Decode::decode(decoder)?;
+    }
+    if mask & STYLE != 0 {
+        unit.style = Decode::decode(decoder)?;
+    }
+    unit.enabled = mask & DISABLED == 0;
+    if mask & DESCRIPTION != 0 {
+        unit.description = Some(Decode::decode(decoder)?);
+    }
+    if mask & DOCS_URL != 0 {
+        unit.docs_url = Some(Decode::decode(decoder)?);
+    }
+    if mask & TAGS != 0 {
+        unit.tags = Decode::decode(decoder)?;
+    }
+    if mask & MAX_MATCHES != 0 {
+        unit.max_matches = Some(Decode::decode(decoder)?);
+    }
+    Ok(unit)
+}
+
+impl Encode for Compact<'_> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let cfg = self.0;
+        let default = PlaygroundConfig::default();
+        let mut mask = 0;
+        if cfg.subject != default.subject {
+            mask |= SUBJECT;
+        }
+        if monaco_language(&cfg.language) != monaco_language(&default.language) {
+            mask |= LANGUAGE;
+        }
+        if !cfg.files.is_empty() {
+            mask |= FILES;
+        }
+        mask.encode(encoder)?;
+        if mask & SUBJECT != 0 {
+            cfg.subject.encode(encoder)?;
+        }
+        if mask & LANGUAGE != 0 {
+            cfg.language.encode(encoder)?;
+        }
+        cfg.lhs.len().encode(encoder)?;
+        for unit in &cfg.lhs {
+            encode_unit(unit, encoder)?;
+        }
+        if mask & FILES != 0 {
+            cfg.files.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Context> Decode<Context> for Decoded {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mask = u32::decode(decoder)?;
+        if mask & !CONFIG_FIELDS != 0 {
+            return Err(DecodeError::Other("unknown config fields"));
+        }
+        let default = PlaygroundConfig::default();
+        let subject = if mask & SUBJECT != 0 {
+            String::decode(decoder)?
+        } else {
+            default.subject
+        };
+        let language = if mask & LANGUAGE != 0 {
+            Language::decode(decoder)?
+        } else {
+            default.language
+        };
+        let len = usize::decode(decoder)?;
+        // the length comes from the link, so it doesn't size the allocation up front
+        let mut lhs = Vec::new();
+        for _ in 0..len {
+            lhs.push(decode_unit(decoder)?);
+        }
+        let files = if mask & FILES != 0 {
+            Decode::decode(decoder)?
+        } else {
+            Vec::new()
+        };
+        Ok(Decoded(PlaygroundConfig {
+            subject,
+            language,
+            lhs,
+            files,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{CompressionLevel, HighlightStyle, LinkEncoding};
+
+    fn round_trip(cfg: &PlaygroundConfig) -> PlaygroundConfig {
+        let config = bincode::config::standard();
+        let bytes = bincode::encode_to_vec(Compact(cfg), config).unwrap();
+        let (Decoded(decoded), read) =
+            bincode::decode_from_slice::<Decoded, _>(&bytes, config).unwrap();
+        assert_eq!(read, bytes.len());
+        decoded
+    }
+
+    #[test]
+    fn default_config_round_trip() {
+        let cfg = PlaygroundConfig::default();
+        assert_eq!(format!("{:?}", round_trip(&cfg)), format!("{:?}", cfg));
+    }
+
+    /// a config setting every field the compact layout knows
+    fn every_field_config() -> PlaygroundConfig {
+        let mut cfg = PlaygroundConfig::from_editor_parts(
+            "os.system(cmd)\n",
+            "python",
+            "- name: os-system\n  patterns:\n    - os.system($CMD)\n- name: off\n  enabled: false\n  patterns:\n    - eval($X)\n",
+        )
+        .unwrap();
+        cfg.add_file("second", "rust", "hello_world(2);\n").unwrap();
+        let unit = &mut cfg.lhs[0];
+        unit.out.insert("cmd".to_string(), "$CMD".to_string());
+        unit.transform
+            .insert("CMD".to_string(), "s/a/b/".to_string());
+        unit.templates
+            .insert("t".to_string(), vec!["$CMD".to_string()]);
+        unit.style = HighlightStyle {
+            background: "#ff000040".to_string(),
+            ..Default::default()
+        };
+        unit.description = Some("runs a shell".to_string());
+        unit.docs_url = Some("https://example.com".to_string());
+        unit.tags = vec!["security".to_string()];
+        unit.max_matches = Some(3);
+        cfg
+    }
+
+    #[test]
+    fn every_field_round_trip() {
+        let cfg = every_field_config();
+        assert_eq!(format!("{:?}", round_trip(&cfg)), format!("{:?}", cfg));
+    }
+
+    #[test]
+    fn link_round_trip() {
+        let cfg = every_field_config();
+        // json links go through js_sys, which needs a browser
+        for encoding in [
+            LinkEncoding::BaseX,
+            LinkEncoding::Base64Url,
+            LinkEncoding::StrictSafe,
+        ] {
+            let (encoded, _) = cfg
+                .pending_link(encoding)
+                .encode_payload(CompressionLevel::Fast);
+            let decoded = PlaygroundConfig::from_encoded(&encoded).unwrap();
+            assert_eq!(format!("{:?}", decoded), format!("{:?}", cfg));
+        }
+    }
+
+    #[test]
+    fn other_tagged_versions_are_rejected() {
+        for version in [0, super::super::FORMAT_VERSION + 1] {
+            let payload = [super::super::FORMAT_TAG, version, 0, 0];
+            let compressed = zstd::encode_all(&payload[..], 0).unwrap();
+            assert!(PlaygroundConfig::from_compressed(&compressed).is_err());
+        }
+    }
+
+    #[test]
+    fn default_unit_is_its_mask_only() {
+        let cfg = PlaygroundConfig {
+            lhs: vec![MatchingUnit::default()],
+            ..Default::default()
+        };
+        let bytes = bincode::encode_to_vec(Compact(&cfg), bincode::config::standard()).unwrap();
+        // config mask, unit count, unit mask
+        assert_eq!(bytes, [0, 1, 0]);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let config = bincode::config::standard();
+        let unknown_config = bincode::encode_to_vec(CONFIG_FIELDS + 1, config).unwrap();
+        assert!(bincode::decode_from_slice::<Decoded, _>(&unknown_config, config).is_err());
+
+        let mut unknown_unit = bincode::encode_to_vec(0u32, config).unwrap();
+        unknown_unit.extend(bincode::encode_to_vec(1usize, config).unwrap());
+        unknown_unit.extend(bincode::encode_to_vec(UNIT_FIELDS + 1, config).unwrap());
+        assert!(bincode::decode_from_slice::<Decoded, _>(&unknown_unit, config).is_err());
+    }
+}
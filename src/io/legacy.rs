@@ -0,0 +1,107 @@
+//! payloads written by earlier versions of the playground, upgraded to the current DTO
+
+use std::collections::BTreeMap;
+
+use lexer_search_lib::{engine::graph::GroupInfo, io::Language};
+
+use super::{MatchingUnit, PlaygroundConfig};
+
+/// `MatchingUnit` before per-unit styles
+#[derive(bincode::Decode)]
+#[cfg_attr(test, derive(bincode::Encode))]
+struct MatchingUnitV1 {
+    patterns: Vec<String>,
+    name: String,
+    group: GroupInfo,
+    out: BTreeMap<String, String>,
+    transform: BTreeMap<String, String>,
+    templates: BTreeMap<String, Vec<String>>,
+}
+
+impl From<MatchingUnitV1> for MatchingUnit {
+    fn from(v1: MatchingUnitV1) -> Self {
+        MatchingUnit {
+            patterns: v1.patterns,
+            name: v1.name,
+            group: v1.group,
+            out: v1.out,
+            transform: v1.transform,
+            templates: v1.templates,
+            ..Default::default()
+        }
+    }
+}
+
+/// the original untagged payload, the only layout that shipped before the tagged ones
+#[derive(bincode::Decode)]
+#[cfg_attr(test, derive(bincode::Encode))]
+struct PlaygroundConfigV1 {
+    subject: String,
+    language: Language,
+    lhs: Vec<MatchingUnitV1>,
+}
+
+/// decode a payload written before link formats were tagged with a version
+pub fn decode_untagged(bytes: &[u8]) -> Result<PlaygroundConfig, String> {
+    let (v1, _) =
+        bincode::decode_from_slice::<PlaygroundConfigV1, _>(bytes, bincode::config::standard())
+            .map_err(|e| e.to_string())?;
+    Ok(PlaygroundConfig {
+        subject: v1.subject,
+        language: v1.language,
+        lhs: v1.lhs.into_iter().map(Into::into).collect(),
+        files: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untagged_v1_round_trip() {
+        let cfg = PlaygroundConfig::from_editor_parts(
+            "hello_world(1);\n",
+            "python",
+            "- name: hi\n  patterns:\n    - hello_world($X)\n",
+        )
+        .unwrap();
+        let unit = &cfg.lhs[0];
+        let v1 = PlaygroundConfigV1 {
+            subject: cfg.subject.clone(),
+            language: serde_yml::from_str("python").unwrap(),
+            lhs: vec![MatchingUnitV1 {
+                patterns: unit.patterns.clone(),
+                name: unit.name.clone(),
+                group: unit.group.clone(),
+                out: BTreeMap::from([("x".to_string(), "$X".to_string())]),
+                transform: BTreeMap::new(),
+                templates: BTreeMap::new(),
+            }],
+        };
+        let bytes = bincode::encode_to_vec(&v1, bincode::config::standard()).unwrap();
+
+        let decoded = decode_untagged(&bytes).unwrap();
+        assert_eq!(decoded.subject, cfg.subject);
+        assert_eq!(
+            format!("{:?}", decoded.language),
+            format!("{:?}", cfg.language)
+        );
+        assert!(decoded.files.is_empty());
+        let [decoded_unit] = &decoded.lhs[..] else {
+            panic!("expected one unit, got {:?}", decoded.lhs);
+        };
+        assert_eq!(decoded_unit.name, "hi");
+        assert_eq!(decoded_unit.patterns, unit.patterns);
+        assert_eq!(decoded_unit.out.get("x").map(String::as_str), Some("$X"));
+        // fields added later take their defaults
+        assert!(decoded_unit.enabled);
+        assert!(decoded_unit.style.is_default());
+        assert_eq!(decoded_unit.max_matches, None);
+    }
+
+    #[test]
+    fn untagged_garbage_is_an_error() {
+        assert!(decode_untagged(&[0xFE, 0xFE, 0xFE]).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use gloo::storage::{LocalStorage, Storage};
+use web_sys::HtmlInputElement;
+use yew::{
+    Callback, Component, Context, Html, InputEvent, KeyboardEvent, NodeRef, Properties, TargetCast,
+    html,
+};
+
+use crate::i18n::tr;
+
+const RECENT_STORAGE_KEY: &str = "lexer-search-ui.recent-languages";
+const MAX_RECENT: usize = 5;
+
+/// monaco language id, display name. C has no entry of its own: it is stored and
+/// highlighted as "cpp", so picking "c" would turn into C++ on the next reload
+pub const LANGUAGES: &[(&str, &str)] = &[
+    ("cpp", "C / C++"),
+    ("csharp", "C#"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("javascript", "JavaScript"),
+    ("kotlin", "Kotlin"),
+    ("python", "Python"),
+    ("rust", "Rust"),
+    ("typescript", "TypeScript"),
+];
+
+pub fn language_label(id: &str) -> &str {
+    LANGUAGES
+        .iter()
+        .find(|(lang, _)| *lang == id)
+        .map(|(_, label)| *label)
+        .unwrap_or(id)
+}
+
+/// most recently chosen languages, most recent first
+pub fn recent_languages() -> Vec<String> {
+    let recent: Vec<String> = LocalStorage::get(RECENT_STORAGE_KEY).unwrap_or_default();
+    recent
+        .into_iter()
+        .filter(|r| LANGUAGES.iter().any(|(lang, _)| lang == r))
+        .collect()
+}
+
+fn remember_language(lang: &str) -> Vec<String> {
+    let mut recent = recent_languages();
+    recent.retain(|r| r != lang);
+    recent.insert(0, lang.to_string());
+    recent.truncate(MAX_RECENT);
+    let _ = LocalStorage::set(RECENT_STORAGE_KEY, &recent);
+    recent
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LanguagePickerProps {
+    /// monaco language id
+    pub current: String,
+    pub on_change: Callback<String>,
+    /// monaco language ids to offer, all of `LANGUAGES` if empty
+    #[prop_or_default]
+    pub offered: Vec<String>,
+}
+
+pub enum PickerMsg {
+    Toggle,
+    Filter(String),
+    Pick(String),
+    KeyDown(KeyboardEvent),
+}
+
+/// searchable language dropdown, listing recently used languages first
+pub struct LanguagePicker {
+    open: bool,
+    filter: String,
+    recent: Vec<String>,
+    input_ref: NodeRef,
+    /// the filter input was just shown and gets the focus once it is rendered
+    focus_input: bool,
+}
+
+impl LanguagePicker {
+    /// offered languages matching the filter, recent ones first
+    fn candidates(&self, props: &LanguagePickerProps) -> Vec<(&'static str, &'static str, bool)> {
+        let filter = self.filter.to_lowercase();
+        let matches = |(lang, label): &(&str, &str)| {
+            (props.offered.is_empty() || props.offered.iter().any(|o| o == lang))
+                && (filter.is_empty()
+                    || lang.contains(&filter)
+                    || label.to_lowercase().contains(&filter))
+        };
+
+        let mut out: Vec<_> = self
+            .recent
+            .iter()
+            .filter_map(|r| LANGUAGES.iter().find(|(lang, _)| lang == r))
+            .filter(|l| matches(l))
+            .map(|(lang, label)| (*lang, *label, true))
+            .collect();
+        out.extend(
+            LANGUAGES
+                .iter()
+                .filter(|(lang, _)| !self.recent.iter().any(|r| r == lang))
+                .filter(|l| matches(l))
+                .map(|(lang, label)| (*lang, *label, false)),
+        );
+        out
+    }
+}
+
+impl Component for LanguagePicker {
+    type Message = PickerMsg;
+    type Properties = LanguagePickerProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            open: false,
+            filter: String::new(),
+            recent: recent_languages(),
+            input_ref: NodeRef::default(),
+            focus_input: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PickerMsg::Toggle => {
+                self.open = !self.open;
+                self.focus_input = self.open;
+                self.filter.clear();
+                true
+            }
+            PickerMsg::Filter(filter) => {
+                self.filter = filter;
+                true
+            }
+            PickerMsg::Pick(lang) => {
+                self.open = false;
+                self.filter.clear();
+                self.recent = remember_language(&lang);
+                ctx.props().on_change.emit(lang);
+                true
+            }
+            PickerMsg::KeyDown(e) => match e.key().as_str() {
+                "Escape" => {
+                    self.open = false;
+                    true
+                }
+                "Enter" => match self.candidates(ctx.props()).first() {
+                    Some((lang, _, _)) => self.update(ctx, PickerMsg::Pick(lang.to_string())),
+                    None => false,
+                },
+                _ => false,
+            },
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if !std::mem::take(&mut self.focus_input) {
+            return;
+        }
+        if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+            let _ = input.focus();
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let current = ctx.props().current.clone();
+
+        html! {
+            <div style="position:relative;">
+                <button onclick={ctx.link().callback(|_| PickerMsg::Toggle)}>
+                    { format!("{} ▾", language_label(&current)) }
+                </button>
+
+                { if self.open {
+                    html! {
+                        <div style="
+                            position:absolute;
+                            top:100%;
+                            left:0;
+                            z-index:10;
+                            min-width:180px;
+                            background:#2d2d2d;
+                            border:1px solid #444;
+                            padding:4px;
+                        ">
+                            <input
+                                ref={self.input_ref.clone()}
+                                placeholder={tr!("filter-languages")}
+                                value={self.filter.clone()}
+                                style="width:100%; box-sizing:border-box;"
+                                oninput={ctx.link().callback(|e: InputEvent| {
+                                    PickerMsg::Filter(e.target_unchecked_into::<HtmlInputElement>().value())
+                                })}
+                                onkeydown={ctx.link().callback(PickerMsg::KeyDown)}
+                            />
+                            { for self.candidates(ctx.props()).into_iter().map(|(lang, label, recent)| {
+                                let selected = lang == current;
+                                html! {
+                                    <div
+                                        style={format!(
+                                            "padding:2px 4px; cursor:pointer; {}",
+                                            if selected { "background:#094771;" } else { "" }
+                                        )}
+                                        onclick={ctx.link().callback(move |_| PickerMsg::Pick(lang.to_string()))}
+                                    >
+                                        { label }
+                                        { if recent {
+                                            html! { <span style="color:#888; float:right;">{ tr!("recent-language") }</span> }
+                                        } else {
+                                            html! {}
+                                        }}
+                                    </div>
+                                }
+                            })}
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        }
+    }
+}
@@ -0,0 +1,118 @@
+/// width of the drag handle between the panes
+pub const SPLITTER_WIDTH: i32 = 6;
+const MIN_PANE_WIDTH: i32 = 200;
+/// pixels the splitter moves per arrow key press
+pub const KEYBOARD_STEP: i32 = 20;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Pane {
+    Patterns,
+    Subject,
+}
+
+impl Pane {
+    pub fn other(self) -> Self {
+        match self {
+            Pane::Patterns => Pane::Subject,
+            Pane::Subject => Pane::Patterns,
+        }
+    }
+}
+
+/// how the two editors are arranged
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Layout {
+    /// both panes visible, `first` on the left at `first_width` pixels
+    Split { first: Pane, first_width: i32 },
+    /// only `pane` is visible. the split is kept so it can be restored
+    Maximized {
+        pane: Pane,
+        first: Pane,
+        first_width: i32,
+    },
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::Split {
+            first: Pane::Patterns,
+            first_width: 500,
+        }
+    }
+}
+
+impl Layout {
+    pub fn swap(self) -> Self {
+        match self {
+            Layout::Split { first, first_width } => Layout::Split {
+                first: first.other(),
+                first_width,
+            },
+            Layout::Maximized {
+                pane,
+                first,
+                first_width,
+            } => Layout::Maximized {
+                pane,
+                first: first.other(),
+                first_width,
+            },
+        }
+    }
+
+    pub fn maximize(self, pane: Pane) -> Self {
+        let (first, first_width) = self.split();
+        Layout::Maximized {
+            pane,
+            first,
+            first_width,
+        }
+    }
+
+    pub fn restore(self) -> Self {
+        let (first, first_width) = self.split();
+        Layout::Split { first, first_width }
+    }
+
+    /// resize the left pane while split. ignored when maximized
+    pub fn drag_to(&mut self, x: i32) {
+        if let Layout::Split { first_width, .. } = self {
+            *first_width = x.max(MIN_PANE_WIDTH);
+        }
+    }
+
+    /// width of the left pane, kept while maximized
+    pub fn first_width(&self) -> i32 {
+        self.split().1
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        matches!(self, Layout::Maximized { .. })
+    }
+
+    fn split(self) -> (Pane, i32) {
+        match self {
+            Layout::Split { first, first_width }
+            | Layout::Maximized {
+                first, first_width, ..
+            } => (first, first_width),
+        }
+    }
+
+    /// flex order of the pane (0 = left, 2 = right; the splitter sits at 1)
+    pub fn order(&self, pane: Pane) -> i32 {
+        if self.split().0 == pane { 0 } else { 2 }
+    }
+
+    /// pixel width of the pane given the window width, `None` if hidden
+    pub fn width(&self, pane: Pane, total_width: i32) -> Option<i32> {
+        match *self {
+            Layout::Maximized { pane: shown, .. } => (shown == pane).then_some(total_width),
+            Layout::Split { first, first_width } => Some(if first == pane {
+                first_width
+            } else {
+                (total_width - first_width - SPLITTER_WIDTH).max(MIN_PANE_WIDTH)
+            }),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! checks a share link against the ways links get mangled on their way to someone else:
+//! proxies that percent-encode or cut at reserved characters, markdown that ends a link at
+//! a parenthesis, and autolinkers that leave trailing punctuation out of the link
+//!
+//! a link with findings can be encoded again as `LinkEncoding::StrictSafe`, whose
+//! characters pass all of them
+
+use crate::i18n::tr;
+
+/// a way a link can be mangled
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Rule {
+    /// sub-delimiters and quotes, which some reverse proxies and firewalls encode or reject
+    Reserved,
+    /// characters that end or restyle a link in markdown
+    Markdown,
+    /// a last character that autolinkers in chat tools take as punctuation
+    Trailing,
+}
+
+impl Rule {
+    pub fn id(self) -> &'static str {
+        match self {
+            Rule::Reserved => "reserved",
+            Rule::Markdown => "markdown",
+            Rule::Trailing => "trailing",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("link-audit-{}", self.id()))
+    }
+
+    fn breaks_on(self, c: char) -> bool {
+        match self {
+            Rule::Reserved => "'\";!*$&+,=@()[]".contains(c),
+            Rule::Markdown => "()[]*`<>".contains(c),
+            Rule::Trailing => "?!.,:;*_~'\")".contains(c),
+        }
+    }
+}
+
+/// a rule the link breaks, with the characters it breaks it with
+#[derive(PartialEq, Debug)]
+pub struct Finding {
+    pub rule: Rule,
+    pub chars: String,
+}
+
+/// audit the encoded part of a link, i.e. the fragment after the public url
+pub fn audit(encoded: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for rule in [Rule::Reserved, Rule::Markdown] {
+        let mut chars: Vec<char> = encoded.chars().filter(|&c| rule.breaks_on(c)).collect();
+        chars.sort_unstable();
+        chars.dedup();
+        if !chars.is_empty() {
+            findings.push(Finding {
+                rule,
+                chars: chars.into_iter().collect(),
+            });
+        }
+    }
+    if let Some(last) = encoded
+        .chars()
+        .last()
+        .filter(|&c| Rule::Trailing.breaks_on(c))
+    {
+        findings.push(Finding {
+            rule: Rule::Trailing,
+            chars: last.to_string(),
+        });
+    }
+    findings
+}
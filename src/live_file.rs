@@ -0,0 +1,75 @@
+//! subject files opened from disk through the File System Access API. the file's handle is
+//! kept, so it can be read again after it was changed in another editor, on request or by
+//! polling its modification time
+
+use wasm_bindgen::{JsValue, prelude::*};
+use yew::Callback;
+
+#[wasm_bindgen(module = "/src/live_file_helper.js")]
+extern "C" {
+    fn live_files_supported_js() -> bool;
+    fn open_live_file_js(done: JsValue);
+    fn read_live_file_js(handle: &JsValue, done: JsValue);
+}
+
+/// a subject file that came from disk
+pub struct LiveFile {
+    handle: JsValue,
+    /// name of the subject file it was loaded into
+    pub name: String,
+    /// `lastModified` of the file as last read, in ms since the epoch
+    pub last_modified: f64,
+}
+
+/// what was read from a file
+pub struct Contents {
+    pub text: String,
+    pub last_modified: f64,
+}
+
+/// whether the browser can open files for reloading
+pub fn supported() -> bool {
+    live_files_supported_js()
+}
+
+/// ask for a file and read it. nothing is emitted if the picker is cancelled
+pub fn open(done: Callback<Result<(LiveFile, String), String>>) {
+    let closure = Closure::once_into_js(
+        move |handle: JsValue,
+              name: Option<String>,
+              text: Option<String>,
+              last_modified: f64,
+              error: Option<String>| {
+            done.emit(match (name, text, error) {
+                (Some(name), Some(text), None) => Ok((
+                    LiveFile {
+                        handle,
+                        name,
+                        last_modified,
+                    },
+                    text,
+                )),
+                (_, _, error) => Err(error.unwrap_or_default()),
+            })
+        },
+    );
+    open_live_file_js(closure);
+}
+
+impl LiveFile {
+    /// read the file again
+    pub fn read(&self, done: Callback<Result<Contents, String>>) {
+        let closure = Closure::once_into_js(
+            move |text: Option<String>, last_modified: f64, error: Option<String>| {
+                done.emit(match (text, error) {
+                    (Some(text), None) => Ok(Contents {
+                        text,
+                        last_modified,
+                    }),
+                    (_, error) => Err(error.unwrap_or_default()),
+                })
+            },
+        );
+        read_live_file_js(&self.handle, closure);
+    }
+}
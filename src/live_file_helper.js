@@ -0,0 +1,29 @@
+// local files opened through the File System Access API, which only Chromium-based
+// browsers have. the handles stay valid for the page's lifetime, so a file can be read again
+// after another editor saved it
+
+export function live_files_supported_js() {
+    return typeof globalThis.showOpenFilePicker === "function";
+}
+
+const message = e => String((e && e.message) || e);
+
+// asks for a file and reads it, then calls `done(handle, name, text, lastModified, null)` or
+// `done(null, null, null, 0, error)`. cancelling the picker calls nothing
+export function open_live_file_js(done) {
+    globalThis.showOpenFilePicker({ multiple: false })
+        .then(([handle]) => handle.getFile().then(file => file.text().then(text =>
+            done(handle, file.name, text, file.lastModified, null))))
+        .catch(e => {
+            if (e && e.name === "AbortError") return;
+            done(null, null, null, 0, message(e));
+        });
+}
+
+// reads the file behind `handle` again, calling `done(text, lastModified, null)` or
+// `done(null, 0, error)`
+export function read_live_file_js(handle, done) {
+    handle.getFile()
+        .then(file => file.text().then(text => done(text, file.lastModified, null)))
+        .catch(e => done(null, 0, message(e)));
+}
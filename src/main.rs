@@ -1,27 +1,111 @@
+pub mod api;
+pub mod batch_decode;
+pub mod deploy;
+pub mod detect;
+pub mod diff_mode;
+pub mod explain;
+pub mod hook;
+pub mod i18n;
 pub mod io;
+pub mod language_picker;
+pub mod layout;
+pub mod link_audit;
+pub mod live_file;
+pub mod memory;
+pub mod overlap;
+pub mod pattern_docs;
+pub mod positions;
+pub mod presets;
+pub mod print_view;
+pub mod recovery;
+pub mod regex_scratchpad;
+pub mod results;
+pub mod results_panel;
+pub mod rule_diff;
+pub mod rule_packs;
+pub mod rules;
+pub mod scrub;
+pub mod self_test;
+pub mod sessions;
+pub mod settings;
+pub mod shortener;
+pub mod snapshot;
+pub mod snippets;
+pub mod stream;
+pub mod styles;
+pub mod tokens;
+pub mod tutorial;
+pub mod usage;
+pub mod validate;
+pub mod workspace;
 
-use gloo::events::EventListener;
-use lexer_search_lib::io::final_postprocess;
+use gloo::events::{EventListener, EventListenerOptions, EventListenerPhase};
 use monaco::{
     api::CodeEditorOptions,
-    sys::editor::BuiltinTheme,
     yew::{CodeEditor, CodeEditorLink},
 };
-use serde::Serialize;
-use serde_json::Value;
-use std::{cell::RefCell, rc::Rc};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet},
+    hash::{DefaultHasher, Hash, Hasher},
+    rc::Rc,
+    time::Duration,
+};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{MouseEvent, window};
+use web_sys::{BeforeUnloadEvent, KeyboardEvent, MouseEvent, window};
 use yew::{Callback, Component, Context, Html, Properties, html};
 
-use crate::io::PlaygroundConfig;
+use crate::{
+    api::{SearchMatch, count, search_compiled},
+    batch_decode::BatchDecodeDialog,
+    deploy::DeployConfig,
+    detect::{MIN_PASTE_CHARS, guess_language},
+    diff_mode::DiffSubjectEditor,
+    explain::{Explanation, NearMiss, RunSource},
+    hook::{HookStyle, ResultHook},
+    i18n::tr,
+    io::{
+        CompiledPatterns, Deadline, EditorFile, LinkEncoding, LinkStats, MAIN_FILE_NAME,
+        MatchingUnit, PatternError, PendingLink, PlaygroundConfig, PlaygroundError, format_lhs,
+        parse_lhs, resolve_language, set_transform, short_id_from_url_str,
+    },
+    language_picker::{LanguagePicker, language_label, recent_languages},
+    layout::{KEYBOARD_STEP, Layout, Pane, SPLITTER_WIDTH},
+    link_audit::{Finding, audit},
+    live_file::{Contents, LiveFile},
+    memory::{heap_in_use, megabytes, memory_size},
+    overlap::OverlapMode,
+    pattern_docs::{PATTERN_DOCS, PATTERN_GUIDE_URL},
+    positions::ColumnMap,
+    print_view::PrintView,
+    regex_scratchpad::RegexScratchpad,
+    results::{InlayHint, ResultOrder, ResultsTab, RuleDoc, RuleDocs, RunResults, RunSummary},
+    results_panel::ResultsPanel,
+    rule_diff::RuleDiffDialog,
+    rule_packs::{RulePack, by_category, fetch_index, fetch_pack},
+    rules::{
+        add_rule, duplicate_rule, merge_rules, pattern_from_selection, rule_line_ranges, rule_list,
+        rule_names, rule_tags, set_enabled, unique_name,
+    },
+    self_test::{SelfTestDialog, is_self_test_url},
+    sessions::{Session, Sessions},
+    settings::{Settings, SettingsPanel},
+    snapshot::{Snapshot, SnapshotDiff},
+    snippets::SnippetLibrary,
+    stream::{ChunkScanner, ScanProgress, SlicedRun, scan_file},
+    styles::{UnitStyles, unit_border_class},
+    tutorial::{TUTORIAL_LHS, TUTORIAL_SUBJECT, TutorialPanel, TutorialStep},
+    usage::{UsageEntry, UsageLog, UsagePanel},
+    validate::{SchemaError, validate_lhs},
+};
 
 // --------------------
 // JS helper function
 // --------------------
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct HighlightElement {
     pub start_line: usize,
     pub start_col: usize,
@@ -29,11 +113,202 @@ pub struct HighlightElement {
     pub end_col: usize,
     pub class_name: String,
     pub text: Option<String>,
+    /// shown in front of the range, e.g. a capture's name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// a non-empty editor selection, 1-based and end exclusive like monaco's ranges
+/// lines of one unit in the lhs editor, 1-based and inclusive
+#[derive(Serialize)]
+struct UnitBlock {
+    start_line: usize,
+    end_line: usize,
+    class_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct EditorSelection {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+    pub text: String,
+}
+
+/// checkpoints kept for undo
+const MAX_UNDO: usize = 50;
+/// states kept under "Recently replaced"
+const MAX_REPLACED: usize = 10;
+
+/// what a load replaced, kept for the rest of the session
+struct Replaced {
+    /// what replaced it
+    by: String,
+    /// `Date::now()` when it was replaced
+    at: f64,
+    entry: UndoEntry,
+}
+
+/// what a session keeps while another one is shown. only the editors are stored, this is
+/// lost with the page
+#[derive(Default)]
+struct ParkedSession {
+    results: Rc<RunResults>,
+    file_highlights: Vec<Vec<HighlightElement>>,
+    summary: Option<RunSummary>,
+    baseline: (String, Vec<EditorFile>),
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+}
+
+/// the editors' content before an action replaced it
+#[derive(Clone, PartialEq)]
+struct UndoEntry {
+    lhs: String,
+    files: Vec<EditorFile>,
+    active_file: usize,
+}
+
+/// outcome of "Check patterns", shown until the lhs changes
+struct PatternCheck {
+    lhs: String,
+    /// patterns of the enabled rules, as written
+    total: usize,
+    failed: usize,
+}
+
+/// a run's matches, ready to be shown
+struct FinishedRun {
+    started: f64,
+    /// the rules as written, whatever the tag filter left to run
+    units: Vec<MatchingUnit>,
+    matches: Vec<SearchMatch>,
+    highlights: Vec<Vec<HighlightElement>>,
+    skipped: Vec<PatternError>,
+    truncated: BTreeSet<String>,
+    timed_out: Option<u32>,
+    rule_count: usize,
+    source: RunSource,
+}
+
+/// a run matched a slice per frame, see `SlicedRun`, with what its results need once the
+/// last slice is done
+struct SlicedState {
+    run: SlicedRun,
+    matches: Vec<SearchMatch>,
+    started: f64,
+    units: Vec<MatchingUnit>,
+    styles: UnitStyles,
+    docs: RuleDocs,
+    rule_order: Vec<String>,
+    rule_count: usize,
+    source: RunSource,
+    deadline: Deadline,
+    /// the next slice waits for a frame, or for a timeout while the page is hidden and
+    /// gets no frames. dropping either cancels it
+    _next_frame: Option<gloo::render::AnimationFrame>,
+    _next_timeout: Option<gloo::timers::callback::Timeout>,
+}
+
+/// limits a run to the selected part of one file
+struct SelectionScope {
+    file: usize,
+    selection: EditorSelection,
+}
+
+impl SelectionScope {
+    /// scan only the selected text, and nothing of the other files
+    fn restrict(&self, cfg: &mut PlaygroundConfig) {
+        let text = &self.selection.text;
+        cfg.subject = if self.file == 0 {
+            text.clone()
+        } else {
+            String::new()
+        };
+        for (i, file) in cfg.files.iter_mut().enumerate() {
+            file.content = if i + 1 == self.file {
+                text.clone()
+            } else {
+                String::new()
+            };
+        }
+    }
+
+    /// move a match found in the selected text to its position in the file
+    fn shift(&self, m: &mut SearchMatch) {
+        let (line, col) = (self.selection.start_line, self.selection.start_col);
+        if m.start_line == 1 {
+            m.start_col += col - 1;
+        }
+        if m.end_line == 1 {
+            m.end_col += col - 1;
+        }
+        m.start_line += line - 1;
+        m.end_line += line - 1;
+    }
+}
+
+impl HighlightElement {
+    fn from_match(m: &SearchMatch, class_name: &str, doc: Option<&RuleDoc>) -> Self {
+        let mut elem = HighlightElement {
+            start_line: m.start_line,
+            start_col: m.start_col,
+            end_line: m.end_line,
+            end_col: m.end_col,
+            class_name: class_name.to_owned(),
+            text: Some(if !m.captures.is_empty() {
+                let captures_str = serde_json::to_string(&m.captures).unwrap_or_default();
+                if m.name.is_empty() {
+                    captures_str
+                } else {
+                    format!("{}: {}", m.name, captures_str)
+                }
+            } else {
+                // Just the name
+                m.name.clone()
+            }),
+            label: None,
+        };
+        if let (Some(text), Some(doc)) = (&mut elem.text, doc) {
+            text.push_str("\n\n");
+            text.push_str(&doc.hover_markdown());
+        }
+        elem
+    }
+
+    fn range(&self) -> (usize, usize, usize, usize) {
+        (self.start_line, self.start_col, self.end_line, self.end_col)
+    }
 }
 
 #[wasm_bindgen(module = "/src/highlight_helper.js")]
 extern "C" {
     fn highlight_ranges_js(editor: &JsValue, elements: &JsValue);
+    fn on_content_change_js(editor: &JsValue, callback: JsValue);
+    fn on_mouse_down_js(editor: &JsValue, callback: JsValue);
+    fn on_cursor_change_js(editor: &JsValue, callback: JsValue);
+    fn on_paste_js(editor: &JsValue, callback: JsValue);
+    fn set_unit_borders_js(editor: &JsValue, blocks: &JsValue);
+    fn set_inlay_hints_js(editor: &JsValue, hints: &JsValue);
+    fn set_markers_js(editor: &JsValue, markers: &JsValue);
+    fn register_pattern_docs_js(docs: &JsValue, guide_url: &str);
+    fn selection_js(editor: &JsValue) -> JsValue;
+    fn reveal_range_js(
+        editor: &JsValue,
+        start_line: usize,
+        start_col: usize,
+        end_line: usize,
+        end_col: usize,
+    );
+    fn set_theme_js(theme: &str);
+    fn center_range_js(
+        editor: &JsValue,
+        start_line: usize,
+        start_col: usize,
+        end_line: usize,
+        end_col: usize,
+    );
 }
 
 // --------------------
@@ -58,11 +333,278 @@ fn url_path() -> String {
         .to_string()
 }
 
+/// share links longer than this get truncated or rejected by some chat tools and browsers
+const LONG_LINK_LEN: usize = 8000;
+/// matches listed in the status bar for the cursor position, innermost first
+const MAX_CURSOR_MATCHES: usize = 3;
+/// distinct values listed by the aggregate tab
+const MAX_AGGREGATE_ROWS: usize = 500;
+/// pause in typing before an auto-run
+const AUTO_RUN_DELAY_MS: u32 = 400;
+/// pause in typing before the shown session is saved
+const SESSION_SAVE_DELAY_MS: u32 = 1000;
+/// how often files opened from disk are checked for changes, while that is on
+const LIVE_FILE_POLL_MS: u32 = 2000;
+
+/// feed `text` to `hasher`, line by line without trailing whitespace if `trim`
+fn hash_text(hasher: &mut DefaultHasher, text: &str, trim: bool) {
+    if trim {
+        for line in text.lines() {
+            line.trim_end().hash(hasher);
+        }
+    } else {
+        text.hash(hasher);
+    }
+}
+
+/// fetch the config behind a short link, answering with `Msg::ShortLinkLoaded`
+fn resolve_short_link(ctx: &Context<App>, endpoint: String, id: String) {
+    let link = ctx.link().clone();
+    yew::platform::spawn_local(async move {
+        let result = crate::shortener::resolve(&endpoint, &id)
+            .await
+            .and_then(|encoded| PlaygroundConfig::from_encoded(&encoded));
+        link.send_message(Msg::ShortLinkLoaded(result));
+    });
+}
+
+/// resolve the short link `id` with the shortener set in the settings, the only one a link
+/// makes the page contact. an error if none is set or the id isn't one
+fn open_short_link(ctx: &Context<App>, id: &str, endpoint: &str) -> Result<(), String> {
+    if endpoint.is_empty() {
+        return Err(tr!("short-link-no-endpoint"));
+    }
+    if !crate::shortener::is_short_id(id) {
+        return Err(tr!("short-link-invalid-id"));
+    }
+    resolve_short_link(ctx, endpoint.to_string(), id.to_string());
+    Ok(())
+}
+
+/// a link to the playground with the given fragment
+fn share_link(fragment: &str) -> String {
+    let origin = window().unwrap().location().origin().unwrap();
+    format!("{}/{}{}", origin, crate::io::PUBLIC_URL, fragment)
+}
+
+/// copy a link to the playground with the given fragment to the clipboard
+fn copy_share_link(fragment: &str) {
+    copy_text(&share_link(fragment));
+}
+
+fn copy_text(text: &str) {
+    let win = web_sys::window().unwrap();
+    let _ = win.navigator().clipboard().write_text(text);
+}
+
+/// save `content` as a file through a temporary link
+fn download(file_name: &str, mime_type: &str, content: impl gloo::file::BlobContents) {
+    let blob = gloo::file::Blob::new_with_options(content, Some(mime_type));
+    let url = gloo::file::ObjectUrl::from(blob);
+    let link = gloo::utils::document().create_element("a").unwrap();
+    let _ = link.set_attribute("href", &url);
+    let _ = link.set_attribute("download", file_name);
+    // Firefox ignores clicks on links outside the document
+    let body = gloo::utils::body();
+    let _ = body.append_child(&link);
+    link.clone()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+    link.remove();
+    // dropping the url revokes it, which mustn't happen before the download has started
+    gloo::timers::callback::Timeout::new(10_000, move || drop(url)).forget();
+}
+
+/// the config of `lhs` over `files`, the first being the main subject
+fn config_from(files: &[EditorFile], lhs: &str) -> Result<PlaygroundConfig, PlaygroundError> {
+    let (main, rest) = files.split_first().unwrap();
+    let mut cfg = PlaygroundConfig::from_editor_parts(&main.content, &main.language, lhs)?;
+    for file in rest {
+        cfg.add_file(&file.name, &file.language, &file.content)?;
+    }
+    Ok(cfg)
+}
+
+/// what the regex scratchpad needs of each unit: its name and transforms
+fn scratchpad_units(units: &[MatchingUnit]) -> Rc<Vec<(String, BTreeMap<String, String>)>> {
+    Rc::new(
+        units
+            .iter()
+            .map(|u| (u.name.clone(), u.transform.clone()))
+            .collect(),
+    )
+}
+
+/// search with the compiled patterns, then reduce overlapping matches according to `mode`.
+/// also returns the patterns the compile left out and the units cut off by `max_matches`
+fn search_resolved(
+    mut cfg: PlaygroundConfig,
+    mode: OverlapMode,
+    scope: Option<&SelectionScope>,
+    compiled: &CompiledPatterns,
+    deadline: &Deadline,
+) -> Result<(Vec<SearchMatch>, Vec<PatternError>, BTreeSet<String>), PlaygroundError> {
+    if let Some(scope) = scope {
+        scope.restrict(&mut cfg);
+    }
+    let rule_order: Vec<String> = cfg.lhs.iter().map(|u| u.name.clone()).collect();
+    // taken before the subjects are consumed; a scope's shift is in UTF-16 columns too
+    let columns: Vec<ColumnMap> = std::iter::once(cfg.subject.as_str())
+        .chain(cfg.files.iter().map(|f| f.content.as_str()))
+        .map(ColumnMap::new)
+        .collect();
+    let (mut matches, truncated) = search_compiled(cfg, compiled, deadline)?;
+    for m in &mut matches {
+        if let Some(map) = columns.get(m.file) {
+            map.convert(m);
+        }
+    }
+    if let Some(scope) = scope {
+        matches.iter_mut().for_each(|m| scope.shift(m));
+    }
+    Ok((
+        crate::overlap::resolve(matches, mode, &rule_order),
+        compiled.skipped.clone(),
+        truncated,
+    ))
+}
+
+/// run the result hook over `matches` and highlight the ones it keeps, grouped by file
+fn present_matches(
+    mut matches: Vec<SearchMatch>,
+    file_count: usize,
+    styles: &UnitStyles,
+    docs: &RuleDocs,
+    hook: Result<Option<ResultHook>, String>,
+) -> Result<(Vec<SearchMatch>, Vec<Vec<HighlightElement>>), PlaygroundError> {
+    let hook_styles = match hook? {
+        Some(hook) => hook.filter(&mut matches)?,
+        None => Vec::new(),
+    };
+    let mut highlights = highlights_by_file(&matches, file_count, "match-highlight", styles, docs);
+    restyle(&mut highlights, &matches, &hook_styles);
+    Ok((matches, highlights))
+}
+
+/// one highlight per match, grouped by file
+fn highlights_by_file(
+    matches: &[SearchMatch],
+    file_count: usize,
+    class_name: &str,
+    styles: &UnitStyles,
+    docs: &RuleDocs,
+) -> Vec<Vec<HighlightElement>> {
+    let mut accumulate: Vec<Vec<HighlightElement>> = Vec::new();
+    accumulate.resize_with(file_count, Vec::new);
+    for m in matches {
+        let class_name = styles.class_for(class_name, &m.name);
+        accumulate[m.file].push(HighlightElement::from_match(
+            m,
+            &class_name,
+            docs.get(&m.name),
+        ));
+    }
+    accumulate
+}
+
+/// apply the result hook's styles to the highlights of `matches`, which are in the order
+/// `highlights_by_file` made them. no styles leaves them as they are
+fn restyle(
+    highlights: &mut [Vec<HighlightElement>],
+    matches: &[SearchMatch],
+    styles: &[HookStyle],
+) {
+    let mut next = vec![0; highlights.len()];
+    for (m, style) in matches.iter().zip(styles) {
+        if let Some(elem) = highlights[m.file].get_mut(next[m.file]) {
+            style.apply(elem);
+        }
+        next[m.file] += 1;
+    }
+}
+
+/// classify the matches of pattern sets A and B by range: A-only and B-only keep their
+/// classes, ranges matched by both are merged into a single "common" highlight
+fn compare_highlights(
+    a: Vec<HighlightElement>,
+    mut b: Vec<HighlightElement>,
+) -> Vec<HighlightElement> {
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    for mut elem in a {
+        match b.iter().position(|other| other.range() == elem.range()) {
+            Some(i) => {
+                let other = b.swap_remove(i);
+                elem.class_name = "match-highlight-common".to_owned();
+                elem.text = Some(format!(
+                    "{}\n\nA: {}\n\nB: {}",
+                    tr!("compare-both"),
+                    elem.text.unwrap_or_default(),
+                    other.text.unwrap_or_default()
+                ));
+            }
+            None => elem.text = Some(tr!("compare-a-only", text = elem.text.unwrap_or_default())),
+        }
+        out.push(elem);
+    }
+    for mut elem in b {
+        elem.text = Some(tr!("compare-b-only", text = elem.text.unwrap_or_default()));
+        out.push(elem);
+    }
+    out
+}
+
+/// current text of an editor, falling back to the options it was created with
+fn editor_content(
+    editor: &Rc<RefCell<Option<CodeEditorLink>>>,
+    options: &CodeEditorOptions,
+) -> String {
+    editor
+        .borrow()
+        .as_ref()
+        .and_then(|editor| editor.with_editor(|m| m.get_model().map(|m| m.get_value())))
+        .unwrap_or_else(|| options.value.clone())
+        .unwrap_or_default()
+}
+
+/// replace an editor's text and optionally its language
+fn set_editor_value(
+    editor: &Rc<RefCell<Option<CodeEditorLink>>>,
+    value: &str,
+    language: Option<&str>,
+) {
+    if let Some(editor) = &*editor.borrow() {
+        editor.with_editor(|e| {
+            if let Some(model) = e.get_model() {
+                model.set_value(value);
+                if let Some(language) = language {
+                    model.set_language(language);
+                }
+            }
+        });
+    }
+}
+
+/// stores the editor link and notifies the app whenever its content changes
+fn editor_created_callback(
+    editor: Rc<RefCell<Option<CodeEditorLink>>>,
+    on_change: Callback<()>,
+) -> Callback<CodeEditorLink> {
+    Callback::from(move |link: CodeEditorLink| {
+        let on_change = on_change.clone();
+        link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+            let js_editor: &JsValue = editor_api.as_ref();
+            let closure = Closure::<dyn FnMut()>::new(move || on_change.emit(()));
+            on_content_change_js(js_editor, closure.into_js_value());
+        });
+        *editor.borrow_mut() = Some(link);
+    })
+}
+
 fn editor_options(content: String, lang: String) -> CodeEditorOptions {
     CodeEditorOptions::default()
         .with_language(lang)
         .with_value(content)
-        .with_builtin_theme(BuiltinTheme::VsDark)
+        .with_builtin_theme(deploy::theme())
         .with_automatic_layout(true)
 }
 
@@ -106,10 +648,167 @@ enum Msg {
     StartDrag,
     Drag(i32),
     StopDrag,
+    SwapPanels,
+    Maximize(Pane),
+    RestoreLayout,
     LanguageChanged(String),
     CopyShareLink,
+    /// copy the share link again in `LinkEncoding::StrictSafe`
+    CopyStrictLink,
     Run,
     ClearHighlights,
+    ShowMoreHighlights,
+    CheckPatterns,
+    /// compile the initial patterns once the page has painted, see `engine_ready`
+    WarmUpEngine,
+    Undo,
+    Redo,
+    SortResults(ResultOrder),
+    ShowResultsTab(ResultsTab),
+    CopyOutputs,
+    ExportOutputs,
+    /// the capture name the aggregate tab groups by
+    AggregateBy(String),
+    /// list only the matches binding (capture name, value), or all
+    FilterResults(Option<(String, String)>),
+    FormatPatterns,
+    AddRule,
+    PatternFromSelection,
+    ToggleRulesMenu,
+    DuplicateRule(usize),
+    ToggleRulePacks,
+    RulePacksLoaded(Result<Vec<RulePack>, String>),
+    /// the deployment's `playground-config.json`, and whether the page opened without a
+    /// link or restored editors, so its preset may be loaded
+    DeployConfigLoaded(Result<Option<DeployConfig>, String>, bool),
+    PickRulePack(RulePack),
+    /// the rules of the named pack were downloaded
+    RulePackLoaded(String, Result<Vec<MatchingUnit>, String>),
+    ToggleScratchpad,
+    SetRuleEnabled(usize, bool),
+    ApplyTransform(usize, String, String),
+    ContentChanged,
+    SaveSnippetAs,
+    ToggleSnippets,
+    ToggleReplaced,
+    /// load what a load replaced, by index into `replaced`
+    RestoreReplaced(usize),
+    SelectSession(usize),
+    NewSession,
+    RenameSession(usize),
+    CloseSession(usize),
+    SaveSession,
+    LoadSnippet(String),
+    RenameSnippet(String),
+    DuplicateSnippet(String),
+    DeleteSnippet(String),
+    ToggleCompare,
+    SwitchFile(usize),
+    AddFile,
+    RenameFile(usize),
+    RemoveFile(usize),
+    Shorten,
+    /// the short id, and the editors as they were shortened, which become the baseline
+    /// once it is stored
+    ShortLinkCreated(Result<String, String>, (String, Vec<EditorFile>)),
+    /// a share link finished encoding in the background. copy it when the flag is set
+    LinkEncoded(String, LinkStats, bool),
+    ShortLinkLoaded(Result<PlaygroundConfig, String>),
+    /// the url fragment changed, e.g. a share link was pasted into the open tab
+    HashChanged,
+    OpenLiveFile,
+    /// a file picked from disk and its text
+    LiveFileOpened(Result<(LiveFile, String), String>),
+    /// read the files opened from disk again. with the flag set even if they didn't change
+    ReloadLiveFiles(bool),
+    /// a file from disk read again, by subject file name
+    LiveFileRead(String, Result<Contents, String>, bool),
+    ToggleLivePolling,
+    /// copy a link with the last run's matches baked in
+    ShareResults,
+    /// matches that came with a link, shown without running
+    ShowSharedResults(Vec<SearchMatch>),
+    /// the subject editor exists, so decorations can be applied
+    SubjectEditorReady,
+    /// replace everything with the opened link's config
+    ReplaceWithIncoming,
+    /// add the opened link's rules to the current ones, keeping the subject
+    MergeIncoming,
+    DismissIncoming,
+    ToggleSettings,
+    SettingsChanged(Settings),
+    SubjectClicked(usize, usize),
+    /// the cursor of the subject editor moved, 1-based line and column
+    CursorMoved(usize, usize),
+    SubjectPasted(String),
+    ToggleTag(String),
+    ClearTagFilter,
+    AcceptLanguageSuggestion,
+    DismissLanguageSuggestion,
+    /// replace an untouched session with the example of the language it was switched to
+    LoadPreset,
+    DismissPreset,
+    RevealMatch(usize),
+    /// reveal the match after the selected one in the results order, or before it when
+    /// true. wraps around
+    StepMatch(bool),
+    ClosePeek,
+    /// explain how the match at this index came about
+    ExplainMatch(usize),
+    CloseExplanation,
+    /// explain why the rule at this index has no matches
+    ExplainNoMatch(usize),
+    RevealNearMiss(usize),
+    CloseNearMisses,
+    ExportSnapshot,
+    SnapshotPicked(gloo::file::File),
+    SnapshotLoaded(Result<String, String>),
+    CloseSnapshotDiff,
+    ExportWorkspace,
+    WorkspacePicked(gloo::file::File),
+    WorkspaceLoaded(Result<Vec<u8>, String>),
+    ScanFilePicked(gloo::file::File),
+    ScanChunk(ScanProgress),
+    ScanFinished(Result<bool, PlaygroundError>),
+    CancelScan,
+    /// match the next slice of a sliced run
+    RunSlice,
+    /// stop a sliced run, showing the matches so far
+    CancelRun,
+    /// the editors have been still for `AUTO_RUN_DELAY_MS`
+    AutoRun,
+    FreeMemory,
+    TogglePrintView,
+    ToggleUsage,
+    ToggleRuleDiff,
+    ToggleBatchDecode,
+    CloseSelfTest,
+    /// rules merged in by the batch decode dialog, and the line to reveal
+    BatchMerged(String, Option<usize>),
+    ToggleDiffMode,
+    DiffEditorCreated(JsValue),
+    ClearUsage,
+    StartTutorial,
+    /// check the current tutorial step and move on if it is done
+    TutorialNext,
+    CloseTutorial,
+    ClosePatternErrors,
+    /// show a 1-based line and column of the lhs editor
+    RevealLhs(usize, usize),
+    ToggleCountOnly,
+    RunOnSelection,
+    ToggleResults,
+    ToggleInlayHints,
+    ToggleCaptureRanges,
+    ToggleLinkScrolling,
+    /// rows of the results list in view, end exclusive
+    ResultsScrolled(usize, usize),
+}
+
+/// for the clicked match, each capture with the other matches binding the same value
+struct CapturePeek {
+    origin: usize,
+    captures: Vec<(String, String, Vec<usize>)>,
 }
 
 // --------------------
@@ -118,320 +817,4560 @@ enum Msg {
 struct App {
     left_options: Rc<CodeEditorOptions>,
     right_options: Rc<CodeEditorOptions>,
-    left_width: i32,
+    layout: Layout,
     mousemove_listener: Option<EventListener>,
     mouseup_listener: Option<EventListener>,
     current_language: String,
     rhs_editor: Rc<RefCell<Option<CodeEditorLink>>>,
     lhs_editor: Rc<RefCell<Option<CodeEditorLink>>>,
 
+    /// second pattern set, only shown in comparison mode
+    compare_mode: bool,
+    left_b_options: Rc<CodeEditorOptions>,
+    lhs_b_editor: Rc<RefCell<Option<CodeEditorLink>>>,
+
+    /// every subject file. the active one's content lives in the rhs editor, so its
+    /// entry here is only up to date after `sync_active_file`
+    files: Vec<EditorFile>,
+    active_file: usize,
+    /// highlights of the last run, per file
+    file_highlights: Vec<Vec<HighlightElement>>,
+    /// matches of the last run (pattern set A in comparison mode)
+    results: Rc<RunResults>,
+    results_open: bool,
+    /// the results panel's callbacks, built once so it isn't re-rendered for new ones
+    on_reveal_match: Callback<usize>,
+    on_results_visible: Callback<(usize, usize)>,
+    /// matches sharing a capture value with the clicked match
+    peek: Option<CapturePeek>,
+    /// the rules and languages of the last run, which its matches are explained against
+    run_source: Option<RunSource>,
+    /// how the last clicked match came about
+    explanation: Option<Explanation>,
+    /// how far each pattern of a rule without matches got: the rule's name and the patterns
+    near_misses: Option<(String, Vec<NearMiss>)>,
+    /// golden snapshot vs. the current run
+    snapshot_diff: Option<SnapshotDiff>,
+    /// the results came with the link instead of from a run here
+    shared_results: bool,
+    /// subject files opened from disk, which can be read again
+    live_files: Vec<LiveFile>,
+    /// checks `live_files` for changes while set
+    live_poll: Option<gloo::timers::callback::Interval>,
+    snapshot_reader: Option<gloo::file::callbacks::FileReader>,
+    workspace_reader: Option<gloo::file::callbacks::FileReader>,
+    /// name of the large file whose streamed matches are in `results`, listed after `files`
+    scanned_file: Option<String>,
+    /// stops the scan in progress
+    scan_cancel: Option<Rc<Cell<bool>>>,
+    /// bytes scanned and total of the scan in progress
+    scan_progress: (u64, u64),
+    /// the run being matched a slice per frame
+    sliced: Option<SlicedState>,
+    /// only count matches per unit instead of highlighting them
+    count_only: bool,
+    scope: Option<SelectionScope>,
+    /// size of the share link as of the last run
+    link_stats: Option<(usize, LinkStats)>,
+    /// how the last share link could be mangled when it is passed on
+    link_audit: Vec<Finding>,
+    /// show capture values inline after each match
+    inlay_hints: bool,
+    /// mark each capture within its match
+    capture_ranges: bool,
+    /// scrolling the results keeps the subject centered on the matches in view
+    link_scrolling: bool,
+    summary: Option<RunSummary>,
+
+    /// lhs and files as of the last load or share
+    baseline: (String, Vec<EditorFile>),
+    /// shared with the beforeunload listener
+    dirty: Rc<Cell<bool>>,
+    _beforeunload_listener: EventListener,
+    _escape_listener: EventListener,
+    _undo_listener: EventListener,
+    _step_listener: EventListener,
+    _hashchange_listener: EventListener,
+    /// a config from a link opened while the user has rules of their own, waiting for
+    /// "Replace" or "Merge rules"
+    incoming: Option<PlaygroundConfig>,
+    undo: Vec<UndoEntry>,
+    /// what loads replaced this session, newest first
+    replaced: Vec<Replaced>,
+    replaced_open: bool,
+    redo: Vec<UndoEntry>,
+    /// the tabs above the app
+    sessions: Sessions,
+    /// per session, what it keeps while another one is shown
+    parked: Vec<Option<ParkedSession>>,
+    session_save_timeout: Option<gloo::timers::callback::Timeout>,
+
+    snippets: SnippetLibrary,
+    snippets_open: bool,
+    rules_menu_open: bool,
+    rule_packs_open: bool,
+    /// the index, fetched when the picker first opens
+    rule_packs: Option<Result<Vec<RulePack>, String>>,
+    /// outcome of the last merged pack, shown in the picker
+    rule_pack_status: Option<String>,
+    /// rule names and transforms for the open regex scratchpad
+    scratchpad_units: Option<Rc<Vec<(String, BTreeMap<String, String>)>>>,
+
+    settings: Settings,
+    settings_open: bool,
+    /// the deployment's defaults, empty until `playground-config.json` arrives
+    deploy: DeployConfig,
+
     error: Option<String>,
+    /// highlights shown in the active file, raised by "show more"
+    highlight_limit: usize,
+    /// rows of the results panel under the last click in the subject
+    selected_results: Vec<usize>,
+    /// how the results list is sorted, kept across runs
+    result_order: ResultOrder,
+    results_tab: ResultsTab,
+    aggregate_by: Option<String>,
+    /// cursor of the subject editor, for the status bar
+    cursor: Option<(usize, usize)>,
+    /// only rules with one of these tags are run. tags no rule uses anymore are ignored
+    tag_filter: BTreeSet<String>,
+    /// tags used in the lhs editor, refreshed as it changes
+    rule_tags: BTreeSet<String>,
+    /// language guessed from the last large paste into the subject, offered as a switch
+    language_suggestion: Option<String>,
+    /// language whose example is offered after switching an untouched session to it
+    preset_offer: Option<String>,
+    /// patterns of the last run that failed to compile
+    pattern_errors: Vec<PatternError>,
+    /// whether they failed the run, rather than being skipped
+    pattern_errors_fatal: bool,
+    /// schema errors and warnings of the lhs editor, refreshed as it changes
+    rule_problems: Vec<SchemaError>,
+    /// graphs of the last run or check, reused while the lhs stays the same
+    compiled: Option<CompiledPatterns>,
+    /// the initial patterns are compiled after the first paint rather than before it, so
+    /// the editors show up first. until then Run reads "engine loading…"
+    engine_ready: bool,
+    warm_up_frame: Option<gloo::render::AnimationFrame>,
+    pattern_check: Option<PatternCheck>,
+    /// `run_key` as of the last run, so an auto-run is skipped if nothing changed since
+    last_run_key: Option<u64>,
+    print_view_open: bool,
+    /// the user's runs, kept in local storage only
+    usage: Rc<UsageLog>,
+    usage_open: bool,
+    rule_diff_open: bool,
+    batch_decode_open: bool,
+    /// opened by the `#/self-test` fragment, see `self_test`
+    self_test_open: bool,
+    /// the subject pane is a diff editor of two versions of the subject
+    diff_mode: bool,
+    diff_editor: Option<JsValue>,
+    /// the text both sides of the diff editor start with
+    diff_start: String,
+    /// matches only in the modified and only in the original version, as of the last run
+    diff_counts: Option<(usize, usize)>,
+    tutorial: Option<TutorialStep>,
+    /// why the current tutorial step isn't done yet
+    tutorial_hint: Option<String>,
+    auto_run_timeout: Option<gloo::timers::callback::Timeout>,
 }
 
-impl Component for App {
-    type Message = Msg;
-    type Properties = ();
+impl App {
+    fn lhs_content(&self) -> String {
+        editor_content(&self.lhs_editor, &self.left_options)
+    }
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        let (cfg, err) = match PlaygroundConfig::from_url_str(&url_path()) {
-            Ok(v) => (v, None),
-            Err(e) => (Default::default(), Some(e)),
-        };
-        let (lhs, rhs, lang) = cfg.to_editor_parts();
+    fn rhs_content(&self) -> String {
+        editor_content(&self.rhs_editor, &self.right_options)
+    }
 
-        Self {
-            left_options: Rc::new(editor_options(lhs, "yaml".to_string())),
-            right_options: Rc::new(editor_options(rhs, lang.clone())),
-            left_width: 500,
-            mousemove_listener: None,
-            mouseup_listener: None,
-            current_language: lang,
-            rhs_editor: Rc::new(RefCell::new(None)),
-            lhs_editor: Rc::new(RefCell::new(None)),
-            error: err,
-        }
+    fn lhs_b_content(&self) -> String {
+        editor_content(&self.lhs_b_editor, &self.left_b_options)
     }
 
-    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
-            Msg::CopyShareLink | Msg::Run => {
-                let was_error = self.error.is_some();
-                self.error = None;
+    fn apply_highlights(&self, elements: &[HighlightElement]) {
+        if let Some(editor_link) = &*self.rhs_editor.borrow() {
+            editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                let js_editor: &JsValue = editor_api.as_ref();
 
-                let rhs_content = self
-                    .rhs_editor
-                    .borrow()
-                    .as_ref()
-                    .and_then(|editor| editor.with_editor(|m| m.get_model().map(|m| m.get_value())))
-                    .unwrap_or_else(|| self.right_options.value.clone())
-                    .unwrap_or_default();
+                let js_elements =
+                    serde_wasm_bindgen::to_value(elements).expect("failed to serialize highlights");
+                highlight_ranges_js(js_editor, &js_elements);
+            });
+        }
+    }
 
-                let lhs_content = self
-                    .lhs_editor
-                    .borrow()
-                    .as_ref()
-                    .and_then(|editor| editor.with_editor(|m| m.get_model().map(|m| m.get_value())))
-                    .unwrap_or_else(|| self.left_options.value.clone())
-                    .unwrap_or_default();
+    fn apply_inlay_hints(&self, hints: &[InlayHint]) {
+        if let Some(editor_link) = &*self.rhs_editor.borrow() {
+            editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                let js_editor: &JsValue = editor_api.as_ref();
 
-                let cfg = match PlaygroundConfig::from_editor_parts(
-                    &rhs_content,
-                    &self.current_language,
-                    &lhs_content,
-                ) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        // preserve current content
-                        self.right_options =
-                            Rc::new(editor_options(rhs_content, self.current_language.clone()));
-                        self.left_options =
-                            Rc::new(editor_options(lhs_content, "yaml".to_string()));
-                        self.error = Some(e);
-                        return true;
-                    }
-                };
+                let js_hints =
+                    serde_wasm_bindgen::to_value(hints).expect("failed to serialize inlay hints");
+                set_inlay_hints_js(js_editor, &js_hints);
+            });
+        }
+    }
 
-                match msg {
-                    Msg::CopyShareLink => {
-                        let path = cfg.to_url_str();
-                        let win = web_sys::window().unwrap();
-                        let location = win.location();
-                        let origin = location.origin().unwrap();
-                        let full_url = format!("{}/{}{}", origin, crate::io::PUBLIC_URL, path);
-                        let _ = win.navigator().clipboard().write_text(&full_url);
-                    }
-                    Msg::Run => {
-                        let mut accumulate: Vec<HighlightElement> = Default::default();
-                        if let Err(e) = cfg.run(|result| {
-                            let result = match final_postprocess(result) {
-                                Some(v) => v,
-                                None => return,
-                            };
-                            accumulate.push(HighlightElement {
-                                start_line: result.start.line,
-                                start_col: result.start.column,
-                                end_line: result.end.line,
-                                end_col: result.end.column,
-                                class_name: "match-highlight".to_owned(),
-                                text: Some(if !result.captures.is_empty() {
-                                    let captures_map: serde_json::Map<String, Value> = result
-                                        .captures
-                                        .iter()
-                                        .map(|(k, v)| {
-                                            (
-                                                String::from_utf8_lossy(k).to_string(),
-                                                Value::String(
-                                                    String::from_utf8_lossy(v).to_string(),
-                                                ),
-                                            )
-                                        })
-                                        .collect();
-                                    let captures_str =
-                                        serde_json::to_string(&captures_map).unwrap_or_default();
-                                    if result.name.is_empty() {
-                                        format!("{}", captures_str)
-                                    } else {
-                                        format!("{}: {}", result.name.clone(), captures_str)
-                                    }
-                                } else {
-                                    // Just the name
-                                    result.name.clone()
-                                }),
-                            });
-                        }) {
-                            // preserve current content
-                            self.right_options =
-                                Rc::new(editor_options(rhs_content, self.current_language.clone()));
-                            self.left_options =
-                                Rc::new(editor_options(lhs_content, "yaml".to_string()));
-                            self.error = Some(e);
-                            return true;
-                        }
+    /// mark each unit of the lhs with a left border in the color of its matches. left as is
+    /// while the yaml doesn't parse, so borders don't flicker while typing
+    fn apply_unit_borders(&self, lhs: &str) {
+        let units = match parse_lhs(lhs) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+        let blocks: Vec<UnitBlock> = rule_line_ranges(lhs)
+            .into_iter()
+            .zip(&units)
+            .map(|(lines, unit)| UnitBlock {
+                start_line: lines.start + 1,
+                end_line: lines.end,
+                class_name: unit_border_class(&unit.style),
+            })
+            .collect();
 
-                        if let Some(editor_link) = &*self.rhs_editor.borrow() {
-                            editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
-                                let js_editor: &JsValue = editor_api.as_ref();
+        if let Some(editor_link) = &*self.lhs_editor.borrow() {
+            editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                let js_editor: &JsValue = editor_api.as_ref();
 
-                                let js_elements = serde_wasm_bindgen::to_value(&accumulate)
-                                    .expect("failed to serialize highlights");
-                                highlight_ranges_js(js_editor, &js_elements);
-                            });
-                        }
-                    }
-                    _ => unreachable!(),
-                }
-                was_error
-            }
-            Msg::StartDrag => {
-                let link = ctx.link().clone();
-                let win = window().unwrap();
+                let js_blocks = serde_wasm_bindgen::to_value(&blocks)
+                    .expect("failed to serialize unit borders");
+                set_unit_borders_js(js_editor, &js_blocks);
+            });
+        }
+    }
 
-                self.mousemove_listener =
-                    Some(EventListener::new(&win, "mousemove", move |event| {
-                        let event = event.dyn_ref::<MouseEvent>().unwrap();
-                        link.send_message(Msg::Drag(event.client_x()));
-                    }));
+    fn apply_schema_markers(&self, errors: &[SchemaError]) {
+        if let Some(editor_link) = &*self.lhs_editor.borrow() {
+            editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                let js_editor: &JsValue = editor_api.as_ref();
 
-                let link = ctx.link().clone();
-                self.mouseup_listener = Some(EventListener::new(&win, "mouseup", move |_| {
-                    link.send_message(Msg::StopDrag);
-                }));
+                let js_markers =
+                    serde_wasm_bindgen::to_value(errors).expect("failed to serialize markers");
+                set_markers_js(js_editor, &js_markers);
+            });
+        }
+    }
 
-                false
-            }
-            Msg::Drag(x) => {
-                self.left_width = x.max(200);
+    /// show the capture values of the active file inline, if enabled
+    fn refresh_inlay_hints(&self) {
+        if self.inlay_hints {
+            self.apply_inlay_hints(&self.results.inlay_hints(self.active_file));
+        } else {
+            self.apply_inlay_hints(&[]);
+        }
+    }
 
-                // Preserve current editor content to prevent clearing during drag
-                let lhs_content = self
-                    .lhs_editor
-                    .borrow()
-                    .as_ref()
-                    .and_then(|editor| editor.with_editor(|m| m.get_model().map(|m| m.get_value())))
-                    .unwrap_or_else(|| self.left_options.value.clone())
-                    .unwrap_or_default();
+    fn current_snapshot(&self) -> Snapshot {
+        let files: Vec<String> = self.files.iter().map(|f| f.name.clone()).collect();
+        Snapshot::new(&self.results.matches, &files)
+    }
 
-                let rhs_content = self
-                    .rhs_editor
-                    .borrow()
-                    .as_ref()
-                    .and_then(|editor| editor.with_editor(|m| m.get_model().map(|m| m.get_value())))
-                    .unwrap_or_else(|| self.right_options.value.clone())
-                    .unwrap_or_default();
+    /// all files, with the active one taken from the rhs editor
+    fn files_snapshot(&self) -> Vec<EditorFile> {
+        let mut files = self.files.clone();
+        files[self.active_file] = EditorFile {
+            name: files[self.active_file].name.clone(),
+            language: self.current_language.clone(),
+            content: self.rhs_content(),
+        };
+        files
+    }
 
-                self.left_options = Rc::new(editor_options(lhs_content, "yaml".to_string()));
-                self.right_options =
-                    Rc::new(editor_options(rhs_content, self.current_language.clone()));
+    fn sync_active_file(&mut self) {
+        self.files = self.files_snapshot();
+    }
 
-                true
-            }
-            Msg::StopDrag => {
-                self.mousemove_listener = None;
-                self.mouseup_listener = None;
-                false
-            }
-            Msg::LanguageChanged(lang) => {
-                self.current_language = lang.clone();
+    /// build the config from the given lhs and every subject file
+    fn config_with_lhs(&self, lhs: &str) -> Result<PlaygroundConfig, PlaygroundError> {
+        config_from(&self.files_snapshot(), lhs)
+    }
 
-                if let Some(editor) = &*self.rhs_editor.borrow() {
-                    editor.with_editor(|e| {
-                        if let Some(model) = e.get_model() {
-                            model.set_language(&lang);
-                        }
-                    });
+    /// show another file in the rhs editor
+    fn switch_file(&mut self, index: usize) {
+        self.sync_active_file();
+        self.active_file = index;
+        let file = self.files[index].clone();
+        set_editor_value(&self.rhs_editor, &file.content, None);
+        self.set_language(file.language);
+        recovery::set_files(self.files.clone(), index);
+        self.right_options = Rc::new(editor_options(file.content, self.current_language.clone()));
+        self.highlight_limit = self.settings.max_highlights;
+        self.show_file_highlights();
+        self.refresh_inlay_hints();
+    }
+
+    /// the only place the subject language changes. the picker, the rhs model and the
+    /// active file all follow `current_language`, which is kept in the form a saved config
+    /// comes back as, so it can't drift between a pick and a reload
+    fn set_language(&mut self, lang: String) {
+        let lang = match resolve_language(&lang) {
+            Ok((canonical, _)) => canonical.to_string(),
+            Err(_) => lang,
+        };
+        if let Some(editor) = &*self.rhs_editor.borrow() {
+            editor.with_editor(|e| {
+                if let Some(model) = e.get_model() {
+                    model.set_language(&lang);
                 }
+            });
+        }
+        self.files[self.active_file].language = lang.clone();
+        self.current_language = lang;
+    }
 
-                false
-            }
-            Msg::ClearHighlights => {
-                if let Some(editor_link) = &*self.rhs_editor.borrow() {
-                    editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
-                        let js_editor: &JsValue = editor_api.as_ref();
+    /// the patterns of `cfg`, compiled by an earlier run or check if its lhs hasn't changed
+    /// since. the caller puts them back into `compiled` once done
+    fn take_compiled(
+        &mut self,
+        cfg: &PlaygroundConfig,
+        skip_invalid: bool,
+    ) -> Result<CompiledPatterns, PlaygroundError> {
+        match self.compiled.take() {
+            Some(compiled) if compiled.fits(cfg, skip_invalid) => Ok(compiled),
+            _ => cfg.compile(skip_invalid),
+        }
+    }
 
-                        let empty: Vec<HighlightElement> = Vec::new();
-                        let js_elements = serde_wasm_bindgen::to_value(&empty)
-                            .expect("failed to serialize highlights");
+    /// the filtered tags that the rules in `lhs` still use
+    fn active_tags(&self, lhs: &str) -> BTreeSet<String> {
+        self.tag_filter
+            .intersection(&rule_tags(lhs))
+            .cloned()
+            .collect()
+    }
 
-                        highlight_ranges_js(js_editor, &js_elements);
-                    });
-                }
+    /// highlight the active file's matches, up to `highlight_limit`
+    fn show_file_highlights(&self) {
+        let highlights = self
+            .file_highlights
+            .get(self.active_file)
+            .map(|h| &h[..h.len().min(self.highlight_limit)])
+            .unwrap_or(&[]);
+        let extra = [self.capture_highlights(), self.near_miss_highlights()].concat();
+        if extra.is_empty() {
+            self.apply_highlights(highlights);
+        } else {
+            self.apply_highlights(&[highlights, extra.as_slice()].concat());
+        }
+    }
 
-                false
-            }
+    /// each capture's part of the active file's matches, labelled with the capture's name
+    fn capture_highlights(&self) -> Vec<HighlightElement> {
+        if !self.capture_ranges {
+            return Vec::new();
         }
+        self.results
+            .capture_spans(self.active_file, self.highlight_limit)
+            .into_iter()
+            .map(|span| HighlightElement {
+                start_line: span.start_line,
+                start_col: span.start_col,
+                end_line: span.end_line,
+                end_col: span.end_col,
+                class_name: "capture-highlight".to_string(),
+                text: None,
+                label: Some(span.name),
+            })
+            .collect()
     }
 
-    fn view(&self, ctx: &Context<Self>) -> Html {
-        let total_width = window().unwrap().inner_width().unwrap().as_f64().unwrap() as i32;
-        let right_width = (total_width - self.left_width - 6).max(200);
+    /// faded highlights of how far each pattern of `near_misses` got in the active file
+    fn near_miss_highlights(&self) -> Vec<HighlightElement> {
+        let Some((_, misses)) = &self.near_misses else {
+            return Vec::new();
+        };
+        misses
+            .iter()
+            .filter_map(|miss| {
+                let m = miss.reached.as_ref()?;
+                (m.file == self.active_file).then(|| HighlightElement {
+                    text: Some(Self::describe_near_miss(miss)),
+                    ..HighlightElement::from_match(m, "match-highlight-near", None)
+                })
+            })
+            .collect()
+    }
 
-        let on_language_change = ctx.link().callback(|e: web_sys::Event| {
-            let select: web_sys::HtmlSelectElement = e.target().unwrap().dyn_into().unwrap();
-            Msg::LanguageChanged(select.value())
-        });
+    fn describe_near_miss(miss: &NearMiss) -> String {
+        match &miss.failed_at {
+            Some(token) => tr!(
+                "near-miss-failed-at",
+                matched = miss.matched_tokens,
+                total = miss.total_tokens,
+                token = token.as_str(),
+            ),
+            None => tr!("near-miss-matches"),
+        }
+    }
 
-        let rhs_editor_clone_clone = self.rhs_editor.clone();
-        let lhs_editor_clone_clone = self.lhs_editor.clone();
+    /// scroll the subject to a range, switching files if needed. false for a scanned file,
+    /// which isn't in an editor
+    fn reveal_in_subject(&mut self, m: &SearchMatch) -> bool {
+        if m.file >= self.files.len() {
+            return false;
+        }
+        if m.file != self.active_file {
+            self.switch_file(m.file);
+        }
+        if let Some(editor_link) = &*self.rhs_editor.borrow() {
+            editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                let js_editor: &JsValue = editor_api.as_ref();
+                reveal_range_js(js_editor, m.start_line, m.start_col, m.end_line, m.end_col);
+            });
+        }
+        true
+    }
 
-        html! {
-            <div style="height:100vh; display:flex; flex-direction:column;">
-                // Header
-                <div style="
-                    height:50px;
-                    background:#222;
-                    color:white;
-                    display:flex;
-                    align-items:center;
-                    padding:0 10px;
-                    gap:10px;
-                ">
-                    <button onclick={ctx.link().callback(|_| Msg::Run)}>{"Run"}</button>
+    /// recompute the dirty flag, returning true if it changed. the panic hook gets the files
+    /// along, as this runs after every edit
+    fn refresh_dirty(&mut self) -> bool {
+        let files = self.files_snapshot();
+        let (lhs, baseline_files) = &self.baseline;
+        let dirty = *lhs != self.lhs_content() || *baseline_files != files;
+        recovery::set_files(files, self.active_file);
+        self.dirty.replace(dirty) != dirty
+    }
 
-                    <button onclick={ctx.link().callback(|_| Msg::ClearHighlights)}>
-                        {"Clear"}
-                    </button>
+    /// replace the content of the subject file `name`, e.g. with what was read from disk
+    fn set_file_content(&mut self, name: &str, text: String) {
+        let Some(index) = self.files.iter().position(|f| f.name == name) else {
+            return;
+        };
+        self.checkpoint();
+        if index == self.active_file {
+            set_editor_value(&self.rhs_editor, &text, None);
+            self.right_options =
+                Rc::new(editor_options(text.clone(), self.current_language.clone()));
+        }
+        self.files[index].content = text;
+        self.refresh_dirty();
+    }
+
+    /// show matches that came with a link as if they were a run's, until the user runs
+    fn show_shared_results(&mut self, mut matches: Vec<SearchMatch>) {
+        let units = parse_lhs(&self.lhs_content()).unwrap_or_default();
+        let files = self.files_snapshot();
+        matches.retain(|m| m.file < files.len());
+        let docs = RuleDocs::new(&units);
+        self.file_highlights = highlights_by_file(
+            &matches,
+            files.len(),
+            "match-highlight",
+            &UnitStyles::new(&units),
+            &docs,
+        );
+        self.summary = Some(RunSummary::from_matches(&matches, BTreeSet::new()));
+        let mut results = RunResults::new(matches, &units);
+        results.set_subjects(files.into_iter().map(|f| f.content).collect());
+        results.sort(self.result_order);
+        self.results = Rc::new(results);
+        // nothing ran here to explain them with
+        self.run_source = None;
+        self.results_open = true;
+        self.shared_results = true;
+        self.highlight_limit = self.settings.max_highlights;
+        self.show_file_highlights();
+        self.refresh_inlay_hints();
+    }
+
+    /// keep the current state under "Recently replaced" before a load overwrites it. the
+    /// untouched default isn't worth keeping
+    fn stash_replaced(&mut self, by: String) {
+        let entry = self.undo_entry();
+        let default = PlaygroundConfig::default();
+        let default_files = default.editor_files();
+        if entry.lhs == default.to_editor_parts().0 && entry.files == default_files {
+            return;
+        }
+        if self.replaced.first().is_some_and(|r| r.entry == entry) {
+            return;
+        }
+        self.replaced.insert(
+            0,
+            Replaced {
+                by,
+                at: js_sys::Date::now(),
+                entry,
+            },
+        );
+        self.replaced.truncate(MAX_REPLACED);
+    }
+
+    /// replace the editors' content and the files with the given config. `by` names it
+    /// under "Recently replaced"
+    fn load_config(&mut self, cfg: PlaygroundConfig, by: String) {
+        self.stash_replaced(by);
+        self.checkpoint();
+        let files = cfg.editor_files();
+        let lhs = cfg.to_editor_parts().0;
+        self.baseline = (lhs.clone(), files.clone());
+        self.show_workspace(lhs, files, 0);
+    }
+
+    /// load a config from a link. if the user has rules of their own it waits for them to
+    /// pick "Replace" or "Merge rules" instead
+    fn open_config(&mut self, cfg: PlaygroundConfig) {
+        let lhs = self.lhs_content();
+        let untouched = lhs == PlaygroundConfig::default().to_editor_parts().0;
+        // a subject of their own counts too
+        if !self.dirty.get() && (rule_names(&lhs).is_empty() || untouched) {
+            self.incoming = None;
+            self.load_config(cfg, tr!("replaced-by-link"));
+        } else {
+            self.incoming = Some(cfg);
+        }
+    }
+
+    /// the link `cfg` is shared as, its subjects scrubbed if the user asked for it. if they
+    /// can't be, nothing is shared rather than the code as it is
+    fn shared_link(
+        &self,
+        cfg: &PlaygroundConfig,
+        lhs: &str,
+        encoding: LinkEncoding,
+    ) -> Result<PendingLink, String> {
+        if self.settings.scrub_shared_links {
+            // `cfg` still has to run, so the scrubbed one is built anew
+            let mut shared = self.config_with_lhs(lhs)?;
+            crate::scrub::scrub_config(&mut shared).map_err(|e| tr!("scrub-failed", error = e))?;
+            return Ok(shared.pending_link(encoding));
+        }
+        Ok(cfg.pending_link(encoding))
+    }
+
+    /// add the run that just finished to the usage log
+    fn record_usage(&mut self, started: f64, rules: usize) {
+        let Some(summary) = &self.summary else {
+            return;
+        };
+        let now = js_sys::Date::now();
+        let entry = UsageEntry {
+            at: now,
+            language: self.current_language.clone(),
+            rules,
+            matches: summary.total(),
+            duration_ms: now - started,
+            per_rule: summary.per_unit.clone(),
+        };
+        Rc::make_mut(&mut self.usage).record(entry);
+    }
+
+    /// when a run started now has to stop, see `Settings::run_timeout_secs`
+    fn run_deadline(&self) -> Deadline {
+        Deadline::after_ms(self.settings.run_timeout_secs.saturating_mul(1000))
+    }
+
+    /// the timeout to report in the summary, if the run hit it
+    fn timed_out(&self, deadline: &Deadline) -> Option<u32> {
+        deadline.expired().then_some(self.settings.run_timeout_secs)
+    }
+
+    /// show the results of a run over the editor subjects
+    fn show_run(&mut self, run: FinishedRun) {
+        self.stop_scan();
+        self.sliced = None;
+        self.scanned_file = None;
+        self.file_highlights = run.highlights;
+        self.highlight_limit = self.settings.max_highlights;
+        self.summary = Some(RunSummary {
+            timed_out: run.timed_out,
+            ..RunSummary::from_matches(&run.matches, run.truncated)
+        });
+        let mut results = RunResults::new(run.matches, &run.units);
+        results.set_subjects(
+            self.files_snapshot()
+                .into_iter()
+                .map(|f| f.content)
+                .collect(),
+        );
+        results.sort(self.result_order);
+        self.results = Rc::new(results);
+        self.run_source = Some(run.source);
+        self.selected_results.clear();
+        self.refresh_inlay_hints();
+        self.peek = None;
+        self.explanation = None;
+        self.near_misses = None;
+        // after the results, which the capture ranges come from
+        self.show_file_highlights();
+        self.pattern_errors = run.skipped;
+        self.pattern_errors_fatal = false;
+        self.record_usage(run.started, run.rule_count);
+    }
+
+    /// match the next slice of a sliced run, scheduling the one after or showing the
+    /// results. returns whether there is progress to render
+    fn run_slice(&mut self, ctx: &Context<Self>) -> bool {
+        let Some(state) = &mut self.sliced else {
+            return false;
+        };
+        match state.run.step(&state.deadline) {
+            Ok(matches) => state.matches.extend(matches),
+            Err(e) => {
+                self.sliced = None;
+                self.show_run_error(e);
+                return true;
+            }
+        }
+        if !state.run.finished() && !state.deadline.check() {
+            let link = ctx.link().clone();
+            if gloo::utils::document().hidden() {
+                state._next_timeout = Some(gloo::timers::callback::Timeout::new(0, move || {
+                    link.send_message(Msg::RunSlice)
+                }));
+            } else {
+                state._next_frame = Some(gloo::render::request_animation_frame(move |_| {
+                    link.send_message(Msg::RunSlice)
+                }));
+            }
+            return true;
+        }
+        if let Some(state) = self.sliced.take() {
+            self.finish_sliced_run(state);
+        }
+        true
+    }
+
+    /// show what a sliced run found, once it is done or stopped
+    fn finish_sliced_run(&mut self, state: SlicedState) {
+        let (compiled, truncated) = state.run.into_parts();
+        let skipped = compiled.skipped.clone();
+        self.compiled = Some(compiled);
+        let matches =
+            crate::overlap::resolve(state.matches, self.settings.overlap_mode, &state.rule_order);
+        let hook = ResultHook::new(&self.settings.result_hook);
+        match present_matches(matches, self.files.len(), &state.styles, &state.docs, hook) {
+            Ok((matches, highlights)) => {
+                let timed_out = self.timed_out(&state.deadline);
+                self.show_run(FinishedRun {
+                    started: state.started,
+                    units: state.units,
+                    matches,
+                    highlights,
+                    skipped,
+                    truncated,
+                    timed_out,
+                    rule_count: state.rule_count,
+                    source: state.source,
+                });
+            }
+            Err(e) => self.show_run_error(e),
+        }
+    }
+
+    /// stop streaming a large file into the results
+    fn stop_scan(&mut self) {
+        if let Some(cancel) = self.scan_cancel.take() {
+            cancel.set(true);
+        }
+    }
+
+    /// file names as the results refer to them: the editor files, then a scanned file
+    fn result_files(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .map(|f| f.name.clone())
+            .chain(self.scanned_file.clone())
+            .collect()
+    }
+
+    /// hash of what a run depends on: the rules, the tag filter and every subject with its
+    /// language
+    fn run_key(&self) -> u64 {
+        let trim = self.settings.ignore_trailing_whitespace;
+        let mut hasher = DefaultHasher::new();
+        let lhs = self.lhs_content();
+        hash_text(&mut hasher, &lhs, trim);
+        self.active_tags(&lhs).hash(&mut hasher);
+        if self.compare_mode {
+            hash_text(&mut hasher, &self.lhs_b_content(), trim);
+        }
+        for file in self.files_snapshot() {
+            file.name.hash(&mut hasher);
+            file.language.hash(&mut hasher);
+            hash_text(&mut hasher, &file.content, trim);
+        }
+        hasher.finish()
+    }
+
+    /// drop the last run's results and their decorations
+    fn clear_results(&mut self) {
+        self.last_run_key = None;
+        self.stop_scan();
+        self.sliced = None;
+        self.scanned_file = None;
+        // replaced rather than cleared, so their capacity is freed too
+        self.file_highlights = Vec::new();
+        self.results = Rc::default();
+        self.run_source = None;
+        self.selected_results = Vec::new();
+        self.peek = None;
+        self.explanation = None;
+        self.near_misses = None;
+        self.summary = None;
+        self.shared_results = false;
+        self.apply_highlights(&[]);
+        self.apply_inlay_hints(&[]);
+    }
+
+    /// put `lhs` and `files` into the editors, showing `files[active_file]`. the results
+    /// belong to the replaced content, so they are cleared
+    fn show_workspace(&mut self, lhs: String, files: Vec<EditorFile>, active_file: usize) {
+        self.files = files;
+        let files = &self.files;
+        self.live_files
+            .retain(|live| files.iter().any(|f| f.name == live.name));
+        if self.live_files.is_empty() {
+            self.live_poll = None;
+        }
+        self.active_file = active_file.min(self.files.len() - 1);
+        self.clear_results();
+
+        let file = self.files[self.active_file].clone();
+        set_editor_value(&self.lhs_editor, &lhs, None);
+        set_editor_value(&self.rhs_editor, &file.content, None);
+        self.set_language(file.language);
+
+        self.left_options = Rc::new(editor_options(lhs, "yaml".to_string()));
+        self.right_options = Rc::new(editor_options(file.content, self.current_language.clone()));
+        self.refresh_dirty();
+    }
+
+    fn undo_entry(&self) -> UndoEntry {
+        UndoEntry {
+            lhs: self.lhs_content(),
+            files: self.files_snapshot(),
+            active_file: self.active_file,
+        }
+    }
+
+    /// remember the current state before an action replaces it. setting an editor's value
+    /// also clears monaco's own undo history, so this is the only way back
+    fn checkpoint(&mut self) {
+        let entry = self.undo_entry();
+        if self.undo.last() != Some(&entry) {
+            self.undo.push(entry);
+            if self.undo.len() > MAX_UNDO {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+    }
+
+    /// step back (`undo`) or forward through the checkpoints
+    fn step_history(&mut self, undo: bool) -> bool {
+        let entry = match if undo {
+            self.undo.pop()
+        } else {
+            self.redo.pop()
+        } {
+            Some(v) => v,
+            None => return false,
+        };
+        let current = self.undo_entry();
+        if undo {
+            self.redo.push(current);
+        } else {
+            self.undo.push(current);
+        }
+        self.show_workspace(entry.lhs, entry.files, entry.active_file);
+        true
+    }
+
+    /// keep the shown session's editors in local storage
+    fn save_session(&mut self) {
+        self.session_save_timeout = None;
+        let entry = self.undo_entry();
+        if let Err(e) = self
+            .sessions
+            .save_active(entry.lhs, entry.files, entry.active_file)
+        {
+            self.error = Some(tr!("session-save-failed", error = e));
+        }
+    }
+
+    /// save the shown session once typing pauses
+    fn schedule_session_save(&mut self, ctx: &Context<Self>) {
+        let link = ctx.link().clone();
+        self.session_save_timeout = Some(gloo::timers::callback::Timeout::new(
+            SESSION_SAVE_DELAY_MS,
+            move || link.send_message(Msg::SaveSession),
+        ));
+    }
+
+    /// take what the shown session keeps in memory, before another one is shown
+    fn park_session(&mut self) -> ParkedSession {
+        self.stop_scan();
+        ParkedSession {
+            results: std::mem::take(&mut self.results),
+            file_highlights: std::mem::take(&mut self.file_highlights),
+            summary: self.summary.take(),
+            baseline: std::mem::take(&mut self.baseline),
+            undo: std::mem::take(&mut self.undo),
+            redo: std::mem::take(&mut self.redo),
+        }
+    }
+
+    /// show a session, with its results and history if it was shown before
+    fn show_session(&mut self, session: Session, parked: Option<ParkedSession>) {
+        let parked = parked.unwrap_or_else(|| ParkedSession {
+            baseline: (session.lhs.clone(), session.files.clone()),
+            ..Default::default()
+        });
+        self.incoming = None;
+        self.pattern_errors.clear();
+        self.baseline = parked.baseline;
+        self.show_workspace(session.lhs, session.files, session.active_file);
+        self.results = parked.results;
+        self.run_source = None;
+        self.file_highlights = parked.file_highlights;
+        self.summary = parked.summary;
+        self.undo = parked.undo;
+        self.redo = parked.redo;
+        self.highlight_limit = self.settings.max_highlights;
+        self.show_file_highlights();
+        self.refresh_inlay_hints();
+    }
+
+    /// park the shown session and show the one at `index`
+    fn switch_session(&mut self, index: usize) {
+        self.save_session();
+        let leaving = self.sessions.active();
+        let parked = self.park_session();
+        let session = match self.sessions.select(index) {
+            Ok(session) => session,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        self.parked[leaving] = Some(parked);
+        let parked = self.parked[index].take();
+        self.show_session(session, parked);
+    }
+
+    fn rhs_selection(&self) -> Option<EditorSelection> {
+        let editor_link = self.rhs_editor.borrow();
+        let editor_link = editor_link.as_ref()?;
+        editor_link
+            .with_editor(|editor_api: &monaco::api::CodeEditor| {
+                let js_editor: &JsValue = editor_api.as_ref();
+                serde_wasm_bindgen::from_value::<Option<EditorSelection>>(selection_js(js_editor))
+                    .ok()
+                    .flatten()
+            })
+            .flatten()
+    }
+
+    /// set the lhs editor's content, scrolling to `reveal_line` if given
+    fn replace_lhs(&mut self, lhs: String, reveal_line: Option<usize>) {
+        self.checkpoint();
+        self.error = None;
+        set_editor_value(&self.lhs_editor, &lhs, None);
+        if let (Some(editor_link), Some(reveal_line)) = (&*self.lhs_editor.borrow(), reveal_line) {
+            editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                let js_editor: &JsValue = editor_api.as_ref();
+                reveal_range_js(js_editor, reveal_line, 1, reveal_line, 1);
+            });
+        }
+        self.left_options = Rc::new(editor_options(lhs, "yaml".to_string()));
+    }
+
+    /// distinct values of one capture with their counts. clicking a value lists its matches
+    fn view_aggregate(&self, ctx: &Context<Self>) -> Html {
+        let names = self.results.capture_names();
+        let name = match self
+            .aggregate_by
+            .as_deref()
+            .filter(|n| names.contains(n))
+            .or_else(|| names.first().copied())
+        {
+            Some(v) => v,
+            None => {
+                return html! {
+                    <div style="padding:8px; color:#aaa; font-size:12px;">{ tr!("aggregate-empty") }</div>
+                };
+            }
+        };
+        let values = self.results.aggregate(name);
+        let most = values.first().map_or(1, |(_, n)| *n);
+        let hidden = values.len().saturating_sub(MAX_AGGREGATE_ROWS);
+
+        html! {
+            <div style="height:100%; overflow-y:auto; font-size:12px; padding:4px 8px;">
+                <label>
+                    { tr!("aggregate-by") }
+                    <select style="margin-left:4px;"
+                        onchange={ctx.link().callback(|e: web_sys::Event| {
+                            let select: web_sys::HtmlSelectElement = e.target().unwrap().dyn_into().unwrap();
+                            Msg::AggregateBy(select.value())
+                        })}>
+                        { for names.iter().map(|n| html! {
+                            <option value={n.to_string()} selected={*n == name}>{ n.to_string() }</option>
+                        })}
+                    </select>
+                    <span style="margin-left:8px; color:#888;">
+                        { tr!("aggregate-distinct", count = values.len()) }
+                    </span>
+                </label>
+                <table style="border-collapse:collapse; margin-top:4px; font-family:monospace;">
+                    { for values.iter().take(MAX_AGGREGATE_ROWS).map(|(value, count)| {
+                        let filter = (name.to_string(), value.to_string());
+                        html! {
+                            <tr style="cursor:pointer;" title={tr!("aggregate-filter.title")}
+                                onclick={ctx.link().callback(move |_| Msg::FilterResults(Some(filter.clone())))}>
+                                <td style="padding:0 8px 0 0; text-align:right; color:#e8c547;">{ *count }</td>
+                                <td style="padding:0 8px 0 0; width:80px;">
+                                    <div style={format!("height:8px; width:{}%; background:#007acc;", count * 100 / most)} />
+                                </td>
+                                <td style="white-space:pre;">{ value.to_string() }</td>
+                            </tr>
+                        }
+                    })}
+                </table>
+                { if hidden > 0 {
+                    html! { <div style="color:#888;">{ tr!("aggregate-more", count = hidden) }</div> }
+                } else {
+                    html! {}
+                }}
+            </div>
+        }
+    }
+
+    /// every rule with its match count and a checkbox muting it
+    fn view_rule_legend(&self, ctx: &Context<Self>) -> Html {
+        let rules = rule_list(&self.lhs_content());
+
+        html! {
+            <div style="width:200px; overflow-y:auto; border-right:1px solid #444; padding:4px 8px; font-size:12px;">
+                { for rules.into_iter().enumerate().map(|(index, (name, enabled))| {
+                    let count = self
+                        .summary
+                        .as_ref()
+                        .and_then(|s| s.per_unit.get(&name))
+                        .copied();
+                    let capped = self.summary.as_ref().is_some_and(|s| s.truncated.contains(&name));
+                    html! {
+                        <label style={format!("display:flex; gap:4px; align-items:center; {}", if enabled { "" } else { "color:#777;" })}
+                            title={tr!("rule-enabled-title")}>
+                            <input type="checkbox" checked={enabled}
+                                onchange={ctx.link().callback(move |_| Msg::SetRuleEnabled(index, !enabled))} />
+                            <span style="flex:1; overflow:hidden; text-overflow:ellipsis;">{ name }</span>
+                            { count.map(|n| if capped {
+                                html! {
+                                    <span style="color:#e8c547;" title={tr!("rule-capped.title")}>{ format!("{}+", n) }</span>
+                                }
+                            } else {
+                                html! { <span style="color:#888;">{ n }</span> }
+                            }) }
+                            // the diff mode only lists new matches, so none doesn't mean no match
+                            { if enabled && count.is_none() && !self.diff_mode && self.run_source.is_some()
+                                && self.summary.as_ref().is_some_and(|s| !s.count_only) {
+                                html! {
+                                    <button style="font-size:11px; padding:0 4px;" title={tr!("explain-no-match.title")}
+                                        onclick={ctx.link().callback(move |e: MouseEvent| {
+                                            e.prevent_default();
+                                            Msg::ExplainNoMatch(index)
+                                        })}>
+                                        { tr!("explain-no-match") }
+                                    </button>
+                                }
+                            } else {
+                                html! {}
+                            }}
+                        </label>
+                    }
+                })}
+            </div>
+        }
+    }
+
+    /// the packs of the index by category. picking one merges its rules into the lhs
+    fn view_rule_packs_menu(&self, ctx: &Context<Self>) -> Html {
+        let body = match &self.rule_packs {
+            None => html! { <div style="color:#aaa;">{ tr!("rule-packs-loading") }</div> },
+            Some(Err(e)) => html! {
+                <div style="color:#f48771;">{ tr!("rule-pack-error", error = e.as_str()) }</div>
+            },
+            Some(Ok(packs)) if packs.is_empty() => {
+                html! { <div style="color:#aaa;">{ tr!("no-rule-packs") }</div> }
+            }
+            Some(Ok(packs)) => html! {
+                { for by_category(packs).into_iter().map(|(category, packs)| html! {
+                    <div style="margin-bottom:4px;">
+                        <div style="color:#888; font-size:11px; text-transform:uppercase;">{ category }</div>
+                        { for packs.into_iter().map(|pack| {
+                            let picked = pack.clone();
+                            html! {
+                                <div style="padding:2px 4px; cursor:pointer;" title={pack.description.clone()}
+                                    onclick={ctx.link().callback(move |_| Msg::PickRulePack(picked.clone()))}>
+                                    { pack.name.clone() }
+                                </div>
+                            }
+                        })}
+                    </div>
+                })}
+            },
+        };
+        html! {
+            <div style="
+                position:absolute;
+                top:100%;
+                left:0;
+                z-index:10;
+                min-width:240px;
+                max-height:360px;
+                overflow-y:auto;
+                background:#2d2d2d;
+                border:1px solid #444;
+                padding:4px;
+                color:#ccc;
+            ">
+                { body }
+                { self.rule_pack_status.as_ref().map(|status| html! {
+                    <div style="border-top:1px solid #444; margin-top:4px; padding-top:4px; font-size:12px;">
+                        { status }
+                    </div>
+                }) }
+            </div>
+        }
+    }
+
+    fn view_rules_toolbar(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div style="display:flex; gap:4px; padding:2px 6px; background:#252526; position:relative;">
+                <button title={tr!("add-rule.title")}
+                    onclick={ctx.link().callback(|_| Msg::AddRule)}>{ tr!("add-rule") }</button>
+                <button title={tr!("rule-from-selection.title")}
+                    onclick={ctx.link().callback(|_| Msg::PatternFromSelection)}>{ tr!("rule-from-selection") }</button>
+                <div style="position:relative;">
+                    <button onclick={ctx.link().callback(|_| Msg::ToggleRulesMenu)}>{ tr!("duplicate-rule") }</button>
+                    { if self.rules_menu_open {
+                        let names = rule_names(&self.lhs_content());
+                        html! {
+                            <div style="
+                                position:absolute;
+                                top:100%;
+                                left:0;
+                                z-index:10;
+                                min-width:200px;
+                                background:#2d2d2d;
+                                border:1px solid #444;
+                                padding:4px;
+                                color:#ccc;
+                            ">
+                                { if names.is_empty() {
+                                    html! { <div style="color:#aaa;">{ tr!("no-rules") }</div> }
+                                } else {
+                                    html! {}
+                                }}
+                                { for names.into_iter().enumerate().map(|(index, name)| html! {
+                                    <div style="padding:2px 4px; cursor:pointer;"
+                                        onclick={ctx.link().callback(move |_| Msg::DuplicateRule(index))}>
+                                        { name }
+                                    </div>
+                                })}
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                </div>
+                <div style="position:relative;">
+                    <button title={tr!("rule-packs.title")}
+                        onclick={ctx.link().callback(|_| Msg::ToggleRulePacks)}>{ tr!("rule-packs") }</button>
+                    { if self.rule_packs_open { self.view_rule_packs_menu(ctx) } else { html! {} } }
+                </div>
+                <button title={tr!("rule-diff.title")}
+                    onclick={ctx.link().callback(|_| Msg::ToggleRuleDiff)}>{ tr!("rule-diff") }</button>
+                <button title={tr!("batch-decode.title")}
+                    onclick={ctx.link().callback(|_| Msg::ToggleBatchDecode)}>{ tr!("batch-decode") }</button>
+                <button title={tr!("check-patterns.title")}
+                    onclick={ctx.link().callback(|_| Msg::CheckPatterns)}>{ tr!("check-patterns") }</button>
+                { self.pattern_check.as_ref().map(|check| if check.failed == 0 {
+                    html! {
+                        <span style="align-self:center; color:#8fd18f; font-size:12px;">
+                            { tr!("patterns-compile", count = check.total) }
+                        </span>
+                    }
+                } else {
+                    html! {
+                        <span style="align-self:center; color:#f48771; font-size:12px;">
+                            { tr!("patterns-fail", failed = check.failed, count = check.total) }
+                        </span>
+                    }
+                }) }
+                <div style="margin-left:auto;">
+                    <button title={tr!("open-scratchpad.title")}
+                        onclick={ctx.link().callback(|_| Msg::ToggleScratchpad)}>{ tr!("open-scratchpad") }</button>
+                </div>
+                { self.scratchpad_units.clone().map(|units| html! {
+                    <RegexScratchpad {units}
+                        on_apply={ctx.link().callback(|(index, capture, regex)| Msg::ApplyTransform(index, capture, regex))}
+                        on_close={ctx.link().callback(|_| Msg::ToggleScratchpad)} />
+                }) }
+            </div>
+        }
+    }
+
+    /// a chip per tag used by the rules. with any selected, only rules carrying one of them
+    /// run
+    fn view_tag_filter(&self, ctx: &Context<Self>) -> Html {
+        if self.rule_tags.is_empty() {
+            return html! {};
+        }
+        let active: BTreeSet<&String> = self.tag_filter.intersection(&self.rule_tags).collect();
+
+        html! {
+            <div role="group" aria-label={tr!("tag-filter-label")}
+                style="display:flex; flex-wrap:wrap; gap:4px; padding:2px 6px; background:#252526; color:#ccc; font-size:12px;">
+                <span style="color:#888;" title={tr!("tag-filter.title")}>{ tr!("tag-filter") }</span>
+                { for self.rule_tags.iter().cloned().map(|tag| {
+                    let selected = active.contains(&tag);
+                    html! {
+                        <button aria-pressed={selected.to_string()}
+                            style={format!(
+                                "padding:0 8px; border-radius:10px; border:1px solid #007acc; cursor:pointer; {}",
+                                if selected { "background:#007acc; color:white;" } else { "background:none; color:#ccc;" }
+                            )}
+                            onclick={ctx.link().callback(move |_| Msg::ToggleTag(tag.clone()))}>
+                            { tag.clone() }
+                        </button>
+                    }
+                })}
+                { if active.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <span style="cursor:pointer; color:#888;" title={tr!("clear-tag-filter")}
+                            onclick={ctx.link().callback(|_| Msg::ClearTagFilter)}>{"×"}</span>
+                    }
+                }}
+            </div>
+        }
+    }
+
+    /// what the schema check found in the rules, each entry linking to its place in the YAML
+    fn view_rule_problems(&self, ctx: &Context<Self>) -> Html {
+        if self.rule_problems.is_empty() {
+            return html! {};
+        }
+        let errors = self.rule_problems.iter().filter(|p| !p.warning).count();
+
+        html! {
+            <details style="
+                max-height:20vh;
+                overflow-y:auto;
+                background:#252526;
+                color:#ccc;
+                padding:2px 6px;
+                font-size:12px;
+            ">
+                <summary style={format!("cursor:pointer; color:{};", if errors > 0 { "#f48771" } else { "#cca700" })}
+                    title={tr!("rule-problems.title")}>
+                    { tr!("rule-problems", count = self.rule_problems.len()) }
+                </summary>
+                { for self.rule_problems.iter().map(|p| {
+                    let (line, col) = (p.line, p.col);
+                    html! {
+                        <div style="display:flex; gap:8px; padding:1px 0; cursor:pointer;"
+                            onclick={ctx.link().callback(move |_| Msg::RevealLhs(line, col))}>
+                            <span style={format!("white-space:nowrap; color:{};", if p.warning { "#cca700" } else { "#f48771" })}>
+                                { format!("{}:{}", line, col) }
+                            </span>
+                            <span style="font-family:monospace;">{ p.to_string() }</span>
+                        </div>
+                    }
+                })}
+            </details>
+        }
+    }
+
+    /// file switcher for the rhs editor, with per-file match counts after a run
+    fn view_file_tabs(&self, ctx: &Context<Self>) -> Html {
+        let total: usize = self.file_highlights.iter().map(|h| h.len()).sum();
+
+        html! {
+            <div style="
+                display:flex;
+                align-items:center;
+                gap:2px;
+                background:#252526;
+                color:#ccc;
+                font-size:13px;
+                overflow-x:auto;
+            ">
+                { for self.files.iter().enumerate().map(|(index, file)| {
+                    let active = index == self.active_file;
+                    let count = self.file_highlights.get(index).map(|h| h.len());
+                    html! {
+                        <div
+                            style={format!(
+                                "padding:4px 8px; cursor:pointer; white-space:nowrap; {}",
+                                if active { "background:#1e1e1e; color:white;" } else { "" }
+                            )}
+                            onclick={ctx.link().callback(move |_| Msg::SwitchFile(index))}
+                            ondblclick={ctx.link().callback(move |_| Msg::RenameFile(index))}
+                            title={format!("{} ({})", file.name, file.language)}
+                        >
+                            { &file.name }
+                            { if self.live_files.iter().any(|l| l.name == file.name) {
+                                html! {
+                                    <span style="margin-left:4px; color:#4ec9b0;" title={tr!("live-file.title")}>{"⟳"}</span>
+                                }
+                            } else {
+                                html! {}
+                            }}
+                            { count.map(|c| html! {
+                                <span style="color:#e8c547; margin-left:4px;">{format!("({})", c)}</span>
+                            })}
+                            { if index == 0 {
+                                html! {}
+                            } else {
+                                html! {
+                                    <span style="margin-left:6px; color:#888;" title={tr!("remove-file-title")}
+                                        onclick={ctx.link().callback(move |e: MouseEvent| {
+                                            e.stop_propagation();
+                                            Msg::RemoveFile(index)
+                                        })}>{"×"}</span>
+                                }
+                            }}
+                        </div>
+                    }
+                })}
+                <button title={tr!("add-file.title")} onclick={ctx.link().callback(|_| Msg::AddFile)}>{ tr!("add-file") }</button>
+                { if crate::live_file::supported() {
+                    html! {
+                        <button title={tr!("open-live-file.title")}
+                            onclick={ctx.link().callback(|_| Msg::OpenLiveFile)}>{ tr!("open-live-file") }</button>
+                    }
+                } else {
+                    html! {}
+                }}
+                { if self.live_files.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <>
+                            <button title={tr!("reload-live-files.title")}
+                                onclick={ctx.link().callback(|_| Msg::ReloadLiveFiles(true))}>{ tr!("reload-live-files") }</button>
+                            <label style="white-space:nowrap;" title={tr!("live-file-polling.title")}>
+                                <input type="checkbox" checked={self.live_poll.is_some()}
+                                    onchange={ctx.link().callback(|_| Msg::ToggleLivePolling)} />
+                                { tr!("live-file-polling") }
+                            </label>
+                        </>
+                    }
+                }}
+                { if self.files.len() > 1 && !self.file_highlights.is_empty() {
+                    html! {
+                        <span style="margin-left:auto; padding:0 8px; white-space:nowrap;">
+                            { tr!("files-summary", matches = total, files = self.file_highlights.iter().filter(|h| !h.is_empty()).count()) }
+                        </span>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        }
+    }
+
+    /// offer to switch to the language a large paste looks like, without getting in the way
+    /// asks what to do with an opened link while the user has rules of their own
+    fn view_incoming(&self, ctx: &Context<Self>) -> Html {
+        let cfg = match &self.incoming {
+            Some(v) => v,
+            None => return html! {},
+        };
+
+        html! {
+            <div role="alertdialog" style="
+                display:flex;
+                align-items:center;
+                gap:8px;
+                padding:6px 10px;
+                background:#252526;
+                color:#ccc;
+                border-bottom:1px solid #007acc;
+                font-size:13px;
+            ">
+                <span>{ tr!("incoming-link", count = cfg.lhs.len()) }</span>
+                <button title={tr!("incoming-replace.title")}
+                    onclick={ctx.link().callback(|_| Msg::ReplaceWithIncoming)}>{ tr!("incoming-replace") }</button>
+                <button title={tr!("incoming-merge.title")}
+                    onclick={ctx.link().callback(|_| Msg::MergeIncoming)}>{ tr!("incoming-merge") }</button>
+                <span style="cursor:pointer;" title={tr!("close")}
+                    onclick={ctx.link().callback(|_| Msg::DismissIncoming)}>{"×"}</span>
+            </div>
+        }
+    }
+
+    /// says the results came with the link, offering to run here instead
+    fn view_shared_results(&self, ctx: &Context<Self>) -> Html {
+        if !self.shared_results {
+            return html! {};
+        }
+        html! {
+            <div role="status" style="
+                display:flex;
+                align-items:center;
+                gap:8px;
+                padding:6px 10px;
+                background:#252526;
+                color:#ccc;
+                border-bottom:1px solid #007acc;
+                font-size:13px;
+            ">
+                <span>{ tr!("shared-results", count = self.results.matches.len()) }</span>
+                <button disabled={!self.engine_ready}
+                    onclick={ctx.link().callback(|_| Msg::Run)}>{ tr!("shared-results-rerun") }</button>
+            </div>
+        }
+    }
+
+    /// offer of the example for the language an untouched session was switched to
+    fn view_preset_offer(&self, ctx: &Context<Self>) -> Html {
+        let lang = match &self.preset_offer {
+            Some(v) => v,
+            None => return html! {},
+        };
+
+        html! {
+            <div role="status" style="
+                position:absolute;
+                top:8px;
+                right:16px;
+                z-index:7;
+                display:flex;
+                align-items:center;
+                gap:8px;
+                padding:6px 10px;
+                background:#252526;
+                color:#ccc;
+                border:1px solid #007acc;
+                border-radius:4px;
+                font-size:13px;
+                box-shadow:0 2px 8px rgba(0,0,0,0.5);
+            ">
+                <span>{ tr!("preset-offer", language = language_label(lang)) }</span>
+                <button onclick={ctx.link().callback(|_| Msg::LoadPreset)}>
+                    { tr!("preset-offer-load") }
+                </button>
+                <span style="cursor:pointer;" title={tr!("close")}
+                    onclick={ctx.link().callback(|_| Msg::DismissPreset)}>{"×"}</span>
+            </div>
+        }
+    }
+
+    fn view_language_suggestion(&self, ctx: &Context<Self>) -> Html {
+        let lang = match &self.language_suggestion {
+            Some(v) => v,
+            None => return html! {},
+        };
+
+        html! {
+            <div role="status" style="
+                position:absolute;
+                top:8px;
+                right:16px;
+                z-index:7;
+                display:flex;
+                align-items:center;
+                gap:8px;
+                padding:6px 10px;
+                background:#252526;
+                color:#ccc;
+                border:1px solid #007acc;
+                border-radius:4px;
+                font-size:13px;
+                box-shadow:0 2px 8px rgba(0,0,0,0.5);
+            ">
+                <span>{ tr!("language-suggestion", language = language_label(lang)) }</span>
+                <button onclick={ctx.link().callback(|_| Msg::AcceptLanguageSuggestion)}>
+                    { tr!("language-suggestion-switch") }
+                </button>
+                <span style="cursor:pointer;" title={tr!("close")}
+                    onclick={ctx.link().callback(|_| Msg::DismissLanguageSuggestion)}>{"×"}</span>
+            </div>
+        }
+    }
+
+    /// peek listing the other matches that captured the same values as the clicked one
+    fn view_capture_peek(&self, ctx: &Context<Self>) -> Html {
+        let peek = match &self.peek {
+            Some(v) => v,
+            None => return html! {},
+        };
+        let origin = &self.results.matches[peek.origin];
+        let files = self.result_files();
+
+        html! {
+            <div style="
+                position:absolute;
+                left:0;
+                right:0;
+                bottom:0;
+                max-height:40%;
+                overflow-y:auto;
+                z-index:5;
+                background:#252526;
+                color:#ccc;
+                border-top:2px solid #007acc;
+                font-size:13px;
+            ">
+                <div style="display:flex; padding:4px 8px; background:#333;">
+                    <span>
+                        { format!("{} {}:{}", if origin.name.is_empty() { tr!("peek-match") } else { origin.name.clone() }, origin.start_line, origin.start_col) }
+                    </span>
+                    { if self.run_source.is_some() {
+                        let index = peek.origin;
+                        html! {
+                            <button style="margin-left:8px; font-size:11px; padding:0 4px;" title={tr!("explain-match.title")}
+                                onclick={ctx.link().callback(move |_| Msg::ExplainMatch(index))}>
+                                { tr!("explain-match") }
+                            </button>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={ctx.link().callback(|_| Msg::ClosePeek)}>{"×"}</span>
+                </div>
+                { for peek.captures.iter().map(|(name, value, others)| html! {
+                    <div style="padding:4px 8px;">
+                        <div style="color:#9cdcfe;">
+                            { tr!("peek-others", capture = name.as_str(), value = format!("{:?}", value), count = others.len()) }
+                        </div>
+                        { for others.iter().map(|&i| {
+                            let m = &self.results.matches[i];
+                            let file = files.get(m.file).map(|f| f.as_str()).unwrap_or("?");
+                            html! {
+                                <div style="padding-left:12px; cursor:pointer;"
+                                    onclick={ctx.link().callback(move |_| Msg::RevealMatch(i))}>
+                                    { format!("{}:{}:{}  {}", file, m.start_line, m.start_col, m.name) }
+                                </div>
+                            }
+                        })}
+                    </div>
+                })}
+            </div>
+        }
+    }
+
+    fn view_explanation(&self, ctx: &Context<Self>) -> Html {
+        let explanation = match &self.explanation {
+            Some(v) => v,
+            None => return html! {},
+        };
+        let section = |title: &str, body: Html| {
+            html! {
+                <div style="padding:4px 8px;">
+                    <div style="color:#888; font-size:11px; text-transform:uppercase;">{ title }</div>
+                    { body }
+                </div>
+            }
+        };
+        let code = |text: &str| {
+            html! {
+                <pre style="margin:2px 0; white-space:pre-wrap; color:#ce9178;">{ text }</pre>
+            }
+        };
+
+        html! {
+            <div style="
+                position:absolute;
+                top:0;
+                right:0;
+                bottom:0;
+                width:340px;
+                overflow-y:auto;
+                z-index:6;
+                background:#252526;
+                color:#ccc;
+                border-left:2px solid #007acc;
+                font-size:13px;
+            ">
+                <div style="display:flex; padding:4px 8px; background:#333;">
+                    <span>{ tr!("explain-title", rule = if explanation.unit.is_empty() { tr!("unnamed") } else { explanation.unit.clone() }) }</span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={ctx.link().callback(|_| Msg::CloseExplanation)}>{"×"}</span>
+                </div>
+                { section(&tr!("explain-pattern"), match &explanation.pattern {
+                    Some(p) => code(p),
+                    None => html! {
+                        <div style="color:#f48771;">
+                            { tr!("explain-no-pattern") }
+                        </div>
+                    },
+                }) }
+                { section(&tr!("explain-matched-text"), code(&explanation.matched_text)) }
+                { section(&tr!("explain-tokens"), match &explanation.alignment {
+                    Some(alignment) => html! {
+                        <table style="border-collapse:collapse; font-family:monospace;">
+                            { for alignment.iter().map(|(pattern_token, consumed)| html! {
+                                <tr>
+                                    <td style="color:#9cdcfe; padding-right:8px; vertical-align:top;">{ pattern_token }</td>
+                                    <td style="color:#888; padding-right:8px; vertical-align:top;">{"→"}</td>
+                                    <td>{ if consumed.is_empty() { tr!("explain-nothing") } else { consumed.join(" ") } }</td>
+                                </tr>
+                            })}
+                        </table>
+                    },
+                    None => html! {
+                        <div style="font-family:monospace;">{ explanation.subject_tokens.join(" · ") }</div>
+                    },
+                }) }
+                { section(&tr!("explain-captures"), if explanation.captures.is_empty() {
+                    html! { <div style="color:#888;">{ tr!("explain-no-captures") }</div> }
+                } else {
+                    html! {
+                        { for explanation.captures.iter().map(|(k, v)| html! {
+                            <div><span style="color:#9cdcfe;">{ k }</span>{ format!(" = {:?}", v) }</div>
+                        })}
+                    }
+                }) }
+            </div>
+        }
+    }
+
+    /// how far each pattern of a rule without matches got, see `explain_no_match`
+    fn view_near_misses(&self, ctx: &Context<Self>) -> Html {
+        let Some((name, misses)) = &self.near_misses else {
+            return html! {};
+        };
+        html! {
+            <div style="
+                position:absolute;
+                top:0;
+                right:0;
+                bottom:0;
+                width:340px;
+                overflow-y:auto;
+                z-index:6;
+                background:#252526;
+                color:#ccc;
+                border-left:2px solid #888;
+                font-size:13px;
+            ">
+                <div style="display:flex; padding:4px 8px; background:#333;">
+                    <span>{ tr!("near-miss-title", rule = if name.is_empty() { tr!("unnamed") } else { name.clone() }) }</span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={ctx.link().callback(|_| Msg::CloseNearMisses)}>{"×"}</span>
+                </div>
+                { for misses.iter().enumerate().map(|(i, miss)| {
+                    let clickable = miss.reached.is_some();
+                    html! {
+                        <div style={format!("padding:4px 8px; border-bottom:1px solid #333; {}", if clickable { "cursor:pointer;" } else { "" })}
+                            onclick={clickable.then(|| ctx.link().callback(move |_| Msg::RevealNearMiss(i)))}>
+                            <pre style="margin:2px 0; white-space:pre-wrap; color:#ce9178;">{ &miss.pattern }</pre>
+                            <div style="color:#888;">
+                                { if miss.reached.is_some() {
+                                    Self::describe_near_miss(miss)
+                                } else {
+                                    tr!("near-miss-nothing")
+                                } }
+                            </div>
+                        </div>
+                    }
+                })}
+            </div>
+        }
+    }
+
+    fn view_pattern_errors(&self, ctx: &Context<Self>) -> Html {
+        if self.pattern_errors.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <details open={true} style="
+                max-height:30vh;
+                overflow-y:auto;
+                background:#3a1d1d;
+                color:#ccc;
+                padding:4px 8px;
+                font-size:12px;
+                border-bottom:1px solid #444;
+            ">
+                <summary style={format!("cursor:pointer; color:{};", if self.pattern_errors_fatal { "#f48771" } else { "#cca700" })}>
+                    { if self.pattern_errors_fatal {
+                        tr!("pattern-errors", count = self.pattern_errors.len())
+                    } else {
+                        tr!("patterns-skipped", count = self.pattern_errors.len())
+                    }}
+                    <span style="float:right; cursor:pointer;" title={tr!("close")}
+                        onclick={ctx.link().callback(|_| Msg::ClosePatternErrors)}>{"×"}</span>
+                </summary>
+                <table style="border-collapse:collapse; width:100%;">
+                    { for self.pattern_errors.iter().map(|e| html! {
+                        <tr style="vertical-align:top; border-top:1px solid #444;" title={e.hint()}>
+                            <td style="padding:2px 8px 2px 0; white-space:nowrap; color:#dcdcaa;">
+                                { format!("#{} {}", e.unit + 1, if e.unit_name.is_empty() { tr!("unnamed") } else { e.unit_name.clone() }) }
+                            </td>
+                            <td style="padding:2px 8px 2px 0;">
+                                <pre style="margin:0; white-space:pre-wrap; color:#ce9178;">{ &e.pattern }</pre>
+                            </td>
+                            <td style="padding:2px 0; font-family:monospace; white-space:pre-wrap;">{ &e.message }</td>
+                        </tr>
+                    })}
+                </table>
+            </details>
+        }
+    }
+
+    /// notice when the active file has more matches than are highlighted
+    fn view_highlight_cap(&self, ctx: &Context<Self>) -> Html {
+        let total = self
+            .file_highlights
+            .get(self.active_file)
+            .map_or(0, |h| h.len());
+        if total <= self.highlight_limit {
+            return html! {};
+        }
+        let next = self
+            .settings
+            .max_highlights
+            .min(total - self.highlight_limit);
+
+        html! {
+            <div style="display:flex; gap:8px; align-items:center; padding:2px 8px; background:#4b3d10; color:#ffd27f; font-size:12px;">
+                <span>{ tr!("highlights-capped", shown = self.highlight_limit, total = total) }</span>
+                <button onclick={ctx.link().callback(|_| Msg::ShowMoreHighlights)}>
+                    { tr!("show-more-highlights", count = next) }
+                </button>
+            </div>
+        }
+    }
+
+    /// what the last run in diff mode found, above the diff editor
+    fn view_diff_summary(&self) -> Html {
+        if !self.diff_mode {
+            return html! {};
+        }
+        let text = match self.diff_counts {
+            Some((new, gone)) => tr!("diff-mode-summary", new = new, gone = gone),
+            None => tr!("diff-mode-hint"),
+        };
+        html! {
+            <div style="padding:2px 8px; background:#333; color:#ccc; font-size:12px;">{ text }</div>
+        }
+    }
+
+    /// which lexer the current language's subject and patterns go through
+    fn view_lexer_badge(&self) -> Html {
+        let badge = "padding:1px 6px; border-radius:8px; font-size:12px; white-space:nowrap;";
+        match resolve_language(&self.current_language) {
+            Ok((_, family)) => html! {
+                <span style={format!("{} background:#3a3d41; color:#ccc;", badge)}
+                    title={tr!("lexer-family.title")}>
+                    { family.label() }
+                </span>
+            },
+            Err(e) => html! {
+                <span style={format!("{} background:#5a1d1d; color:#f88;", badge)}
+                    title={tr!("lexer-unknown.title", error = e)}>
+                    { tr!("lexer-unknown") }
+                </span>
+            },
+        }
+    }
+
+    /// cursor position in the subject, with the matches covering it
+    fn view_cursor_status(&self) -> Html {
+        let (line, col) = match self.cursor {
+            Some(v) => v,
+            None => return html! {},
+        };
+        let here: Vec<String> = self
+            .results
+            .at(self.active_file, line, col)
+            .into_iter()
+            .take(MAX_CURSOR_MATCHES)
+            .map(|i| {
+                let m = &self.results.matches[i];
+                let name = if m.name.is_empty() {
+                    tr!("unnamed")
+                } else {
+                    m.name.clone()
+                };
+                if m.captures.is_empty() {
+                    name
+                } else {
+                    let captures: Vec<String> = m
+                        .captures
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, v))
+                        .collect();
+                    format!("{} {{{}}}", name, captures.join(", "))
+                }
+            })
+            .collect();
+
+        html! {
+            <>
+                <span>{ tr!("cursor-position", line = line, col = col) }</span>
+                { if here.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <span style="overflow:hidden; text-overflow:ellipsis;" title={here.join("\n")}>
+                            { format!("▸ {}", here.join("; ")) }
+                        </span>
+                    }
+                }}
+            </>
+        }
+    }
+
+    /// a failed run or config. compile errors go to the error list, the banner just counts
+    /// them. the banner ends with where to look, if the error says
+    fn show_run_error(&mut self, e: PlaygroundError) {
+        let hint = e.hint();
+        let message = match e {
+            PlaygroundError::PatternCompile(errors) => {
+                let message = tr!("patterns-failed", count = errors.len());
+                self.pattern_errors = errors;
+                self.pattern_errors_fatal = true;
+                message
+            }
+            other => other.to_string(),
+        };
+        self.error = Some(match hint {
+            Some(hint) => format!("{}\n→ {}", message, hint),
+            None => message,
+        });
+    }
+
+    fn view_snapshot_diff(&self, ctx: &Context<Self>) -> Html {
+        let diff = match &self.snapshot_diff {
+            Some(v) => v,
+            None => return html! {},
+        };
+        let rows = |title: &str, color: &str, matches: &[crate::snapshot::SnapshotMatch]| {
+            html! {
+                <div>
+                    <div style={format!("color:{};", color)}>{ format!("{} ({})", title, matches.len()) }</div>
+                    { for matches.iter().map(|m| html! {
+                        <div style="padding-left:12px;">
+                            { format!("{}:{}:{}  {}  {}", m.file, m.start_line, m.start_col, m.name, serde_json::to_string(&m.captures).unwrap_or_default()) }
+                        </div>
+                    })}
+                </div>
+            }
+        };
+
+        html! {
+            <div style="
+                max-height:30vh;
+                overflow-y:auto;
+                background:#252526;
+                color:#ccc;
+                padding:8px;
+                font-family:monospace;
+                font-size:12px;
+                border-bottom:1px solid #444;
+            ">
+                <div style="display:flex;">
+                    <span>
+                        { if diff.is_empty() {
+                            tr!("snapshot-unchanged")
+                        } else {
+                            tr!("snapshot-diff")
+                        }}
+                    </span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={ctx.link().callback(|_| Msg::CloseSnapshotDiff)}>{"×"}</span>
+                </div>
+                { if diff.missing.is_empty() { html! {} } else { rows(&tr!("snapshot-missing"), "#f48771", &diff.missing) } }
+                { if diff.added.is_empty() { html! {} } else { rows(&tr!("snapshot-new"), "#8fd18f", &diff.added) } }
+            </div>
+        }
+    }
+
+    /// the states loads replaced this session, newest first
+    fn view_session_tabs(&self, ctx: &Context<Self>) -> Html {
+        let closable = self.sessions.len() > 1;
+        html! {
+            <div role="tablist" aria-label={tr!("sessions")} style="
+                display:flex;
+                align-items:flex-end;
+                gap:2px;
+                background:#181818;
+                padding:4px 6px 0;
+                font-size:12px;
+                overflow-x:auto;
+            ">
+                { for self.sessions.iter().enumerate().map(|(i, session)| {
+                    let shown = i == self.sessions.active();
+                    let colors = if shown {
+                        "background:#222; color:white;"
+                    } else {
+                        "background:#2d2d2d; color:#999;"
+                    };
+                    html! {
+                        <div role="tab" aria-selected={shown.to_string()} title={tr!("session-tab.title")}
+                            style={format!("display:flex; align-items:center; gap:6px; padding:3px 8px; \
+                                border-radius:4px 4px 0 0; cursor:pointer; white-space:nowrap; {}", colors)}
+                            onclick={ctx.link().callback(move |_| Msg::SelectSession(i))}
+                            ondblclick={ctx.link().callback(move |_| Msg::RenameSession(i))}>
+                            { session.name.clone() }
+                            { if closable {
+                                html! {
+                                    <span title={tr!("close-session")}
+                                        onclick={ctx.link().callback(move |e: MouseEvent| {
+                                            e.stop_propagation();
+                                            Msg::CloseSession(i)
+                                        })}>{"×"}</span>
+                                }
+                            } else {
+                                html! {}
+                            }}
+                        </div>
+                    }
+                })}
+                <button title={tr!("new-session.title")} style="margin-left:4px;"
+                    onclick={ctx.link().callback(|_| Msg::NewSession)}>{"+"}</button>
+            </div>
+        }
+    }
+
+    fn view_replaced_menu(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div style="
+                position:absolute;
+                top:100%;
+                left:0;
+                z-index:10;
+                min-width:280px;
+                background:#2d2d2d;
+                border:1px solid #444;
+                padding:6px;
+            ">
+                { for self.replaced.iter().enumerate().map(|(i, replaced)| {
+                    let at = js_sys::Date::new(&JsValue::from_f64(replaced.at));
+                    let rules = rule_names(&replaced.entry.lhs).len();
+                    html! {
+                        <a href="#" style="display:block; padding:2px 0; color:white;"
+                            title={tr!("recently-replaced-item.title", rules = rules, files = replaced.entry.files.len())}
+                            onclick={ctx.link().callback(move |e: MouseEvent| {
+                                e.prevent_default();
+                                Msg::RestoreReplaced(i)
+                            })}>
+                            { tr!(
+                                "recently-replaced-item",
+                                time = String::from(at.to_locale_time_string("default")),
+                                by = replaced.by.as_str(),
+                            ) }
+                        </a>
+                    }
+                })}
+            </div>
+        }
+    }
+
+    fn view_snippets_menu(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div style="
+                position:absolute;
+                top:100%;
+                left:0;
+                z-index:10;
+                min-width:320px;
+                background:#2d2d2d;
+                border:1px solid #444;
+                padding:6px;
+            ">
+                { if self.snippets.is_empty() {
+                    html! { <div style="color:#aaa;">{ tr!("no-snippets") }</div> }
+                } else {
+                    html! {}
+                }}
+                { for self.snippets.names().map(|name| {
+                    let load = name.clone();
+                    let rename = name.clone();
+                    let duplicate = name.clone();
+                    let delete = name.clone();
+                    html! {
+                        <div style="display:flex; align-items:center; gap:4px; padding:2px 0;">
+                            <a href="#" style="flex:1; color:white;"
+                                onclick={ctx.link().callback(move |e: MouseEvent| {
+                                    e.prevent_default();
+                                    Msg::LoadSnippet(load.clone())
+                                })}>{name.clone()}</a>
+                            <button title={tr!("rename")}
+                                onclick={ctx.link().callback(move |_| Msg::RenameSnippet(rename.clone()))}>{"✎"}</button>
+                            <button title={tr!("duplicate")}
+                                onclick={ctx.link().callback(move |_| Msg::DuplicateSnippet(duplicate.clone()))}>{"⧉"}</button>
+                            <button title={tr!("delete")}
+                                onclick={ctx.link().callback(move |_| Msg::DeleteSnippet(delete.clone()))}>{"✕"}</button>
+                        </div>
+                    }
+                })}
+            </div>
+        }
+    }
+}
+
+impl Component for App {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let path = url_path();
+        let settings = Settings::load();
+        i18n::set_locale(settings.locale);
+
+        let (cfg, mut shared, err) = if let Some(id) = short_id_from_url_str(&path) {
+            // start from the defaults until the stored config arrives
+            let err = open_short_link(ctx, id, &settings.shortener_url).err();
+            (Default::default(), None, err)
+        } else if is_self_test_url(&path) {
+            (Default::default(), None, None)
+        } else {
+            match PlaygroundConfig::from_url_str_with_results(&path) {
+                Ok((cfg, shared)) => (cfg, shared, None),
+                Err(e) => (Default::default(), None, Some(e)),
+            }
+        };
+        let mut files = cfg.editor_files();
+        let (mut lhs, mut rhs, mut lang) = cfg.to_editor_parts();
+
+        // without a share link, start in the language the user last picked
+        let mut fresh_start = path.len() <= crate::io::PUBLIC_URL.len() || is_self_test_url(&path);
+        if fresh_start {
+            if let Some(last) = recent_languages().into_iter().next() {
+                lang = last;
+                files[0].language = lang.clone();
+            }
+        }
+
+        // the editors as the panic hook saved them, if the last session crashed
+        let mut active_file = 0;
+        let err = match recovery::take().filter(|saved| !saved.files.is_empty()) {
+            Some(saved) => {
+                active_file = saved.active_file.min(saved.files.len() - 1);
+                let file = &saved.files[active_file];
+                (rhs, lang) = (file.content.clone(), file.language.clone());
+                (lhs, files) = (saved.lhs, saved.files);
+                fresh_start = false;
+                shared = None;
+                Some(tr!("recovered-after-crash", error = saved.message))
+            }
+            None => err,
+        };
+        // the tabs of earlier visits. without a link the one shown last comes back, a link or
+        // a recovered crash opens in a tab of its own
+        let mut sessions = Sessions::load();
+        let err = match sessions.active_session().cloned() {
+            Some(session) if fresh_start => {
+                active_file = session.active_file.min(session.files.len() - 1);
+                let file = &session.files[active_file];
+                (rhs, lang) = (file.content.clone(), file.language.clone());
+                (lhs, files) = (session.lhs, session.files);
+                fresh_start = false;
+                err
+            }
+            Some(_) => {
+                let name = sessions.free_name();
+                let session = Session {
+                    name,
+                    lhs: lhs.clone(),
+                    files: files.clone(),
+                    active_file,
+                };
+                sessions.add(session).err().or(err)
+            }
+            None => sessions
+                .save_active(lhs.clone(), files.clone(), active_file)
+                .err()
+                .or(err),
+        };
+        let parked = (0..sessions.len()).map(|_| None).collect();
+        if let Some(matches) = shared {
+            ctx.link().send_message(Msg::ShowSharedResults(matches));
+        }
+
+        let link = ctx.link().clone();
+        yew::platform::spawn_local(async move {
+            link.send_message(Msg::DeployConfigLoaded(deploy::fetch().await, fresh_start));
+        });
+
+        let lhs_editor: Rc<RefCell<Option<CodeEditorLink>>> = Rc::default();
+        let rhs_editor: Rc<RefCell<Option<CodeEditorLink>>> = Rc::default();
+        recovery::install(lhs_editor.clone(), rhs_editor.clone());
+        recovery::set_files(files.clone(), active_file);
+
+        let dirty = Rc::new(Cell::new(false));
+        let dirty_clone = dirty.clone();
+        let beforeunload_listener =
+            EventListener::new(&window().unwrap(), "beforeunload", move |event| {
+                if dirty_clone.get() {
+                    let event = event.dyn_ref::<BeforeUnloadEvent>().unwrap();
+                    event.prevent_default();
+                    // required by older browsers to show the prompt
+                    event.set_return_value("unsaved changes");
+                }
+            });
+
+        let link = ctx.link().clone();
+        let escape_listener = EventListener::new(&window().unwrap(), "keydown", move |event| {
+            let event = event.dyn_ref::<KeyboardEvent>().unwrap();
+            if event.key() == "Escape" {
+                link.send_message(Msg::RestoreLayout);
+            }
+        });
+
+        // ctrl+alt+z and ctrl+alt+shift+z, leaving ctrl+z to the editors' own undo. the code
+        // is checked since alt changes the key on some layouts
+        let link = ctx.link().clone();
+        let undo_listener = EventListener::new(&window().unwrap(), "keydown", move |event| {
+            let event = event.dyn_ref::<KeyboardEvent>().unwrap();
+            if event.ctrl_key() && event.alt_key() && event.code() == "KeyZ" {
+                event.prevent_default();
+                link.send_message(if event.shift_key() {
+                    Msg::Redo
+                } else {
+                    Msg::Undo
+                });
+            }
+        });
+
+        // f8 and shift+f8 as in VS Code. caught before the editors, whose own f8 goes to the
+        // next problem
+        let link = ctx.link().clone();
+        let step_listener = EventListener::new_with_options(
+            &window().unwrap(),
+            "keydown",
+            EventListenerOptions {
+                phase: EventListenerPhase::Capture,
+                passive: false,
+            },
+            move |event| {
+                let event = event.dyn_ref::<KeyboardEvent>().unwrap();
+                if event.key() == "F8" && !event.ctrl_key() && !event.alt_key() {
+                    event.prevent_default();
+                    event.stop_propagation();
+                    link.send_message(Msg::StepMatch(event.shift_key()));
+                }
+            },
+        );
+
+        let link = ctx.link().clone();
+        let hashchange_listener = EventListener::new(&window().unwrap(), "hashchange", move |_| {
+            link.send_message(Msg::HashChanged)
+        });
+
+        Self {
+            baseline: (lhs.clone(), files.clone()),
+            files,
+            active_file,
+            file_highlights: Vec::new(),
+            results: Rc::default(),
+            results_open: false,
+            on_reveal_match: ctx.link().callback(Msg::RevealMatch),
+            on_results_visible: ctx
+                .link()
+                .callback(|(first, last)| Msg::ResultsScrolled(first, last)),
+            peek: None,
+            explanation: None,
+            near_misses: None,
+            snapshot_diff: None,
+            shared_results: false,
+            live_files: Vec::new(),
+            live_poll: None,
+            snapshot_reader: None,
+            workspace_reader: None,
+            scanned_file: None,
+            scan_cancel: None,
+            scan_progress: (0, 0),
+            sliced: None,
+            count_only: false,
+            scope: None,
+            link_stats: None,
+            link_audit: Vec::new(),
+            inlay_hints: false,
+            capture_ranges: false,
+            link_scrolling: false,
+            summary: None,
+            dirty,
+            _beforeunload_listener: beforeunload_listener,
+            _escape_listener: escape_listener,
+            _undo_listener: undo_listener,
+            _step_listener: step_listener,
+            _hashchange_listener: hashchange_listener,
+            incoming: None,
+            undo: Vec::new(),
+            replaced: Vec::new(),
+            replaced_open: false,
+            redo: Vec::new(),
+            sessions,
+            parked,
+            session_save_timeout: None,
+            left_options: Rc::new(editor_options(lhs, "yaml".to_string())),
+            right_options: Rc::new(editor_options(rhs, lang.clone())),
+            layout: Layout::default(),
+            mousemove_listener: None,
+            mouseup_listener: None,
+            current_language: lang,
+            rhs_editor,
+            lhs_editor,
+            compare_mode: false,
+            left_b_options: Rc::new(editor_options(String::new(), "yaml".to_string())),
+            lhs_b_editor: Rc::new(RefCell::new(None)),
+            snippets: SnippetLibrary::load(),
+            snippets_open: false,
+            rules_menu_open: false,
+            rule_packs_open: false,
+            rule_packs: None,
+            rule_pack_status: None,
+            scratchpad_units: None,
+            highlight_limit: settings.max_highlights,
+            settings,
+            settings_open: false,
+            deploy: DeployConfig::default(),
+            error: err,
+            selected_results: Vec::new(),
+            result_order: ResultOrder::default(),
+            results_tab: ResultsTab::default(),
+            aggregate_by: None,
+            cursor: None,
+            tag_filter: BTreeSet::new(),
+            rule_tags: BTreeSet::new(),
+            rule_problems: Vec::new(),
+            language_suggestion: None,
+            preset_offer: None,
+            pattern_errors: Vec::new(),
+            pattern_errors_fatal: false,
+            compiled: None,
+            engine_ready: false,
+            warm_up_frame: None,
+            pattern_check: None,
+            last_run_key: None,
+            print_view_open: false,
+            usage: Rc::new(UsageLog::load()),
+            usage_open: false,
+            rule_diff_open: false,
+            batch_decode_open: false,
+            self_test_open: is_self_test_url(&path),
+            diff_mode: false,
+            diff_editor: None,
+            diff_start: String::new(),
+            diff_counts: None,
+            tutorial: None,
+            tutorial_hint: None,
+            auto_run_timeout: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            // e.g. from a shortcut, while the buttons are disabled. an auto-run is made up
+            // for by `WarmUpEngine`
+            Msg::Run | Msg::RunOnSelection if !self.engine_ready => false,
+            Msg::CopyShareLink
+            | Msg::Run
+            | Msg::CheckPatterns
+            | Msg::SaveSnippetAs
+            | Msg::Shorten => {
+                let was_error = self.error.is_some() || !self.pattern_errors.is_empty();
+                self.error = None;
+                if matches!(msg, Msg::Run | Msg::CheckPatterns) {
+                    self.pattern_errors.clear();
+                }
+
+                let rhs_content = self.rhs_content();
+                let lhs_content = self.lhs_content();
+                // set by `RunOnSelection` for this one run
+                let scope = self.scope.take();
+                if matches!(msg, Msg::Run) {
+                    // a new run replaces one still being matched in slices
+                    self.sliced = None;
+                    // a run on a selection doesn't cover everything an auto-run would
+                    self.last_run_key = scope.is_none().then(|| self.run_key());
+                    self.shared_results = false;
+                }
+
+                let schema_errors = validate_lhs(&lhs_content);
+                self.apply_schema_markers(&schema_errors);
+                let schema_errors: Vec<&SchemaError> =
+                    schema_errors.iter().filter(|e| !e.warning).collect();
+
+                // the schema errors say where the problem is, prefer them over serde's
+                let cfg = match schema_errors.first() {
+                    None => self.config_with_lhs(&lhs_content),
+                    Some(first) => {
+                        let lines: Vec<String> =
+                            schema_errors.iter().map(|e| e.to_string()).collect();
+                        Err(PlaygroundError::YamlLhs {
+                            message: lines.join("\n"),
+                            span: Some((first.line, first.col)),
+                        })
+                    }
+                };
+                let cfg = match cfg {
+                    Ok(v) => v,
+                    Err(e) => {
+                        // preserve current content
+                        self.right_options =
+                            Rc::new(editor_options(rhs_content, self.current_language.clone()));
+                        self.left_options =
+                            Rc::new(editor_options(lhs_content, "yaml".to_string()));
+                        self.show_run_error(e);
+                        return true;
+                    }
+                };
+
+                if matches!(msg, Msg::Run | Msg::CopyShareLink) {
+                    // compressing a big subject at max level can take a while, so it
+                    // happens after the results are rendered
+                    match self.shared_link(&cfg, &lhs_content, self.settings.link_encoding) {
+                        Ok(pending) => {
+                            let level = self.settings.compression;
+                            let copy = matches!(msg, Msg::CopyShareLink);
+                            let link = ctx.link().clone();
+                            yew::platform::spawn_local(async move {
+                                yew::platform::time::sleep(Duration::ZERO).await;
+                                let (fragment, stats) = pending.encode(level);
+                                link.send_message(Msg::LinkEncoded(fragment, stats, copy));
+                            });
+                        }
+                        Err(e) => {
+                            self.error = Some(e);
+                            if matches!(msg, Msg::CopyShareLink) {
+                                return true;
+                            }
+                        }
+                    }
+                }
+
+                match msg {
+                    Msg::CopyShareLink => {
+                        self.baseline = (lhs_content, self.files_snapshot());
+                        return self.refresh_dirty() || was_error;
+                    }
+                    Msg::Shorten => {
+                        let endpoint = self.settings.shortener_url.clone();
+                        let pending =
+                            match self.shared_link(&cfg, &lhs_content, LinkEncoding::BaseX) {
+                                Ok(v) => v,
+                                Err(e) => {
+                                    self.error = Some(e);
+                                    return true;
+                                }
+                            };
+                        let level = self.settings.compression;
+                        let link = ctx.link().clone();
+                        let shortened = (lhs_content, self.files_snapshot());
+                        yew::platform::spawn_local(async move {
+                            yew::platform::time::sleep(Duration::ZERO).await;
+                            let (encoded, _) = pending.encode(level);
+                            let result = crate::shortener::shorten(&endpoint, encoded).await;
+                            link.send_message(Msg::ShortLinkCreated(result, shortened));
+                        });
+                        return was_error;
+                    }
+                    Msg::SaveSnippetAs => {
+                        let name = match gloo::dialogs::prompt(&tr!("save-snippet-prompt"), None) {
+                            Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                            _ => return was_error,
+                        };
+                        if self.snippets.get(&name).is_some()
+                            && !gloo::dialogs::confirm(&tr!(
+                                "overwrite-snippet-confirm",
+                                name = name.as_str()
+                            ))
+                        {
+                            return was_error;
+                        }
+                        if let Err(e) = self.snippets.save(name, cfg.to_url_str()) {
+                            self.error = Some(e);
+                            return true;
+                        }
+                        self.baseline = (lhs_content, self.files_snapshot());
+                        self.refresh_dirty();
+                        return true;
+                    }
+                    Msg::CheckPatterns => {
+                        let mut cfg = cfg;
+                        cfg.retain_tagged(&self.active_tags(&lhs_content));
+                        // every broken pattern at once, and graphs a run can reuse if there
+                        // are none
+                        match cfg.compile(true) {
+                            Ok(compiled) => {
+                                let failed: BTreeSet<(usize, &str)> = compiled
+                                    .skipped
+                                    .iter()
+                                    .map(|e| (e.unit, e.pattern.as_str()))
+                                    .collect();
+                                self.pattern_check = Some(PatternCheck {
+                                    lhs: lhs_content,
+                                    total: compiled.pattern_count,
+                                    failed: failed.len(),
+                                });
+                                self.pattern_errors = compiled.skipped.clone();
+                                self.pattern_errors_fatal = !self.pattern_errors.is_empty();
+                                self.compiled = Some(compiled);
+                            }
+                            Err(e) => {
+                                self.pattern_check = None;
+                                self.show_run_error(e);
+                            }
+                        }
+                        return true;
+                    }
+                    Msg::Run if self.diff_mode => {
+                        let Some(editor) = self.diff_editor.clone() else {
+                            return was_error;
+                        };
+                        let started = js_sys::Date::now();
+                        let (original, modified) = diff_mode::texts(&editor);
+                        let units = cfg.lhs.clone();
+                        let docs = RuleDocs::new(&units);
+                        let lang = self.current_language.clone();
+                        let cfg =
+                            PlaygroundConfig::from_editor_parts(&original, &lang, &lhs_content)
+                                .and_then(|mut cfg| {
+                                    cfg.add_file("modified", &lang, &modified)?;
+                                    Ok(cfg)
+                                });
+                        let mut cfg = match cfg {
+                            Ok(v) => v,
+                            Err(e) => {
+                                self.show_run_error(e);
+                                return true;
+                            }
+                        };
+                        cfg.retain_tagged(&self.active_tags(&lhs_content));
+                        let rule_count = cfg.lhs.iter().filter(|u| u.enabled).count();
+                        let compiled =
+                            match self.take_compiled(&cfg, self.settings.skip_invalid_patterns) {
+                                Ok(v) => v,
+                                Err(e) => {
+                                    self.show_run_error(e);
+                                    return true;
+                                }
+                            };
+                        let deadline = self.run_deadline();
+                        let run = search_resolved(
+                            cfg,
+                            self.settings.overlap_mode,
+                            None,
+                            &compiled,
+                            &deadline,
+                        );
+                        self.compiled = Some(compiled);
+                        let (matches, skipped, truncated) = match run {
+                            Ok(v) => v,
+                            Err(e) => {
+                                self.show_run_error(e);
+                                return true;
+                            }
+                        };
+                        let one_sided = diff_mode::one_sided(&matches, &original, &modified);
+                        let mut sides: [Vec<HighlightElement>; 2] = Default::default();
+                        let mut new_only = Vec::new();
+                        let mut gone = 0;
+                        for (m, one_sided) in matches.into_iter().zip(one_sided) {
+                            let modified_side = m.file == diff_mode::MODIFIED;
+                            let class_name = match (one_sided, modified_side) {
+                                (false, _) => "match-highlight",
+                                (true, false) => "match-highlight-a-only",
+                                (true, true) => "match-highlight-b-only",
+                            };
+                            sides[usize::from(modified_side)].push(HighlightElement::from_match(
+                                &m,
+                                class_name,
+                                docs.get(&m.name),
+                            ));
+                            match (one_sided, modified_side) {
+                                (true, true) => new_only.push(SearchMatch { file: 0, ..m }),
+                                (true, false) => gone += 1,
+                                _ => {}
+                            }
+                        }
+                        for (modified_side, elements) in [false, true].into_iter().zip(&sides) {
+                            let elements = serde_wasm_bindgen::to_value(elements)
+                                .expect("failed to serialize highlights");
+                            diff_mode::set_highlights(&editor, modified_side, &elements);
+                        }
+
+                        self.stop_scan();
+                        self.scanned_file = None;
+                        self.apply_highlights(&[]);
+                        self.file_highlights.clear();
+                        self.diff_counts = Some((new_only.len(), gone));
+                        self.summary = Some(RunSummary {
+                            timed_out: self.timed_out(&deadline),
+                            ..RunSummary::from_matches(&new_only, truncated)
+                        });
+                        let mut results = RunResults::new(new_only, &units);
+                        results.set_subjects(vec![modified]);
+                        results.sort(self.result_order);
+                        self.results = Rc::new(results);
+                        self.run_source = None;
+                        self.selected_results.clear();
+                        self.refresh_inlay_hints();
+                        self.peek = None;
+                        self.explanation = None;
+                        self.near_misses = None;
+                        self.pattern_errors = skipped;
+                        self.pattern_errors_fatal = false;
+                        self.record_usage(started, rule_count);
+                        return true;
+                    }
+                    Msg::Run if self.count_only => {
+                        let started = js_sys::Date::now();
+                        let skip_invalid = self.settings.skip_invalid_patterns;
+                        let mut cfg = cfg;
+                        cfg.retain_tagged(&self.active_tags(&lhs_content));
+                        let rule_count = cfg.lhs.iter().filter(|u| u.enabled).count();
+                        if let Some(scope) = &scope {
+                            scope.restrict(&mut cfg);
+                        }
+                        let compiled = match self.take_compiled(&cfg, skip_invalid) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                self.show_run_error(e);
+                                return true;
+                            }
+                        };
+                        let deadline = self.run_deadline();
+                        let counted = count(cfg, &compiled, &deadline);
+                        let skipped = compiled.skipped.clone();
+                        self.compiled = Some(compiled);
+                        let (per_unit, truncated) = match counted {
+                            Ok(v) => v,
+                            Err(e) => {
+                                self.show_run_error(e);
+                                return true;
+                            }
+                        };
+                        self.apply_highlights(&[]);
+                        self.file_highlights.clear();
+                        self.results = Rc::default();
+                        self.run_source = None;
+                        self.selected_results.clear();
+                        self.refresh_inlay_hints();
+                        self.peek = None;
+                        self.explanation = None;
+                        self.near_misses = None;
+                        self.summary = Some(RunSummary {
+                            per_unit,
+                            truncated,
+                            count_only: true,
+                            timed_out: self.timed_out(&deadline),
+                        });
+                        self.pattern_errors = skipped;
+                        self.pattern_errors_fatal = false;
+                        self.record_usage(started, rule_count);
+                        return true;
+                    }
+                    Msg::Run => {
+                        let started = js_sys::Date::now();
+                        let file_count = self.files.len();
+                        let overlap_mode = self.settings.overlap_mode;
+                        let skip_invalid = self.settings.skip_invalid_patterns;
+                        // as written: the tag filter below only decides what runs
+                        let units = cfg.lhs.clone();
+                        let docs = RuleDocs::new(&units);
+                        let mut cfg = cfg;
+                        cfg.retain_tagged(&self.active_tags(&lhs_content));
+                        let rule_count = cfg.lhs.iter().filter(|u| u.enabled).count();
+                        let source = RunSource::new(&cfg);
+                        let deadline = self.run_deadline();
+                        let slice_tokens = self.settings.run_slice_tokens;
+                        if slice_tokens > 0 && !self.compare_mode && scope.is_none() {
+                            let compiled = match self.take_compiled(&cfg, skip_invalid) {
+                                Ok(v) => v,
+                                Err(e) => {
+                                    self.show_run_error(e);
+                                    return true;
+                                }
+                            };
+                            self.sliced = Some(SlicedState {
+                                styles: UnitStyles::new(&cfg.lhs),
+                                rule_order: cfg.lhs.iter().map(|u| u.name.clone()).collect(),
+                                run: SlicedRun::new(cfg, compiled, slice_tokens),
+                                matches: Vec::new(),
+                                started,
+                                units,
+                                docs,
+                                rule_count,
+                                source,
+                                deadline,
+                                _next_frame: None,
+                                _next_timeout: None,
+                            });
+                            ctx.link().send_message(Msg::RunSlice);
+                            return true;
+                        }
+                        let run = match self.take_compiled(&cfg, skip_invalid) {
+                            Ok(compiled) => {
+                                let run = if self.compare_mode {
+                                    // B's pattern errors are flattened so they aren't mistaken for A's
+                                    let in_b = |e: PlaygroundError| {
+                                        PlaygroundError::Engine(format!("pattern set B: {}", e))
+                                    };
+                                    let lhs_b = self.lhs_b_content();
+                                    let cfg_b = self.config_with_lhs(&lhs_b).map_err(in_b);
+                                    let tags_b = self.active_tags(&lhs_b);
+                                    cfg_b.and_then(|mut cfg_b| {
+                                        cfg_b.retain_tagged(&tags_b);
+                                        let (a, mut skipped, truncated) = search_resolved(
+                                            cfg,
+                                            overlap_mode,
+                                            scope.as_ref(),
+                                            &compiled,
+                                            &deadline,
+                                        )?;
+                                        // B is edited on its own, so it isn't worth caching
+                                        let (b, skipped_b, _) = cfg_b
+                                            .compile(skip_invalid)
+                                            .and_then(|compiled_b| {
+                                                search_resolved(
+                                                    cfg_b,
+                                                    overlap_mode,
+                                                    scope.as_ref(),
+                                                    &compiled_b,
+                                                    &deadline,
+                                                )
+                                            })
+                                            .map_err(in_b)?;
+                                        skipped.extend(skipped_b.into_iter().map(|mut e| {
+                                            e.unit_name = format!("{} (B)", e.unit_name);
+                                            e
+                                        }));
+                                        let highlights = highlights_by_file(
+                                            &a,
+                                            file_count,
+                                            "match-highlight-a-only",
+                                            &UnitStyles::default(),
+                                            &docs,
+                                        )
+                                        .into_iter()
+                                        .zip(highlights_by_file(
+                                            &b,
+                                            file_count,
+                                            "match-highlight-b-only",
+                                            &UnitStyles::default(),
+                                            &RuleDocs::default(),
+                                        ))
+                                        .map(|(a, b)| compare_highlights(a, b))
+                                        .collect();
+                                        Ok((a, highlights, skipped, truncated))
+                                    })
+                                } else {
+                                    let styles = UnitStyles::new(&cfg.lhs);
+                                    let hook = ResultHook::new(&self.settings.result_hook);
+                                    search_resolved(
+                                        cfg,
+                                        overlap_mode,
+                                        scope.as_ref(),
+                                        &compiled,
+                                        &deadline,
+                                    )
+                                    .and_then(
+                                        |(matches, skipped, truncated)| {
+                                            let (matches, highlights) = present_matches(
+                                                matches, file_count, &styles, &docs, hook,
+                                            )?;
+                                            Ok((matches, highlights, skipped, truncated))
+                                        },
+                                    )
+                                };
+                                self.compiled = Some(compiled);
+                                run
+                            }
+                            Err(e) => Err(e),
+                        };
+                        let (matches, highlights, skipped, truncated) = match run {
+                            Ok(v) => v,
+                            Err(e) => {
+                                // preserve current content
+                                self.right_options = Rc::new(editor_options(
+                                    rhs_content,
+                                    self.current_language.clone(),
+                                ));
+                                self.left_options =
+                                    Rc::new(editor_options(lhs_content, "yaml".to_string()));
+                                self.show_run_error(e);
+                                return true;
+                            }
+                        };
+
+                        let timed_out = self.timed_out(&deadline);
+                        self.show_run(FinishedRun {
+                            started,
+                            units,
+                            matches,
+                            highlights,
+                            skipped,
+                            truncated,
+                            timed_out,
+                            rule_count,
+                            source,
+                        });
+                        return true;
+                    }
+                    _ => unreachable!(),
+                }
+                was_error
+            }
+            Msg::StartDrag => {
+                let link = ctx.link().clone();
+                let win = window().unwrap();
+
+                self.mousemove_listener =
+                    Some(EventListener::new(&win, "mousemove", move |event| {
+                        let event = event.dyn_ref::<MouseEvent>().unwrap();
+                        link.send_message(Msg::Drag(event.client_x()));
+                    }));
+
+                let link = ctx.link().clone();
+                self.mouseup_listener = Some(EventListener::new(&win, "mouseup", move |_| {
+                    link.send_message(Msg::StopDrag);
+                }));
+
+                false
+            }
+            Msg::Drag(x) => {
+                self.layout.drag_to(x);
+
+                // Preserve current editor content to prevent clearing during drag
+                let lhs_content = self.lhs_content();
+                let rhs_content = self.rhs_content();
+
+                self.left_options = Rc::new(editor_options(lhs_content, "yaml".to_string()));
+                self.right_options =
+                    Rc::new(editor_options(rhs_content, self.current_language.clone()));
+
+                true
+            }
+            Msg::StopDrag => {
+                self.mousemove_listener = None;
+                self.mouseup_listener = None;
+                false
+            }
+            Msg::SwapPanels => {
+                self.layout = self.layout.swap();
+                true
+            }
+            Msg::Maximize(pane) => {
+                self.layout = self.layout.maximize(pane);
+                true
+            }
+            Msg::RestoreLayout => {
+                if !self.layout.is_maximized() {
+                    return false;
+                }
+                self.layout = self.layout.restore();
+                true
+            }
+            Msg::LanguageChanged(lang) => {
+                self.language_suggestion = None;
+                // before the switch, which changes the files' language
+                let untouched =
+                    crate::presets::untouched(&self.lhs_content(), &self.files_snapshot());
+                self.preset_offer =
+                    (untouched && crate::presets::preset(&lang).is_some()).then(|| lang.clone());
+                self.checkpoint();
+                self.set_language(lang);
+                // the matches were lexed as the old language
+                self.clear_results();
+                if self.settings.auto_run {
+                    ctx.link().send_message(Msg::Run);
+                }
+                self.schedule_session_save(ctx);
+                // the badge follows the language even when the dirty flag doesn't change
+                self.refresh_dirty();
+                true
+            }
+            Msg::ContentChanged => {
+                self.schedule_session_save(ctx);
+                let lhs = self.lhs_content();
+                let problems = validate_lhs(&lhs);
+                self.apply_schema_markers(&problems);
+                let problems_changed = self.rule_problems != problems;
+                self.rule_problems = problems;
+                self.apply_unit_borders(&lhs);
+                let tags = rule_tags(&lhs);
+                let tags_changed = self.rule_tags != tags;
+                self.rule_tags = tags;
+                let check_outdated = self.pattern_check.as_ref().is_some_and(|c| c.lhs != lhs);
+                if check_outdated {
+                    self.pattern_check = None;
+                }
+                // loading the example would replace edits made since it was offered
+                let offer_outdated = self.preset_offer.is_some()
+                    && !crate::presets::untouched(&lhs, &self.files_snapshot());
+                if offer_outdated {
+                    self.preset_offer = None;
+                }
+                // restarted on every change, so a run happens once typing pauses
+                if self.settings.auto_run && self.last_run_key != Some(self.run_key()) {
+                    let link = ctx.link().clone();
+                    self.auto_run_timeout = Some(gloo::timers::callback::Timeout::new(
+                        AUTO_RUN_DELAY_MS,
+                        move || link.send_message(Msg::AutoRun),
+                    ));
+                }
+                self.refresh_dirty()
+                    || tags_changed
+                    || check_outdated
+                    || problems_changed
+                    || offer_outdated
+            }
+            Msg::AutoRun => {
+                self.auto_run_timeout = None;
+                if !self.settings.auto_run || self.last_run_key == Some(self.run_key()) {
+                    return false;
+                }
+                self.update(ctx, Msg::Run)
+            }
+            Msg::ToggleSnippets => {
+                self.snippets_open = !self.snippets_open;
+                true
+            }
+            Msg::SelectSession(index) => {
+                if index == self.sessions.active() {
+                    return false;
+                }
+                if self.diff_mode {
+                    self.error = Some(tr!("sessions-diff-mode"));
+                    return true;
+                }
+                self.switch_session(index);
+                true
+            }
+            Msg::NewSession => {
+                if self.diff_mode {
+                    self.error = Some(tr!("sessions-diff-mode"));
+                    return true;
+                }
+                self.save_session();
+                let leaving = self.sessions.active();
+                let parked = self.park_session();
+                let (lhs, _, _) = PlaygroundConfig::default().to_editor_parts();
+                let session = Session {
+                    name: self.sessions.free_name(),
+                    lhs,
+                    files: PlaygroundConfig::default().editor_files(),
+                    active_file: 0,
+                };
+                if let Err(e) = self.sessions.add(session.clone()) {
+                    self.error = Some(tr!("session-save-failed", error = e));
+                }
+                self.parked[leaving] = Some(parked);
+                self.parked.push(None);
+                self.show_session(session, None);
+                true
+            }
+            Msg::RenameSession(index) => {
+                let Some(name) = self.sessions.iter().nth(index).map(|s| s.name.clone()) else {
+                    return false;
+                };
+                let to = match gloo::dialogs::prompt(&tr!("rename-session-prompt"), Some(&name)) {
+                    Some(to) if !to.trim().is_empty() && to.trim() != name => to.trim().to_string(),
+                    _ => return false,
+                };
+                if let Err(e) = self.sessions.rename(index, to) {
+                    self.error = Some(e);
+                }
+                true
+            }
+            Msg::CloseSession(index) => {
+                if self.diff_mode {
+                    self.error = Some(tr!("sessions-diff-mode"));
+                    return true;
+                }
+                let Some(name) = self.sessions.iter().nth(index).map(|s| s.name.clone()) else {
+                    return false;
+                };
+                if !gloo::dialogs::confirm(&tr!("close-session-confirm", name = name.as_str())) {
+                    return false;
+                }
+                let was_shown = index == self.sessions.active();
+                if !was_shown {
+                    self.save_session();
+                }
+                if let Err(e) = self.sessions.close(index) {
+                    self.error = Some(e);
+                    return true;
+                }
+                self.parked.remove(index);
+                if was_shown {
+                    if let Some(session) = self.sessions.active_session().cloned() {
+                        let parked = self.parked[self.sessions.active()].take();
+                        self.show_session(session, parked);
+                    }
+                }
+                true
+            }
+            Msg::SaveSession => {
+                self.save_session();
+                self.error.is_some()
+            }
+            Msg::ToggleReplaced => {
+                self.replaced_open = !self.replaced_open;
+                true
+            }
+            Msg::RestoreReplaced(index) => {
+                self.replaced_open = false;
+                let Some(entry) = self.replaced.get(index).map(|r| r.entry.clone()) else {
+                    return true;
+                };
+                // restoring replaces too, so what is shown now can be had back the same way
+                self.stash_replaced(tr!("replaced-by-restore"));
+                self.checkpoint();
+                self.show_workspace(entry.lhs, entry.files, entry.active_file);
+                true
+            }
+            Msg::LoadSnippet(name) => {
+                self.snippets_open = false;
+                if self.dirty.get() && !gloo::dialogs::confirm(&tr!("load-snippet-confirm")) {
+                    return true;
+                }
+                let encoded = self.snippets.get(&name).unwrap_or_default().to_string();
+                match PlaygroundConfig::from_encoded(&encoded) {
+                    Ok(cfg) => {
+                        self.error = None;
+                        self.load_config(cfg, tr!("replaced-by-snippet", name = name.as_str()));
+                    }
+                    Err(e) => self.error = Some(tr!("snippet-error", name = name, error = e)),
+                }
+                true
+            }
+            Msg::RenameSnippet(name) => {
+                let to = match gloo::dialogs::prompt(&tr!("rename-snippet-prompt"), Some(&name)) {
+                    Some(to) if !to.trim().is_empty() && to.trim() != name => to.trim().to_string(),
+                    _ => return false,
+                };
+                if let Err(e) = self.snippets.rename(&name, to) {
+                    self.error = Some(e);
+                }
+                true
+            }
+            Msg::DuplicateSnippet(name) => {
+                if let Err(e) = self.snippets.duplicate(&name) {
+                    self.error = Some(e);
+                }
+                true
+            }
+            Msg::DeleteSnippet(name) => {
+                if !gloo::dialogs::confirm(&tr!("delete-snippet-confirm", name = name.as_str())) {
+                    return false;
+                }
+                if let Err(e) = self.snippets.delete(&name) {
+                    self.error = Some(e);
+                }
+                true
+            }
+            Msg::ShowMoreHighlights => {
+                self.highlight_limit += self.settings.max_highlights;
+                self.show_file_highlights();
+                true
+            }
+            Msg::ClearHighlights => {
+                self.apply_highlights(&[]);
+                self.apply_inlay_hints(&[]);
+                false
+            }
+            Msg::FormatPatterns => {
+                let lhs = self.lhs_content();
+                if lhs.lines().any(|l| l.trim_start().starts_with('#'))
+                    && !gloo::dialogs::confirm(&tr!("format-confirm"))
+                {
+                    return false;
+                }
+                match format_lhs(&lhs) {
+                    Ok(formatted) => {
+                        self.error = None;
+                        if formatted != lhs {
+                            self.checkpoint();
+                            set_editor_value(&self.lhs_editor, &formatted, None);
+                            self.left_options =
+                                Rc::new(editor_options(formatted, "yaml".to_string()));
+                        }
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::AddRule => {
+                let lhs = self.lhs_content();
+                let name = unique_name("new_rule", &rule_names(&lhs));
+                match add_rule(&lhs, &name, None) {
+                    Ok((lhs, line)) => self.replace_lhs(lhs, Some(line)),
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::PatternFromSelection => {
+                let selection = match self.rhs_selection() {
+                    Some(v) => v,
+                    None => {
+                        self.error =
+                            Some("select some code in the subject editor first".to_string());
+                        return true;
+                    }
+                };
+                let lhs = self.lhs_content();
+                let name = unique_name("from_selection", &rule_names(&lhs));
+                let pattern = pattern_from_selection(&selection.text);
+                match add_rule(&lhs, &name, Some(&pattern)) {
+                    Ok((lhs, line)) => self.replace_lhs(lhs, Some(line)),
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::ToggleRulesMenu => {
+                self.rules_menu_open = !self.rules_menu_open;
+                true
+            }
+            Msg::DuplicateRule(index) => {
+                self.rules_menu_open = false;
+                let lhs = self.lhs_content();
+                let names = rule_names(&lhs);
+                let base = names.get(index).map(|n| n.as_str()).unwrap_or("rule");
+                let name = unique_name(&format!("{}_copy", base), &names);
+                match duplicate_rule(&lhs, index, &name) {
+                    Ok((lhs, line)) => self.replace_lhs(lhs, Some(line)),
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::ToggleRulePacks => {
+                self.rule_packs_open = !self.rule_packs_open;
+                // a failed fetch is retried on the next open
+                if self.rule_packs_open && !matches!(self.rule_packs, Some(Ok(_))) {
+                    self.rule_packs = None;
+                    let url = self.settings.rule_pack_index.clone();
+                    let link = ctx.link().clone();
+                    yew::platform::spawn_local(async move {
+                        link.send_message(Msg::RulePacksLoaded(fetch_index(&url).await));
+                    });
+                }
+                true
+            }
+            Msg::DeployConfigLoaded(config, fresh_start) => {
+                let config = match config {
+                    Ok(Some(v)) => v,
+                    Ok(None) => return false,
+                    Err(e) => {
+                        self.error = Some(e);
+                        return true;
+                    }
+                };
+                match config.apply_theme() {
+                    Ok(Some(theme)) => set_theme_js(theme),
+                    Ok(None) => {}
+                    Err(e) => self.error = Some(e),
+                }
+                if let Some(settings) = config.settings.clone().filter(|_| !Settings::saved()) {
+                    self.settings = Settings {
+                        locale: self.settings.locale,
+                        ..settings
+                    };
+                    self.highlight_limit = self.settings.max_highlights;
+                }
+                // the user may have started typing while it loaded
+                let preset = config.preset.as_deref();
+                if let Some(preset) = preset.filter(|_| fresh_start && !self.dirty.get()) {
+                    match PlaygroundConfig::from_encoded(preset) {
+                        Ok(cfg) => self.load_config(cfg, tr!("replaced-by-preset")),
+                        Err(e) => self.error = Some(tr!("deploy-preset-invalid", error = e)),
+                    }
+                }
+                self.deploy = config;
+                true
+            }
+            Msg::RulePacksLoaded(packs) => {
+                self.rule_packs = Some(packs);
+                self.rule_packs_open
+            }
+            Msg::PickRulePack(pack) => {
+                self.rule_pack_status = Some(tr!("rule-pack-loading", name = pack.name.as_str()));
+                let link = ctx.link().clone();
+                yew::platform::spawn_local(async move {
+                    let units = fetch_pack(&pack).await;
+                    link.send_message(Msg::RulePackLoaded(pack.name, units));
+                });
+                true
+            }
+            Msg::RulePackLoaded(name, units) => {
+                let merged = units.and_then(|units| merge_rules(&self.lhs_content(), units));
+                match merged {
+                    Ok(merged) => {
+                        self.rule_pack_status = Some(if merged.skipped.is_empty() {
+                            tr!("rule-pack-merged", name = name, count = merged.added)
+                        } else {
+                            tr!(
+                                "rule-pack-merged-skipped",
+                                name = name,
+                                count = merged.added,
+                                skipped = merged.skipped.join(", ")
+                            )
+                        });
+                        if merged.added > 0 {
+                            self.replace_lhs(merged.lhs, merged.line);
+                        }
+                    }
+                    Err(e) => {
+                        self.rule_pack_status = None;
+                        self.error = Some(tr!("rule-pack-error", error = e));
+                    }
+                }
+                true
+            }
+            Msg::ToggleScratchpad => {
+                if self.scratchpad_units.take().is_none() {
+                    match parse_lhs(&self.lhs_content()) {
+                        Ok(units) => self.scratchpad_units = Some(scratchpad_units(&units)),
+                        Err(e) => self.error = Some(e),
+                    }
+                }
+                true
+            }
+            Msg::ApplyTransform(index, capture, regex) => {
+                let lhs = self.lhs_content();
+                if lhs.lines().any(|l| l.trim_start().starts_with('#'))
+                    && !gloo::dialogs::confirm(&tr!("transform-confirm"))
+                {
+                    return false;
+                }
+                match set_transform(&lhs, index, &capture, &regex) {
+                    Ok(lhs) => {
+                        if let Ok(units) = parse_lhs(&lhs) {
+                            self.scratchpad_units = Some(scratchpad_units(&units));
+                        }
+                        self.replace_lhs(lhs, None);
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::SetRuleEnabled(index, enabled) => {
+                match set_enabled(&self.lhs_content(), index, enabled) {
+                    Ok(lhs) => self.replace_lhs(lhs, None),
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::SwitchFile(index) => {
+                if index == self.active_file {
+                    return false;
+                }
+                self.switch_file(index);
+                true
+            }
+            Msg::AddFile => {
+                let name = match gloo::dialogs::prompt(&tr!("new-file-prompt"), None) {
+                    Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                    _ => return false,
+                };
+                if self.files.iter().any(|f| f.name == name) {
+                    self.error = Some(tr!("file-exists", name = name));
+                    return true;
+                }
+                // the new file would take the scanned file's index
+                if self.scanned_file.is_some() {
+                    self.clear_results();
+                }
+                self.sync_active_file();
+                self.files.push(EditorFile {
+                    name,
+                    language: self.current_language.clone(),
+                    content: String::new(),
+                });
+                self.switch_file(self.files.len() - 1);
+                self.refresh_dirty();
+                true
+            }
+            Msg::RenameFile(index) => {
+                // the main file's name is not part of the config
+                if index == 0 {
+                    return false;
+                }
+                let current = self.files[index].name.clone();
+                let name = match gloo::dialogs::prompt(&tr!("rename-file-prompt"), Some(&current)) {
+                    Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                    _ => return false,
+                };
+                if name == current {
+                    return false;
+                }
+                if self.files.iter().any(|f| f.name == name) {
+                    self.error = Some(tr!("file-exists", name = name));
+                    return true;
+                }
+                if let Some(live) = self.live_files.iter_mut().find(|l| l.name == current) {
+                    live.name = name.clone();
+                }
+                self.files[index].name = name;
+                self.refresh_dirty();
+                true
+            }
+            Msg::RemoveFile(index) => {
+                if index == 0
+                    || !gloo::dialogs::confirm(&tr!(
+                        "remove-file-confirm",
+                        name = self.files[index].name.as_str()
+                    ))
+                {
+                    return false;
+                }
+                if index == self.active_file {
+                    self.switch_file(0);
+                } else {
+                    self.sync_active_file();
+                }
+                if self.scanned_file.is_some() {
+                    self.clear_results();
+                }
+                self.live_files
+                    .retain(|live| live.name != self.files[index].name);
+                self.files.remove(index);
+                if index < self.file_highlights.len() {
+                    self.file_highlights.remove(index);
+                }
+                if self.active_file > index {
+                    self.active_file -= 1;
+                }
+                self.refresh_dirty();
+                true
+            }
+            Msg::ShortLinkCreated(result, shortened) => match result {
+                Ok(id) => {
+                    copy_share_link(&format!("s={}", id));
+                    // only now is the state saved somewhere
+                    self.baseline = shortened;
+                    self.refresh_dirty()
+                }
+                Err(e) => {
+                    self.error = Some(tr!("shorten-failed", error = e));
+                    true
+                }
+            },
+            Msg::LinkEncoded(fragment, stats, copy) => {
+                if copy {
+                    copy_share_link(&fragment);
+                }
+                self.link_stats = Some((share_link(&fragment).len(), stats));
+                self.link_audit = audit(&fragment);
+                true
+            }
+            Msg::ShortLinkLoaded(result) => {
+                match result {
+                    Ok(cfg) => self.open_config(cfg),
+                    Err(e) => self.error = Some(tr!("short-link-failed", error = e)),
+                }
+                true
+            }
+            Msg::HashChanged => {
+                let path = url_path();
+                if path.len() <= crate::io::PUBLIC_URL.len() {
+                    return false;
+                }
+                if let Some(id) = short_id_from_url_str(&path) {
+                    if let Err(e) = open_short_link(ctx, id, &self.settings.shortener_url) {
+                        self.error = Some(e);
+                    }
+                    return true;
+                }
+                if is_self_test_url(&path) {
+                    self.self_test_open = true;
+                    return true;
+                }
+                match PlaygroundConfig::from_url_str_with_results(&path) {
+                    Ok((cfg, shared)) => {
+                        self.error = None;
+                        self.open_config(cfg);
+                        // only if it was loaded, not while it waits for "Replace"
+                        if let Some(matches) = shared.filter(|_| self.incoming.is_none()) {
+                            self.show_shared_results(matches);
+                        }
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::OpenLiveFile => {
+                crate::live_file::open(ctx.link().callback(Msg::LiveFileOpened));
+                false
+            }
+            Msg::LiveFileOpened(Err(e)) => {
+                self.error = Some(tr!("live-file-failed", error = e));
+                true
+            }
+            Msg::LiveFileOpened(Ok((live, text))) => {
+                if self.files.iter().any(|f| f.name == live.name) {
+                    // the same file opened again takes over, any other is in the way
+                    if !self.live_files.iter().any(|l| l.name == live.name) {
+                        self.error = Some(tr!("file-exists", name = live.name));
+                        return true;
+                    }
+                    self.live_files.retain(|l| l.name != live.name);
+                    self.set_file_content(&live.name, text);
+                } else {
+                    // the new file would take the scanned file's index
+                    if self.scanned_file.is_some() {
+                        self.clear_results();
+                    }
+                    self.sync_active_file();
+                    let language = guess_language(&text)
+                        .map_or_else(|| self.current_language.clone(), str::to_string);
+                    self.files.push(EditorFile {
+                        name: live.name.clone(),
+                        language,
+                        content: text,
+                    });
+                    self.switch_file(self.files.len() - 1);
+                    self.refresh_dirty();
+                }
+                self.live_files.push(live);
+                true
+            }
+            Msg::ReloadLiveFiles(forced) => {
+                for live in &self.live_files {
+                    let name = live.name.clone();
+                    live.read(
+                        ctx.link().callback(move |result| {
+                            Msg::LiveFileRead(name.clone(), result, forced)
+                        }),
+                    );
+                }
+                false
+            }
+            Msg::LiveFileRead(name, result, forced) => {
+                let Some(index) = self.live_files.iter().position(|l| l.name == name) else {
+                    return false;
+                };
+                match result {
+                    Ok(contents) => {
+                        if !forced && contents.last_modified == self.live_files[index].last_modified
+                        {
+                            return false;
+                        }
+                        self.live_files[index].last_modified = contents.last_modified;
+                        self.set_file_content(&name, contents.text);
+                        ctx.link().send_message(Msg::AutoRun);
+                    }
+                    Err(e) => {
+                        // e.g. the file was deleted, polling would only repeat it
+                        self.live_poll = None;
+                        self.error = Some(tr!(
+                            "live-file-read-failed",
+                            name = name.as_str(),
+                            error = e
+                        ));
+                    }
+                }
+                true
+            }
+            Msg::ToggleLivePolling => {
+                self.live_poll = match self.live_poll.take() {
+                    Some(_) => None,
+                    None => {
+                        let link = ctx.link().clone();
+                        Some(gloo::timers::callback::Interval::new(
+                            LIVE_FILE_POLL_MS,
+                            move || link.send_message(Msg::ReloadLiveFiles(false)),
+                        ))
+                    }
+                };
+                true
+            }
+            Msg::ShareResults => {
+                if self.diff_mode || self.last_run_key != Some(self.run_key()) {
+                    self.error = Some(tr!("share-results-stale"));
+                    return true;
+                }
+                if self.settings.scrub_shared_links {
+                    self.error = Some(tr!("share-results-scrubbed"));
+                    return true;
+                }
+                let cfg = match self.config_with_lhs(&self.lhs_content()) {
+                    Ok(cfg) => cfg,
+                    Err(e) => {
+                        self.show_run_error(e);
+                        return true;
+                    }
+                };
+                let pending = cfg
+                    .pending_link_with_results(self.settings.link_encoding, &self.results.matches);
+                let level = self.settings.compression;
+                let link = ctx.link().clone();
+                yew::platform::spawn_local(async move {
+                    yew::platform::time::sleep(Duration::ZERO).await;
+                    let (fragment, stats) = pending.encode(level);
+                    link.send_message(Msg::LinkEncoded(fragment, stats, true));
+                });
+                false
+            }
+            Msg::CopyStrictLink => {
+                let lhs = self.lhs_content();
+                let cfg = match self.config_with_lhs(&lhs) {
+                    Ok(cfg) => cfg,
+                    Err(e) => {
+                        self.show_run_error(e);
+                        return true;
+                    }
+                };
+                let pending = match self.shared_link(&cfg, &lhs, LinkEncoding::StrictSafe) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        self.error = Some(e);
+                        return true;
+                    }
+                };
+                let level = self.settings.compression;
+                let link = ctx.link().clone();
+                yew::platform::spawn_local(async move {
+                    yew::platform::time::sleep(Duration::ZERO).await;
+                    let (fragment, stats) = pending.encode(level);
+                    link.send_message(Msg::LinkEncoded(fragment, stats, true));
+                });
+                false
+            }
+            Msg::ShowSharedResults(matches) => {
+                self.show_shared_results(matches);
+                true
+            }
+            Msg::SubjectEditorReady => {
+                self.show_file_highlights();
+                self.refresh_inlay_hints();
+                false
+            }
+            Msg::ReplaceWithIncoming => match self.incoming.take() {
+                Some(cfg) => {
+                    self.load_config(cfg, tr!("replaced-by-link"));
+                    true
+                }
+                None => false,
+            },
+            Msg::MergeIncoming => match self.incoming.take() {
+                Some(cfg) => {
+                    match merge_rules(&self.lhs_content(), cfg.lhs) {
+                        Ok(merged) if merged.added > 0 => self.replace_lhs(merged.lhs, merged.line),
+                        Ok(_) => {}
+                        Err(e) => self.error = Some(e),
+                    }
+                    true
+                }
+                None => false,
+            },
+            Msg::DismissIncoming => self.incoming.take().is_some(),
+            Msg::ToggleSettings => {
+                self.settings_open = !self.settings_open;
+                true
+            }
+            Msg::SettingsChanged(settings) => {
+                i18n::set_locale(settings.locale);
+                if let Err(e) = settings.save() {
+                    self.error = Some(e);
+                }
+                if settings.rule_pack_index != self.settings.rule_pack_index {
+                    self.rule_packs = None;
+                }
+                self.settings = settings;
+                true
+            }
+            Msg::SubjectClicked(line, col) => {
+                let here = self.results.at(self.active_file, line, col);
+                let origin = match here.first() {
+                    Some(&i) => i,
+                    None => {
+                        let had_selection = !std::mem::take(&mut self.selected_results).is_empty();
+                        let had_peek = self.peek.take().is_some();
+                        return self.explanation.take().is_some() || had_peek || had_selection;
+                    }
+                };
+                self.selected_results = here;
+                // explained on request, see `Msg::ExplainMatch`
+                self.explanation = None;
+                let captures: Vec<_> = self.results.matches[origin]
+                    .captures
+                    .iter()
+                    .map(|(k, v)| {
+                        let others = self
+                            .results
+                            .references(k, v)
+                            .iter()
+                            .copied()
+                            .filter(|&i| i != origin)
+                            .collect();
+                        (k.clone(), v.clone(), others)
+                    })
+                    .collect();
+                self.peek = Some(CapturePeek { origin, captures });
+                true
+            }
+            Msg::RevealMatch(index) => {
+                let m = self.results.matches[index].clone();
+                if let (true, Some(editor)) = (self.diff_mode, &self.diff_editor) {
+                    diff_mode::reveal(editor, &m);
+                    return true;
+                }
+                self.reveal_in_subject(&m)
+            }
+            Msg::StepMatch(backwards) => {
+                let order = self.results.order();
+                if order.is_empty() {
+                    return false;
+                }
+                let current = self
+                    .selected_results
+                    .first()
+                    .and_then(|&i| self.results.row_of(i));
+                let len = order.len();
+                let row = match (current, backwards) {
+                    (None, false) => 0,
+                    (None, true) => len - 1,
+                    (Some(row), false) => (row + 1) % len,
+                    (Some(row), true) => (row + len - 1) % len,
+                };
+                let index = order[row];
+                self.selected_results = vec![index];
+                self.peek = None;
+                self.explanation = None;
+                ctx.link().send_message(Msg::RevealMatch(index));
+                true
+            }
+            Msg::CursorMoved(line, col) => {
+                let changed = self.cursor != Some((line, col));
+                self.cursor = Some((line, col));
+                changed
+            }
+            Msg::SubjectPasted(text) => {
+                if text.len() < MIN_PASTE_CHARS {
+                    return false;
+                }
+                let suggestion = guess_language(&text)
+                    .filter(|lang| *lang != self.current_language)
+                    .map(str::to_string);
+                let changed = self.language_suggestion != suggestion;
+                self.language_suggestion = suggestion;
+                changed
+            }
+            Msg::WarmUpEngine => {
+                self.warm_up_frame = None;
+                // errors are left for Run to report, with the editors in place
+                let (lhs, files) = &self.baseline;
+                if let Ok(cfg) = config_from(files, lhs) {
+                    self.compiled = cfg.compile(self.settings.skip_invalid_patterns).ok();
+                }
+                self.engine_ready = true;
+                if self.settings.auto_run {
+                    ctx.link().send_message(Msg::AutoRun);
+                }
+                true
+            }
+            Msg::Undo => self.step_history(true),
+            Msg::Redo => self.step_history(false),
+            Msg::SortResults(order) => {
+                if order == self.result_order {
+                    return false;
+                }
+                self.result_order = order;
+                let mut results = (*self.results).clone();
+                results.sort(order);
+                self.results = Rc::new(results);
+                true
+            }
+            Msg::ShowResultsTab(tab) => {
+                let changed = tab != self.results_tab;
+                self.results_tab = tab;
+                changed
+            }
+            Msg::CopyOutputs => {
+                copy_text(&self.results.outputs_text());
+                false
+            }
+            Msg::ExportOutputs => {
+                download(
+                    "lexer-search-outputs.txt",
+                    "text/plain",
+                    self.results.outputs_text().as_str(),
+                );
+                false
+            }
+            Msg::AggregateBy(name) => {
+                self.aggregate_by = Some(name);
+                true
+            }
+            Msg::FilterResults(filter) => {
+                let mut results = (*self.results).clone();
+                results.set_filter(filter);
+                self.results = Rc::new(results);
+                self.results_tab = ResultsTab::Matches;
+                true
+            }
+            Msg::ToggleTag(tag) => {
+                if !self.tag_filter.remove(&tag) {
+                    self.tag_filter.insert(tag);
+                }
+                true
+            }
+            Msg::ClearTagFilter => {
+                let changed = !self.tag_filter.is_empty();
+                self.tag_filter.clear();
+                changed
+            }
+            Msg::AcceptLanguageSuggestion => match self.language_suggestion.take() {
+                Some(lang) => {
+                    self.checkpoint();
+                    self.set_language(lang);
+                    self.refresh_dirty();
+                    true
+                }
+                None => false,
+            },
+            Msg::DismissLanguageSuggestion => self.language_suggestion.take().is_some(),
+            Msg::LoadPreset => {
+                let Some(cfg) = self
+                    .preset_offer
+                    .take()
+                    .and_then(|lang| crate::presets::preset(&lang))
+                else {
+                    return false;
+                };
+                self.checkpoint();
+                let files = cfg.editor_files();
+                let (lhs, _, _) = cfg.to_editor_parts();
+                self.baseline = (lhs.clone(), files.clone());
+                self.show_workspace(lhs, files, 0);
+                true
+            }
+            Msg::DismissPreset => self.preset_offer.take().is_some(),
+            Msg::ClosePeek => self.peek.take().is_some(),
+            Msg::ExplainMatch(index) => {
+                let (Some(source), Some(m)) =
+                    (&mut self.run_source, self.results.matches.get(index))
+                else {
+                    return false;
+                };
+                let explained = self
+                    .results
+                    .subjects()
+                    .get(m.file)
+                    .ok_or_else(|| "the file of this match wasn't part of the run".to_string())
+                    .and_then(|subject| source.explain(m, subject));
+                match explained {
+                    Ok(explanation) => self.explanation = Some(explanation),
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::CloseExplanation => self.explanation.take().is_some(),
+            Msg::ExplainNoMatch(index) => {
+                let lhs = self.lhs_content();
+                let name = rule_list(&lhs)
+                    .into_iter()
+                    .nth(index)
+                    .map(|(name, _)| name)
+                    .unwrap_or_default();
+                let Some(source) = &mut self.run_source else {
+                    return false;
+                };
+                match source.explain_no_match(index, &name, &self.results.subjects()) {
+                    Ok(misses) => {
+                        self.explanation = None;
+                        self.near_misses = Some((name, misses));
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                self.show_file_highlights();
+                true
+            }
+            Msg::RevealNearMiss(index) => {
+                let reached = self
+                    .near_misses
+                    .as_ref()
+                    .and_then(|(_, misses)| misses.get(index)?.reached.clone());
+                match reached {
+                    Some(m) => self.reveal_in_subject(&m),
+                    None => false,
+                }
+            }
+            Msg::CloseNearMisses => {
+                self.near_misses = None;
+                self.show_file_highlights();
+                true
+            }
+            Msg::ToggleCaptureRanges => {
+                self.capture_ranges = !self.capture_ranges;
+                self.show_file_highlights();
+                true
+            }
+            Msg::ToggleInlayHints => {
+                self.inlay_hints = !self.inlay_hints;
+                self.refresh_inlay_hints();
+                true
+            }
+            Msg::ToggleLinkScrolling => {
+                self.link_scrolling = !self.link_scrolling;
+                true
+            }
+            Msg::ResultsScrolled(first, last) => {
+                if !self.link_scrolling {
+                    return false;
+                }
+                let rows = &self.results.order()[first..last];
+                // the file of the middle row, when the rows span several
+                let Some(file) = rows
+                    .get(rows.len() / 2)
+                    .map(|&i| self.results.matches[i].file)
+                    .filter(|&file| file < self.files.len())
+                else {
+                    return false;
+                };
+                let (start, end) = rows
+                    .iter()
+                    .map(|&i| &self.results.matches[i])
+                    .filter(|m| m.file == file)
+                    .fold(((usize::MAX, 0), (0, 0)), |(start, end), m| {
+                        (
+                            start.min((m.start_line, m.start_col)),
+                            end.max((m.end_line, m.end_col)),
+                        )
+                    });
+                let switched = file != self.active_file;
+                if switched {
+                    self.switch_file(file);
+                }
+                if let Some(editor_link) = &*self.rhs_editor.borrow() {
+                    editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                        let js_editor: &JsValue = editor_api.as_ref();
+                        center_range_js(js_editor, start.0, start.1, end.0, end.1);
+                    });
+                }
+                switched
+            }
+            Msg::ExportSnapshot => {
+                if self.summary.as_ref().is_none_or(|s| s.count_only) {
+                    self.error = Some(tr!("snapshot-needs-run"));
+                    return true;
+                }
+                download(
+                    "lexer-search-snapshot.json",
+                    "application/json",
+                    self.current_snapshot().to_json().as_str(),
+                );
+                false
+            }
+            Msg::SnapshotPicked(file) => {
+                let link = ctx.link().clone();
+                self.snapshot_reader =
+                    Some(gloo::file::callbacks::read_as_text(&file, move |res| {
+                        link.send_message(Msg::SnapshotLoaded(res.map_err(|e| e.to_string())))
+                    }));
+                false
+            }
+            Msg::SnapshotLoaded(res) => {
+                self.snapshot_reader = None;
+                match res.and_then(|s| Snapshot::from_json(&s)) {
+                    Ok(golden) => {
+                        self.snapshot_diff =
+                            Some(SnapshotDiff::new(&golden, &self.current_snapshot()));
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::CloseSnapshotDiff => self.snapshot_diff.take().is_some(),
+            Msg::ExportWorkspace => {
+                match crate::workspace::export_zip(
+                    &self.lhs_content(),
+                    &self.files_snapshot(),
+                    self.active_file,
+                ) {
+                    Ok(zip) => {
+                        download(
+                            "lexer-search-workspace.zip",
+                            "application/zip",
+                            zip.as_slice(),
+                        );
+                        self.baseline = (self.lhs_content(), self.files_snapshot());
+                        self.refresh_dirty()
+                    }
+                    Err(e) => {
+                        self.error = Some(tr!("workspace-error", error = e));
+                        true
+                    }
+                }
+            }
+            Msg::WorkspacePicked(file) => {
+                if self.dirty.get() && !gloo::dialogs::confirm(&tr!("load-workspace-confirm")) {
+                    return false;
+                }
+                let link = ctx.link().clone();
+                self.workspace_reader =
+                    Some(gloo::file::callbacks::read_as_bytes(&file, move |res| {
+                        link.send_message(Msg::WorkspaceLoaded(res.map_err(|e| e.to_string())))
+                    }));
+                false
+            }
+            Msg::WorkspaceLoaded(res) => {
+                self.workspace_reader = None;
+                match res.and_then(|bytes| crate::workspace::import_zip(&bytes)) {
+                    Ok((lhs, files, active_file)) => {
+                        self.error = None;
+                        self.stash_replaced(tr!("replaced-by-workspace"));
+                        self.checkpoint();
+                        self.baseline = (lhs.clone(), files.clone());
+                        self.show_workspace(lhs, files, active_file);
+                    }
+                    Err(e) => self.error = Some(tr!("workspace-error", error = e)),
+                }
+                true
+            }
+            Msg::ScanFilePicked(file) => {
+                let lhs = self.lhs_content();
+                let cfg = PlaygroundConfig::from_editor_parts("", &self.current_language, &lhs)
+                    .map(|mut cfg| {
+                        cfg.retain_tagged(&self.active_tags(&lhs));
+                        cfg
+                    });
+                let units = match &cfg {
+                    Ok(cfg) => cfg.lhs.clone(),
+                    Err(_) => Vec::new(),
+                };
+                let scanner = cfg.and_then(|cfg| {
+                    ChunkScanner::new(cfg, self.settings.skip_invalid_patterns, self.files.len())
+                });
+                let scanner = match scanner {
+                    Ok(scanner) => scanner,
+                    Err(e) => {
+                        self.show_run_error(e);
+                        return true;
+                    }
+                };
+                self.clear_results();
+                self.error = None;
+                let mut results = RunResults::new(Vec::new(), &units);
+                results.sort(self.result_order);
+                self.results = Rc::new(results);
+                self.summary = Some(RunSummary::from_matches(&[], BTreeSet::new()));
+                self.scanned_file = Some(file.name());
+                self.scan_progress = (0, file.size());
+                self.results_open = true;
+
+                let cancel = Rc::new(Cell::new(false));
+                self.scan_cancel = Some(cancel.clone());
+                let deadline = self.run_deadline();
+                let link = ctx.link().clone();
+                yew::platform::spawn_local(async move {
+                    let result = scan_file(file, scanner, cancel, deadline, |progress| {
+                        link.send_message(Msg::ScanChunk(progress))
+                    })
+                    .await;
+                    link.send_message(Msg::ScanFinished(result));
+                });
+                true
+            }
+            Msg::ScanChunk(progress) => {
+                // a chunk already on its way when the scan was stopped
+                if self.scan_cancel.is_none() {
+                    return false;
+                }
+                self.scan_progress = (progress.bytes_done, progress.bytes_total);
+                let results = Rc::make_mut(&mut self.results);
+                results.extend(progress.matches);
+                self.summary = Some(RunSummary::from_matches(
+                    &results.matches,
+                    progress.truncated,
+                ));
+                true
+            }
+            Msg::ScanFinished(result) => {
+                if self.scan_cancel.take().is_none() {
+                    return false;
+                }
+                match result {
+                    Ok(true) => {
+                        let timed_out = Some(self.settings.run_timeout_secs);
+                        if let Some(summary) = &mut self.summary {
+                            summary.timed_out = timed_out;
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => self.show_run_error(e),
+                }
+                true
+            }
+            Msg::CancelScan => {
+                self.stop_scan();
+                true
+            }
+            Msg::RunSlice => self.run_slice(ctx),
+            Msg::CancelRun => match self.sliced.take() {
+                Some(state) => {
+                    self.finish_sliced_run(state);
+                    true
+                }
+                None => false,
+            },
+            Msg::StartTutorial => {
+                if self.dirty.get() && !gloo::dialogs::confirm(&tr!("tutorial-confirm")) {
+                    return false;
+                }
+                self.stash_replaced(tr!("replaced-by-tutorial"));
+                self.checkpoint();
+                let lhs = TUTORIAL_LHS.to_string();
+                let files = vec![EditorFile {
+                    name: MAIN_FILE_NAME.to_string(),
+                    language: "rust".to_string(),
+                    content: TUTORIAL_SUBJECT.to_string(),
+                }];
+                self.baseline = (lhs.clone(), files.clone());
+                self.show_workspace(lhs, files, 0);
+                self.tutorial = Some(TutorialStep::Rule);
+                self.tutorial_hint = None;
+                true
+            }
+            Msg::TutorialNext => {
+                let Some(step) = self.tutorial else {
+                    return false;
+                };
+                match step.check(&self.lhs_content(), &self.results) {
+                    Ok(()) => {
+                        self.tutorial = step.next();
+                        self.tutorial_hint = None;
+                    }
+                    Err(hint) => self.tutorial_hint = Some(hint),
+                }
+                true
+            }
+            Msg::CloseTutorial => {
+                self.tutorial = None;
+                self.tutorial_hint = None;
+                true
+            }
+            Msg::ToggleRuleDiff => {
+                self.rule_diff_open = !self.rule_diff_open;
+                true
+            }
+            Msg::ToggleBatchDecode => {
+                self.batch_decode_open = !self.batch_decode_open;
+                true
+            }
+            Msg::CloseSelfTest => {
+                self.self_test_open = false;
+                true
+            }
+            Msg::BatchMerged(lhs, line) => {
+                self.replace_lhs(lhs, line);
+                true
+            }
+            Msg::ToggleDiffMode => {
+                self.diff_mode = !self.diff_mode;
+                self.diff_editor = None;
+                self.diff_counts = None;
+                self.clear_results();
+                if self.diff_mode {
+                    self.diff_start = self.rhs_content();
+                }
+                true
+            }
+            Msg::DiffEditorCreated(editor) => {
+                self.diff_editor = Some(editor);
+                false
+            }
+            Msg::ToggleUsage => {
+                self.usage_open = !self.usage_open;
+                true
+            }
+            Msg::ClearUsage => {
+                if !gloo::dialogs::confirm(&tr!("usage-clear-confirm")) {
+                    return false;
+                }
+                Rc::make_mut(&mut self.usage).clear();
+                true
+            }
+            Msg::TogglePrintView => {
+                // the print view reads `files`, which lag behind the active editor
+                self.sync_active_file();
+                self.print_view_open = !self.print_view_open;
+                true
+            }
+            Msg::FreeMemory => {
+                self.clear_results();
+                // recompiled by the next run
+                self.compiled = None;
+                self.pattern_check = None;
+                self.snapshot_diff = None;
+                self.sync_active_file();
+                for file in &mut self.files {
+                    file.content.shrink_to_fit();
+                }
+                self.undo.shrink_to_fit();
+                self.redo.shrink_to_fit();
+                true
+            }
+            Msg::ClosePatternErrors => !std::mem::take(&mut self.pattern_errors).is_empty(),
+            Msg::RevealLhs(line, col) => {
+                if let Some(editor_link) = &*self.lhs_editor.borrow() {
+                    editor_link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                        let js_editor: &JsValue = editor_api.as_ref();
+                        reveal_range_js(js_editor, line, col, line, col);
+                    });
+                }
+                false
+            }
+            Msg::ToggleResults => {
+                self.results_open = !self.results_open;
+                true
+            }
+            Msg::RunOnSelection => match self.rhs_selection() {
+                Some(selection) => {
+                    self.scope = Some(SelectionScope {
+                        file: self.active_file,
+                        selection,
+                    });
+                    self.update(ctx, Msg::Run)
+                }
+                None => {
+                    self.error = Some(tr!("select-code-first"));
+                    true
+                }
+            },
+            Msg::ToggleCountOnly => {
+                self.count_only = !self.count_only;
+                true
+            }
+            Msg::ToggleCompare => {
+                self.compare_mode = !self.compare_mode;
+                if self.compare_mode && self.lhs_b_content().trim().is_empty() {
+                    // start B as a copy of A, the usual starting point for tightening a rule
+                    let lhs = self.lhs_content();
+                    if let Some(editor) = &*self.lhs_b_editor.borrow() {
+                        editor.with_editor(|e| {
+                            if let Some(model) = e.get_model() {
+                                model.set_value(&lhs);
+                            }
+                        });
+                    }
+                    self.left_b_options = Rc::new(editor_options(lhs, "yaml".to_string()));
+                }
+                true
+            }
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            // the frame callback runs right before the first paint, the timeout after it
+            let link = ctx.link().clone();
+            self.warm_up_frame = Some(gloo::render::request_animation_frame(move |_| {
+                yew::platform::spawn_local(async move {
+                    yew::platform::time::sleep(Duration::ZERO).await;
+                    link.send_message(Msg::WarmUpEngine);
+                });
+            }));
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let total_width = window().unwrap().inner_width().unwrap().as_f64().unwrap() as i32;
+        let pane_style = |pane: Pane| match self.layout.width(pane, total_width) {
+            Some(width) => format!("order:{}; width:{}px;", self.layout.order(pane), width),
+            None => "display:none;".to_string(),
+        };
+        // a hidden pane stays hidden, the flex rules would override its `display:none`
+        let column_pane_style = |pane: Pane| match self.layout.width(pane, total_width) {
+            Some(_) => format!("{} display:flex; flex-direction:column;", pane_style(pane)),
+            None => pane_style(pane),
+        };
+
+        let settings = self.settings.clone();
+        let on_overlap_change = ctx.link().callback(move |e: web_sys::Event| {
+            let select: web_sys::HtmlSelectElement = e.target().unwrap().dyn_into().unwrap();
+            Msg::SettingsChanged(Settings {
+                overlap_mode: OverlapMode::from_id(&select.value()).unwrap_or_default(),
+                ..settings.clone()
+            })
+        });
+
+        let on_content_change = ctx.link().callback(|_| Msg::ContentChanged);
+        let on_lhs_created = {
+            let inner = editor_created_callback(self.lhs_editor.clone(), on_content_change.clone());
+            let on_content_change = on_content_change.clone();
+            Callback::from(move |link: CodeEditorLink| {
+                let docs = serde_wasm_bindgen::to_value(PATTERN_DOCS)
+                    .expect("failed to serialize pattern docs");
+                register_pattern_docs_js(&docs, PATTERN_GUIDE_URL);
+                inner.emit(link);
+                // decorate the initial content
+                on_content_change.emit(());
+            })
+        };
+        let on_rhs_created = {
+            let inner = editor_created_callback(self.rhs_editor.clone(), on_content_change);
+            let on_click = ctx
+                .link()
+                .callback(|(line, col)| Msg::SubjectClicked(line, col));
+            let on_cursor = ctx
+                .link()
+                .callback(|(line, col)| Msg::CursorMoved(line, col));
+            let on_paste = ctx.link().callback(Msg::SubjectPasted);
+            let on_ready = ctx.link().callback(|_| Msg::SubjectEditorReady);
+            Callback::from(move |link: CodeEditorLink| {
+                let on_click = on_click.clone();
+                let on_cursor = on_cursor.clone();
+                let on_paste = on_paste.clone();
+                link.with_editor(|editor_api: &monaco::api::CodeEditor| {
+                    let js_editor: &JsValue = editor_api.as_ref();
+                    let closure = Closure::<dyn FnMut(usize, usize)>::new(move |line, col| {
+                        on_click.emit((line, col))
+                    });
+                    on_mouse_down_js(js_editor, closure.into_js_value());
+                    let closure = Closure::<dyn FnMut(usize, usize)>::new(move |line, col| {
+                        on_cursor.emit((line, col))
+                    });
+                    on_cursor_change_js(js_editor, closure.into_js_value());
+                    let closure =
+                        Closure::<dyn FnMut(String)>::new(move |text| on_paste.emit(text));
+                    on_paste_js(js_editor, closure.into_js_value());
+                });
+                inner.emit(link);
+                on_ready.emit(());
+            })
+        };
+        let on_lhs_b_created = editor_created_callback(self.lhs_b_editor.clone(), Callback::noop());
+
+        html! {
+            <div style="height:100vh; display:flex; flex-direction:column;">
+                { self.view_session_tabs(ctx) }
+                // Header
+                <div style="
+                    height:50px;
+                    background:#222;
+                    color:white;
+                    display:flex;
+                    align-items:center;
+                    padding:0 10px;
+                    gap:10px;
+                ">
+                    <button disabled={!self.engine_ready} aria-busy={(!self.engine_ready).to_string()}
+                        onclick={ctx.link().callback(|_| Msg::Run)}>
+                        { if self.engine_ready { tr!("run") } else { tr!("engine-loading") } }
+                    </button>
+                    <button title={tr!("run-on-selection.title")} disabled={!self.engine_ready}
+                        onclick={ctx.link().callback(|_| Msg::RunOnSelection)}>{ tr!("run-on-selection") }</button>
+
+                    <label title={tr!("count-only.title")}>
+                        <input type="checkbox" checked={self.count_only}
+                            onchange={ctx.link().callback(|_| Msg::ToggleCountOnly)} />
+                        { tr!("count-only") }
+                    </label>
+
+                    <label title={tr!("inline-captures.title")}>
+                        <input type="checkbox" checked={self.inlay_hints}
+                            onchange={ctx.link().callback(|_| Msg::ToggleInlayHints)} />
+                        { tr!("inline-captures") }
+                    </label>
+
+                    <label title={tr!("capture-ranges.title")}>
+                        <input type="checkbox" checked={self.capture_ranges}
+                            onchange={ctx.link().callback(|_| Msg::ToggleCaptureRanges)} />
+                        { tr!("capture-ranges") }
+                    </label>
 
-                    <select onchange={on_language_change}>
-                        <option value="c" selected={self.current_language == "c"}>{"C"}</option>
-                        <option value="cpp" selected={self.current_language == "cpp"}>{"C++"}</option>
-                        <option value="csharp" selected={self.current_language == "csharp"}>{"C#"}</option>
-                        <option value="go" selected={self.current_language == "go"}>{"Go"}</option>
-                        <option value="java" selected={self.current_language == "java"}>{"Java"}</option>
-                        <option value="javascript" selected={self.current_language == "javascript"}>{"JavaScript"}</option>
-                        <option value="kotlin" selected={self.current_language == "kotlin"}>{"Kotlin"}</option>
-                        <option value="python" selected={self.current_language == "python"}>{"Python"}</option>
-                        <option value="rust" selected={self.current_language == "rust"}>{"Rust"}</option>
-                        <option value="typescript" selected={self.current_language == "typescript"}>{"TypeScript"}</option>
+                    <select title={tr!("overlap-select-title")} onchange={on_overlap_change}>
+                        { for OverlapMode::ALL.into_iter().map(|mode| html! {
+                            <option value={mode.id()} selected={self.settings.overlap_mode == mode}>
+                                {mode.label()}
+                            </option>
+                        })}
                     </select>
 
-                    <button onclick={ctx.link().callback(|_| Msg::CopyShareLink)}>{"Copy Share Link"}</button>
+                    <button onclick={ctx.link().callback(|_| Msg::ClearHighlights)}>
+                        { tr!("clear") }
+                    </button>
+
+                    <button title={tr!("undo.title")} disabled={self.undo.is_empty()}
+                        onclick={ctx.link().callback(|_| Msg::Undo)}>{ tr!("undo") }</button>
+                    <button title={tr!("redo.title")} disabled={self.redo.is_empty()}
+                        onclick={ctx.link().callback(|_| Msg::Redo)}>{ tr!("redo") }</button>
+
+                    <button title={tr!("format.title")}
+                        onclick={ctx.link().callback(|_| Msg::FormatPatterns)}>
+                        { tr!("format") }
+                    </button>
+
+                    <LanguagePicker
+                        current={self.current_language.clone()}
+                        offered={self.deploy.languages.clone()}
+                        on_change={ctx.link().callback(Msg::LanguageChanged)}
+                    />
+                    { self.view_lexer_badge() }
+
+                    <button onclick={ctx.link().callback(|_| Msg::CopyShareLink)}>{ tr!("copy-share-link") }</button>
+                    <button title={tr!("share-results.title")}
+                        onclick={ctx.link().callback(|_| Msg::ShareResults)}>{ tr!("share-results") }</button>
+
+                    { if self.settings.shortener_url.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <button title={tr!("shorten.title")}
+                                onclick={ctx.link().callback(|_| Msg::Shorten)}>{ tr!("shorten") }</button>
+                        }
+                    }}
+
+                    <button onclick={ctx.link().callback(|_| Msg::SaveSnippetAs)}>{ tr!("save-as") }</button>
+
+                    <div style="position:relative;">
+                        <button onclick={ctx.link().callback(|_| Msg::ToggleSnippets)}>{ tr!("my-snippets") }</button>
+                        { if self.snippets_open { self.view_snippets_menu(ctx) } else { html! {} } }
+                    </div>
+
+                    { if self.replaced.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <div style="position:relative;">
+                                <button title={tr!("recently-replaced.title")}
+                                    onclick={ctx.link().callback(|_| Msg::ToggleReplaced)}>{ tr!("recently-replaced") }</button>
+                                { if self.replaced_open { self.view_replaced_menu(ctx) } else { html! {} } }
+                            </div>
+                        }
+                    }}
+
+                    <label title={tr!("ab-compare.title")}>
+                        <input type="checkbox" checked={self.compare_mode}
+                            onchange={ctx.link().callback(|_| Msg::ToggleCompare)} />
+                        { tr!("ab-compare") }
+                    </label>
+
+                    <label title={tr!("diff-mode.title")}>
+                        <input type="checkbox" checked={self.diff_mode}
+                            onchange={ctx.link().callback(|_| Msg::ToggleDiffMode)} />
+                        { tr!("diff-mode") }
+                    </label>
+
+                    <button title={tr!("swap.title")}
+                        onclick={ctx.link().callback(|_| Msg::SwapPanels)}>{ tr!("swap") }</button>
+
+                    { if self.layout.is_maximized() {
+                        html! {
+                            <button title={tr!("restore.title")}
+                                onclick={ctx.link().callback(|_| Msg::RestoreLayout)}>{ tr!("restore") }</button>
+                        }
+                    } else {
+                        html! {
+                            <>
+                                <button title={tr!("maximize-patterns.title")}
+                                    onclick={ctx.link().callback(|_| Msg::Maximize(Pane::Patterns))}>{ tr!("maximize-patterns") }</button>
+                                <button title={tr!("maximize-subject.title")}
+                                    onclick={ctx.link().callback(|_| Msg::Maximize(Pane::Subject))}>{ tr!("maximize-subject") }</button>
+                            </>
+                        }
+                    }}
 
                     <button onclick={
                         |_| {
                             if let Some(win) = web_sys::window() {
-                                let _ = win.open_with_url_and_target(
-                                    "https://github.com/thescanner42/LexerSearch/blob/main/lexer-search-lib/PATTERN-GUIDE.md",
-                                    "_blank",
-                                );
+                                let _ = win.open_with_url_and_target(PATTERN_GUIDE_URL, "_blank");
                             }
                         }
-                    }>{"Docs"}</button>
+                    }>{ tr!("docs") }</button>
+                    <button title={tr!("tutorial.title")}
+                        onclick={ctx.link().callback(|_| Msg::StartTutorial)}>{ tr!("tutorial") }</button>
+
+                    { if self.dirty.get() {
+                        html! {
+                            <span title={tr!("modified-title")} style="color:#e8c547;">{"●"}</span>
+                        }
+                    } else {
+                        html! {}
+                    }}
 
                     <span style="margin-left:auto;">{format!("LexerSearch v{}", env!("CARGO_PKG_VERSION"))}</span>
+
+                    <div style="position:relative;">
+                        <button title={tr!("settings-title")} onclick={ctx.link().callback(|_| Msg::ToggleSettings)}>{"⚙"}</button>
+                        { if self.settings_open {
+                            html! {
+                                <SettingsPanel
+                                    settings={self.settings.clone()}
+                                    on_change={ctx.link().callback(Msg::SettingsChanged)}
+                                />
+                            }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
                 </div>
 
                 { self.error.as_ref().map(|err| html! {
-                    <div style="
+                    <div role="alert" aria-live="assertive" style="
                         background:#5a1a1a;
                         color:#ffb3b3;
                         padding:8px;
                         font-family:monospace;
+                        white-space:pre-wrap;
                     ">
-                        { format!("Error: {}", err) }
+                        { tr!("error-banner", error = err.as_str()) }
                     </div>
                 })}
 
+                { self.view_incoming(ctx) }
+                { self.view_shared_results(ctx) }
+                { self.view_pattern_errors(ctx) }
+                { self.view_snapshot_diff(ctx) }
+
                 // Editors
                 <div style="flex:1; display:flex;">
-                    <div style={format!("width:{}px;", self.left_width)}>
-                        <StableEditor options={self.left_options.clone()}
-                            on_editor_created={Some(Callback::from(move |link: CodeEditorLink| {
-                                *lhs_editor_clone_clone.borrow_mut() = Some(link);
-                            }))} />
+                    <div style={column_pane_style(Pane::Patterns)}>
+                        { self.view_rules_toolbar(ctx) }
+                        { self.view_tag_filter(ctx) }
+                        { self.view_rule_problems(ctx) }
+                        { if self.compare_mode {
+                            html! { <div style="background:#333; color:#8fd18f; padding:2px 6px;">{ tr!("pattern-set-a") }</div> }
+                        } else {
+                            html! {}
+                        }}
+                        <div style="flex:1; min-height:0;">
+                            <StableEditor options={self.left_options.clone()}
+                                on_editor_created={Some(on_lhs_created)} />
+                        </div>
+                        <div style={if self.compare_mode { "flex:1; min-height:0; display:flex; flex-direction:column;" } else { "display:none;" }}>
+                            <div style="background:#333; color:#f08c8c; padding:2px 6px;">{ tr!("pattern-set-b") }</div>
+                            <div style="flex:1; min-height:0;">
+                                <StableEditor options={self.left_b_options.clone()}
+                                    on_editor_created={Some(on_lhs_b_created)} />
+                            </div>
+                        </div>
                     </div>
 
-                    <div style="width:6px; cursor:col-resize; background:#444;"
-                        onmousedown={ctx.link().callback(|_| Msg::StartDrag)} />
+                    { if self.layout.is_maximized() {
+                        html! {}
+                    } else {
+                        let first_width = self.layout.first_width();
+                        html! {
+                            <div style={format!("order:1; width:{}px; cursor:col-resize; background:#444;", SPLITTER_WIDTH)}
+                                role="separator"
+                                aria-orientation="vertical"
+                                aria-label={tr!("splitter-label")}
+                                aria-valuenow={first_width.to_string()}
+                                tabindex="0"
+                                onmousedown={ctx.link().callback(|_| Msg::StartDrag)}
+                                onkeydown={ctx.link().batch_callback(move |e: KeyboardEvent| match e.key().as_str() {
+                                    "ArrowLeft" => Some(Msg::Drag(first_width - KEYBOARD_STEP)),
+                                    "ArrowRight" => Some(Msg::Drag(first_width + KEYBOARD_STEP)),
+                                    _ => None,
+                                })} />
+                        }
+                    }}
 
-                    <div style={format!("width:{}px;", right_width)}>
-                        <StableEditor
-                            options={self.right_options.clone()}
-                            on_editor_created={Some(Callback::from(move |link: CodeEditorLink| {
-                                *rhs_editor_clone_clone.borrow_mut() = Some(link);
-                            }))}
-                        />
+                    <div style={column_pane_style(Pane::Subject)}>
+                        { self.view_file_tabs(ctx) }
+                        { self.view_highlight_cap(ctx) }
+                        { self.view_diff_summary() }
+                        <div style="flex:1; min-height:0; position:relative;">
+                            // kept mounted, so the subject survives a trip through diff mode
+                            <div class="full-height" style={if self.diff_mode { "display:none;" } else { "" }}>
+                                <StableEditor
+                                    options={self.right_options.clone()}
+                                    on_editor_created={Some(on_rhs_created)}
+                                />
+                            </div>
+                            { if self.diff_mode {
+                                html! {
+                                    <DiffSubjectEditor
+                                        original={self.diff_start.clone()}
+                                        modified={self.diff_start.clone()}
+                                        language={self.current_language.clone()}
+                                        on_created={ctx.link().callback(Msg::DiffEditorCreated)}
+                                    />
+                                }
+                            } else {
+                                html! {}
+                            }}
+                            { self.view_capture_peek(ctx) }
+                            { self.view_explanation(ctx) }
+                            { self.view_near_misses(ctx) }
+                            { self.view_language_suggestion(ctx) }
+                            { self.view_preset_offer(ctx) }
+                        </div>
                     </div>
                 </div>
+
+                { if self.results_open {
+                    html! {
+                        <div style="height:200px; flex-shrink:0; display:flex; background:#1e1e1e; color:#ccc; border-top:1px solid #444;">
+                            { self.view_rule_legend(ctx) }
+                            <div style="flex:1; min-width:0; display:flex; flex-direction:column;">
+                                <div style="display:flex; gap:8px; align-items:center; padding:2px 8px; font-size:12px; border-bottom:1px solid #333;">
+                                    <div role="tablist" style="display:flex; gap:2px;">
+                                        { for ResultsTab::ALL.into_iter().map(|tab| html! {
+                                            <button role="tab" aria-selected={(tab == self.results_tab).to_string()}
+                                                style={if tab == self.results_tab { "background:#094771; color:white;" } else { "" }}
+                                                onclick={ctx.link().callback(move |_| Msg::ShowResultsTab(tab))}>
+                                                { tab.label() }
+                                            </button>
+                                        })}
+                                    </div>
+                                    { if self.results_tab == ResultsTab::Matches { html! {
+                                    <label>
+                                        { tr!("sort-results") }
+                                        <select style="margin-left:4px;"
+                                            onchange={ctx.link().batch_callback(|e: web_sys::Event| {
+                                                let select: web_sys::HtmlSelectElement = e.target().unwrap().dyn_into().unwrap();
+                                                ResultOrder::from_id(&select.value()).map(Msg::SortResults)
+                                            })}>
+                                            { for ResultOrder::ALL.into_iter().map(|order| html! {
+                                                <option value={order.id()} selected={order == self.result_order}>
+                                                    { order.label() }
+                                                </option>
+                                            })}
+                                        </select>
+                                    </label>
+                                    } } else { html! {} } }
+                                    { if self.results_tab == ResultsTab::Matches { html! {
+                                    <label title={tr!("link-scrolling.title")}>
+                                        <input type="checkbox" checked={self.link_scrolling}
+                                            onchange={ctx.link().callback(|_| Msg::ToggleLinkScrolling)} />
+                                        { tr!("link-scrolling") }
+                                    </label>
+                                    } } else { html! {} } }
+                                    { self.results.filter().map(|(name, value)| html! {
+                                        <span style="padding:0 6px; border-radius:6px; background:#3a3d41;">
+                                            { tr!("results-filter", name = name.as_str(), value = value.as_str()) }
+                                            <span style="margin-left:4px; cursor:pointer;" title={tr!("clear-results-filter")}
+                                                onclick={ctx.link().callback(|_| Msg::FilterResults(None))}>{"×"}</span>
+                                        </span>
+                                    }) }
+                                    { if self.results.has_outputs() {
+                                        html! {
+                                            <span style="margin-left:auto; display:flex; gap:4px;">
+                                                <button title={tr!("copy-outputs.title")}
+                                                    onclick={ctx.link().callback(|_| Msg::CopyOutputs)}>{ tr!("copy-outputs") }</button>
+                                                <button title={tr!("export-outputs.title")}
+                                                    onclick={ctx.link().callback(|_| Msg::ExportOutputs)}>{ tr!("export-outputs") }</button>
+                                            </span>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }}
+                                </div>
+                                <div style="flex:1; min-height:0;">
+                                { match self.results_tab {
+                                    ResultsTab::Matches => html! {
+                                        <ResultsPanel
+                                            results={self.results.clone()}
+                                            files={self.result_files()}
+                                            selected={self.selected_results.clone()}
+                                            context_lines={self.settings.context_lines}
+                                            on_select={self.on_reveal_match.clone()}
+                                            on_visible={self.link_scrolling.then(|| self.on_results_visible.clone())}
+                                        />
+                                    },
+                                    ResultsTab::Aggregate => self.view_aggregate(ctx),
+                                } }
+                                </div>
+                            </div>
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }}
+
+                { self.tutorial.map(|step| html! {
+                    <TutorialPanel
+                        step={step}
+                        hint={self.tutorial_hint.clone()}
+                        on_next={ctx.link().callback(|_| Msg::TutorialNext)}
+                        on_close={ctx.link().callback(|_| Msg::CloseTutorial)}
+                    />
+                }) }
+
+                { if self.rule_diff_open {
+                    html! {
+                        <RuleDiffDialog
+                            current_lhs={self.lhs_content()}
+                            on_close={ctx.link().callback(|_| Msg::ToggleRuleDiff)}
+                        />
+                    }
+                } else {
+                    html! {}
+                }}
+
+                { if self.batch_decode_open {
+                    html! {
+                        <BatchDecodeDialog
+                            current_lhs={self.lhs_content()}
+                            on_merge={ctx.link().callback(|(lhs, line)| Msg::BatchMerged(lhs, line))}
+                            on_close={ctx.link().callback(|_| Msg::ToggleBatchDecode)}
+                        />
+                    }
+                } else {
+                    html! {}
+                }}
+
+                { if self.self_test_open {
+                    html! {
+                        <SelfTestDialog on_close={ctx.link().callback(|_| Msg::CloseSelfTest)} />
+                    }
+                } else {
+                    html! {}
+                }}
+
+                { if self.usage_open {
+                    html! {
+                        <UsagePanel
+                            log={self.usage.clone()}
+                            on_clear={ctx.link().callback(|_| Msg::ClearUsage)}
+                            on_close={ctx.link().callback(|_| Msg::ToggleUsage)}
+                        />
+                    }
+                } else {
+                    html! {}
+                }}
+
+                { if self.print_view_open {
+                    html! {
+                        <PrintView
+                            files={self.files.clone()}
+                            names={self.result_files()}
+                            results={self.results.clone()}
+                            on_close={ctx.link().callback(|_| Msg::TogglePrintView)}
+                        />
+                    }
+                } else {
+                    html! {}
+                }}
+
+                <div class="sr-only" role="status" aria-live="polite">
+                    { self.summary.as_ref().map(|s| tr!("matches-found", count = s.total())) }
+                </div>
+
+                // Status bar
+                <div style="
+                    height:22px;
+                    background:#007acc;
+                    color:white;
+                    display:flex;
+                    align-items:center;
+                    padding:0 10px;
+                    gap:16px;
+                    font-size:12px;
+                    white-space:nowrap;
+                    overflow:hidden;
+                ">
+                    <span style="cursor:pointer;" title={tr!("results-toggle.title")}
+                        onclick={ctx.link().callback(|_| Msg::ToggleResults)}>
+                        { format!("{} {}", if self.results_open { "▾" } else { "▸" }, tr!("results-toggle", count = self.results.matches.len())) }
+                    </span>
+                    { self.summary.as_ref().map(|s| html! { <span>{ s.describe() }</span> }) }
+                    { self.view_cursor_status() }
+                    <span title={tr!("memory-usage.title", total = megabytes(memory_size()))}>
+                        { tr!("memory-usage", used = megabytes(heap_in_use())) }
+                    </span>
+                    <span style="cursor:pointer;" title={tr!("free-memory.title")}
+                        onclick={ctx.link().callback(|_| Msg::FreeMemory)}>
+                        { tr!("free-memory") }
+                    </span>
+                    { self.link_stats.map(|(url_len, stats)| html! {
+                        <span style={format!("cursor:pointer; {}", if url_len > LONG_LINK_LEN { "color:#ffd27f;" } else { "" })}
+                            title={tr!(
+                                "link-stats.title",
+                                raw = stats.raw_len,
+                                compressed = stats.compressed_len,
+                                ratio = format!("{:.1}", stats.compression_ratio()),
+                                ms = format!("{:.0}", stats.encode_ms),
+                            )}
+                            onclick={ctx.link().callback(|_| Msg::CopyShareLink)}>
+                            { tr!("link-stats", chars = url_len, ratio = format!("{:.1}", stats.compression_ratio())) }
+                        </span>
+                    }) }
+                    { if self.link_audit.is_empty() {
+                        html! {}
+                    } else {
+                        let findings: Vec<String> = self
+                            .link_audit
+                            .iter()
+                            .map(|f| format!("{}: {}", f.rule.label(), f.chars))
+                            .collect();
+                        html! {
+                            <span style="cursor:pointer; color:#ffd27f;"
+                                title={tr!("link-audit.title", findings = findings.join("\n"))}
+                                onclick={ctx.link().callback(|_| Msg::CopyStrictLink)}>
+                                { tr!("link-audit") }
+                            </span>
+                        }
+                    }}
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("usage.title")}
+                        onclick={ctx.link().callback(|_| Msg::ToggleUsage)}>
+                        { tr!("usage") }
+                    </span>
+                    <span style="cursor:pointer;" title={tr!("print-view.title")}
+                        onclick={ctx.link().callback(|_| Msg::TogglePrintView)}>
+                        { tr!("print-view") }
+                    </span>
+                    <span style="cursor:pointer;" title={tr!("export-snapshot.title")}
+                        onclick={ctx.link().callback(|_| Msg::ExportSnapshot)}>
+                        { tr!("export-snapshot") }
+                    </span>
+                    <label style="cursor:pointer;" title={tr!("compare-snapshot.title")}>
+                        { tr!("compare-snapshot") }
+                        <input type="file" accept=".json,application/json" style="display:none;"
+                            onchange={ctx.link().batch_callback(|e: web_sys::Event| {
+                                let input: web_sys::HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+                                let file = input.files().and_then(|files| files.get(0));
+                                input.set_value("");
+                                file.map(|f| Msg::SnapshotPicked(gloo::file::File::from(f)))
+                            })} />
+                    </label>
+                    <span style="cursor:pointer;" title={tr!("export-workspace.title")}
+                        onclick={ctx.link().callback(|_| Msg::ExportWorkspace)}>
+                        { tr!("export-workspace") }
+                    </span>
+                    { self.sliced.as_ref().map(|state| {
+                        let (done, total) = (state.run.tokens_done, state.run.tokens_total);
+                        html! {
+                            <span style="cursor:pointer;" title={tr!("run-progress.title")}
+                                onclick={ctx.link().callback(|_| Msg::CancelRun)}>
+                                { tr!("run-progress", percent = (done * 100).checked_div(total).unwrap_or(100)) }
+                            </span>
+                        }
+                    }) }
+                    { if self.scan_cancel.is_some() {
+                        let (done, total) = self.scan_progress;
+                        html! {
+                            <span style="cursor:pointer;" title={tr!("cancel-scan.title")}
+                                onclick={ctx.link().callback(|_| Msg::CancelScan)}>
+                                { tr!("scan-progress", percent = (done * 100).checked_div(total).unwrap_or(100)) }
+                            </span>
+                        }
+                    } else {
+                        html! {
+                            <label style="cursor:pointer;" title={tr!("scan-file.title")}>
+                                { tr!("scan-file") }
+                                <input type="file" style="display:none;"
+                                    onchange={ctx.link().batch_callback(|e: web_sys::Event| {
+                                        let input: web_sys::HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+                                        let file = input.files().and_then(|files| files.get(0));
+                                        input.set_value("");
+                                        file.map(|f| Msg::ScanFilePicked(gloo::file::File::from(f)))
+                                    })} />
+                            </label>
+                        }
+                    }}
+                    <label style="cursor:pointer;" title={tr!("import-workspace.title")}>
+                        { tr!("import-workspace") }
+                        <input type="file" accept=".zip,application/zip" style="display:none;"
+                            onchange={ctx.link().batch_callback(|e: web_sys::Event| {
+                                let input: web_sys::HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+                                let file = input.files().and_then(|files| files.get(0));
+                                input.set_value("");
+                                file.map(|f| Msg::WorkspacePicked(gloo::file::File::from(f)))
+                            })} />
+                    </label>
+                </div>
             </div>
         }
     }
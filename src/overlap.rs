@@ -0,0 +1,109 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{api::SearchMatch, i18n::tr};
+
+/// what to do when several matches cover the same text
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum OverlapMode {
+    #[default]
+    ShowAll,
+    LongestMatch,
+    FirstRule,
+}
+
+impl OverlapMode {
+    pub const ALL: [OverlapMode; 3] = [
+        OverlapMode::ShowAll,
+        OverlapMode::LongestMatch,
+        OverlapMode::FirstRule,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            OverlapMode::ShowAll => "all",
+            OverlapMode::LongestMatch => "longest",
+            OverlapMode::FirstRule => "first-rule",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("overlap-{}", self.id()))
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
+    }
+}
+
+/// drop matches overlapping a higher priority one, keeping the original order.
+/// `rule_order` lists the unit names in config order, for `FirstRule`
+pub fn resolve(
+    matches: Vec<SearchMatch>,
+    mode: OverlapMode,
+    rule_order: &[String],
+) -> Vec<SearchMatch> {
+    if mode == OverlapMode::ShowAll {
+        return matches;
+    }
+
+    let mut rank: HashMap<&str, usize> = HashMap::new();
+    for (i, name) in rule_order.iter().enumerate() {
+        rank.entry(name.as_str()).or_insert(i);
+    }
+    // spans are only known as line/column pairs, so length compares lines first
+    let length = |m: &SearchMatch| {
+        (
+            m.end_line - m.start_line,
+            m.end_col as isize - m.start_col as isize,
+        )
+    };
+
+    let mut by_priority: Vec<usize> = (0..matches.len()).collect();
+    match mode {
+        OverlapMode::ShowAll => unreachable!(),
+        OverlapMode::LongestMatch => {
+            by_priority.sort_by_key(|&i| std::cmp::Reverse(length(&matches[i])))
+        }
+        OverlapMode::FirstRule => by_priority.sort_by_key(|&i| {
+            let m = &matches[i];
+            (
+                rank.get(m.name.as_str()).copied().unwrap_or(usize::MAX),
+                std::cmp::Reverse(length(m)),
+            )
+        }),
+    }
+
+    // kept ranges never overlap, so checking the neighbours by start position is enough
+    let mut kept_ranges: BTreeMap<(usize, usize, usize), (usize, usize)> = BTreeMap::new();
+    let mut keep = vec![false; matches.len()];
+    for i in by_priority {
+        let m = &matches[i];
+        let start = (m.file, m.start_line, m.start_col);
+        let end = (m.end_line, m.end_col);
+
+        let overlaps_before =
+            kept_ranges
+                .range(..=start)
+                .next_back()
+                .is_some_and(|(&(file, ..), &prev_end)| {
+                    file == m.file && prev_end > (m.start_line, m.start_col)
+                });
+        let overlaps_after = kept_ranges
+            .range(start..)
+            .next()
+            .is_some_and(|(&(file, line, col), _)| file == m.file && (line, col) < end);
+        if overlaps_before || overlaps_after {
+            continue;
+        }
+        kept_ranges.insert(start, end);
+        keep[i] = true;
+    }
+
+    matches
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(m, keep)| keep.then_some(m))
+        .collect()
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+/// documentation of one pattern language construct, shown on hover in the lhs editor and
+/// offered as a snippet
+#[derive(Serialize)]
+pub struct PatternDoc {
+    /// short form shown in completions, e.g. "$_X"
+    pub label: &'static str,
+    /// JS regex matching the construct under the cursor
+    pub token_regex: &'static str,
+    /// monaco snippet syntax
+    pub snippet: &'static str,
+    /// markdown
+    pub doc: &'static str,
+}
+
+pub const PATTERN_DOCS: &[PatternDoc] = &[
+    PatternDoc {
+        label: "$_X",
+        token_regex: r"\$[A-Za-z_][A-Za-z0-9_]*",
+        snippet: "\\$${1:_X}",
+        doc: "**Metavariable.** Matches a token and captures it under the given name. \
+              The captured value shows up in the match's captures and can be used by \
+              `out` and `transform`.",
+    },
+    PatternDoc {
+        label: "&_X",
+        token_regex: r"&[A-Za-z_][A-Za-z0-9_]*",
+        snippet: "&${1:_X}",
+        doc: "**Back-reference.** Matches only the same value that `$_X` captured earlier \
+              in the pattern.",
+    },
+    PatternDoc {
+        label: "...",
+        token_regex: r"\.\.\.",
+        snippet: "...",
+        doc: "**Ellipsis.** Matches any sequence of tokens, including none, e.g. \
+              `call(... $_ARG ...)` finds `$_ARG` anywhere in the arguments.",
+    },
+    PatternDoc {
+        label: "\\$",
+        token_regex: r"\\[$&.\\]",
+        snippet: "\\\\\\$",
+        doc: "**Escape.** A backslash makes the next character literal, so `\\$`, `\\&` and \
+              `\\...` match the characters themselves instead of starting a metavariable, \
+              back-reference or ellipsis.",
+    },
+];
+
+/// where the complete pattern language is documented
+pub const PATTERN_GUIDE_URL: &str =
+    "https://github.com/thescanner42/LexerSearch/blob/main/lexer-search-lib/PATTERN-GUIDE.md";
@@ -0,0 +1,63 @@
+//! the engine reports 1-based lines and byte columns, while Monaco counts columns in UTF-16
+//! code units. they agree on ASCII, tabs included since both count a tab as one column, so
+//! only the lines with other characters are kept for converting
+
+use std::collections::HashMap;
+
+use crate::api::SearchMatch;
+
+/// the non-ASCII lines of a subject, by 1-based line number
+#[derive(Default)]
+pub struct ColumnMap {
+    lines: HashMap<usize, String>,
+}
+
+impl ColumnMap {
+    pub fn new(text: &str) -> Self {
+        let lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_ascii())
+            .map(|(i, line)| (i + 1, line.to_string()))
+            .collect();
+        Self { lines }
+    }
+
+    /// the UTF-16 column of byte column `col` on `line`
+    pub fn to_utf16(&self, line: usize, col: usize) -> usize {
+        match self.lines.get(&line) {
+            Some(text) => utf16_col(text, col),
+            None => col,
+        }
+    }
+
+    /// convert the columns of a match found in this subject
+    pub fn convert(&self, m: &mut SearchMatch) {
+        m.start_col = self.to_utf16(m.start_line, m.start_col);
+        m.end_col = self.to_utf16(m.end_line, m.end_col);
+    }
+}
+
+/// 1-based UTF-16 column of the 1-based byte column `col` in `line`. a column inside a
+/// multi-byte character maps to that character
+pub fn utf16_col(line: &str, col: usize) -> usize {
+    let mut end = (col.max(1) - 1).min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line[..end].encode_utf16().count() + 1
+}
+
+/// 0-based char index of the 1-based UTF-16 column `col` in `line`, for slicing by chars.
+/// columns past the end give the char count
+pub fn char_index(line: &str, col: usize) -> usize {
+    let target = col.saturating_sub(1);
+    let mut units = 0;
+    for (i, c) in line.chars().enumerate() {
+        if units >= target {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    line.chars().count()
+}
@@ -0,0 +1,97 @@
+//! an example subject and rule per language, offered when the language of an untouched
+//! session is switched, so a fresh Python subject doesn't sit under the Rust example
+
+use crate::io::{EditorFile, MatchingUnit, PlaygroundConfig};
+
+struct Example {
+    /// monaco id
+    language: &'static str,
+    name: &'static str,
+    pattern: &'static str,
+    subject: &'static str,
+}
+
+/// rust has the default config
+const EXAMPLES: &[Example] = &[
+    Example {
+        language: "python",
+        name: "os-system",
+        pattern: "os.system(... $CMD ...)",
+        subject: "# click the \"Docs\" button above!\nimport os\n\nos.system(\"echo \" + name)\n",
+    },
+    Example {
+        language: "javascript",
+        name: "inner-html",
+        pattern: "$EL.innerHTML = $VALUE;",
+        subject: "// click the \"Docs\" button above!\nconst el = document.getElementById(\"out\");\nel.innerHTML = name;\n",
+    },
+    Example {
+        language: "typescript",
+        name: "inner-html",
+        pattern: "$EL.innerHTML = $VALUE;",
+        subject: "// click the \"Docs\" button above!\nconst el: HTMLElement = document.getElementById(\"out\")!;\nel.innerHTML = name;\n",
+    },
+    Example {
+        language: "java",
+        name: "runtime-exec",
+        pattern: "Runtime.getRuntime().exec(... $CMD ...)",
+        subject: "// click the \"Docs\" button above!\nRuntime.getRuntime().exec(\"echo \" + name);\n",
+    },
+    Example {
+        language: "kotlin",
+        name: "runtime-exec",
+        pattern: "Runtime.getRuntime().exec(... $CMD ...)",
+        subject: "// click the \"Docs\" button above!\nval process = Runtime.getRuntime().exec(\"echo \" + name)\n",
+    },
+    Example {
+        language: "csharp",
+        name: "process-start",
+        pattern: "Process.Start(... $ARGS ...)",
+        subject: "// click the \"Docs\" button above!\nProcess.Start(\"cmd.exe\", \"/c echo \" + name);\n",
+    },
+    Example {
+        language: "cpp",
+        name: "gets",
+        pattern: "gets($BUF)",
+        subject: "// click the \"Docs\" button above!\nchar buf[16];\ngets(buf);\n",
+    },
+    Example {
+        language: "go",
+        name: "exec-command",
+        pattern: "exec.Command(... $ARGS ...)",
+        subject: "// click the \"Docs\" button above!\nexec.Command(\"sh\", \"-c\", \"echo \" + name).Run()\n",
+    },
+];
+
+/// the example config for the monaco language `id`
+pub fn preset(id: &str) -> Option<PlaygroundConfig> {
+    if id == "rust" {
+        return Some(PlaygroundConfig::default());
+    }
+    let example = EXAMPLES.iter().find(|e| e.language == id)?;
+    Some(PlaygroundConfig {
+        subject: example.subject.to_string(),
+        language: serde_yml::from_str(id).ok()?,
+        lhs: vec![MatchingUnit {
+            patterns: vec![example.pattern.to_string()],
+            name: example.name.to_string(),
+            ..Default::default()
+        }],
+        files: Vec::new(),
+    })
+}
+
+/// whether `lhs` and `files` are the default config or one of the examples, so loading
+/// another loses nothing. the language isn't compared, it was just switched
+pub fn untouched(lhs: &str, files: &[EditorFile]) -> bool {
+    let [file] = files else {
+        return false;
+    };
+    std::iter::once("rust")
+        .chain(EXAMPLES.iter().map(|e| e.language))
+        .filter_map(preset)
+        .any(|cfg| {
+            let (preset_lhs, subject, _) = cfg.to_editor_parts();
+            preset_lhs == lhs && subject == file.content
+        })
+}
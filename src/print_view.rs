@@ -0,0 +1,236 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use yew::{Callback, Component, Context, Html, MouseEvent, Properties, html};
+
+use crate::{i18n::tr, io::EditorFile, positions::char_index, results::RunResults};
+
+/// lines of a subject printed before the rest is left out
+const MAX_PRINT_LINES: usize = 5000;
+
+#[derive(Properties)]
+pub struct PrintViewProps {
+    pub files: Vec<EditorFile>,
+    /// file names, indexed like `SearchMatch::file`. may list a scanned file after `files`
+    pub names: Vec<String>,
+    pub results: Rc<RunResults>,
+    pub on_close: Callback<()>,
+}
+
+impl PartialEq for PrintViewProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.results, &other.results)
+            && self.files == other.files
+            && self.names == other.names
+            && self.on_close == other.on_close
+    }
+}
+
+/// what to draw on one line: highlighted 1-based UTF-16 column ranges, end exclusive, and
+/// the numbers of the matches starting at a column
+#[derive(Default)]
+struct LineMarks {
+    ranges: Vec<(usize, usize)>,
+    starts: Vec<(usize, usize)>,
+}
+
+/// the subjects with their matches highlighted and a table of the matches, on a light
+/// background without Monaco, for `window.print()`. it is rendered into the body, so the
+/// print stylesheet in index.html can hide the rest of the page
+pub struct PrintView;
+
+impl PrintView {
+    /// marks of `file` by 0-based line. matches are numbered by their row in the results
+    fn marks(results: &RunResults, file: usize) -> BTreeMap<usize, LineMarks> {
+        let mut marks: BTreeMap<usize, LineMarks> = BTreeMap::new();
+        for (row, &index) in results.order().iter().enumerate() {
+            let m = &results.matches[index];
+            if m.file != file {
+                continue;
+            }
+            let (start_line, end_line) = (m.start_line.max(1) - 1, m.end_line.max(1) - 1);
+            marks
+                .entry(start_line)
+                .or_default()
+                .starts
+                .push((m.start_col, row + 1));
+            for line in start_line..=end_line {
+                let from = if line == start_line { m.start_col } else { 1 };
+                let to = if line == end_line {
+                    m.end_col
+                } else {
+                    usize::MAX
+                };
+                marks.entry(line).or_default().ranges.push((from, to));
+            }
+        }
+        marks
+    }
+
+    fn view_line(text: &str, marks: Option<&LineMarks>) -> Html {
+        let marks = match marks {
+            Some(v) => v,
+            None => return html! { { text } },
+        };
+        let chars: Vec<char> = text.chars().collect();
+        // columns as char indices
+        let ranges: Vec<(usize, usize)> = marks
+            .ranges
+            .iter()
+            .map(|&(from, to)| (char_index(text, from), char_index(text, to)))
+            .collect();
+        let starts: Vec<(usize, usize)> = marks
+            .starts
+            .iter()
+            .map(|&(col, number)| (char_index(text, col), number))
+            .collect();
+        let mut cuts: Vec<usize> = vec![0, chars.len()];
+        for &(from, to) in &ranges {
+            cuts.push(from);
+            cuts.push(to);
+        }
+        cuts.extend(starts.iter().map(|&(at, _)| at));
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let mut nodes = Vec::new();
+        for (i, &at) in cuts.iter().enumerate() {
+            for &(_, number) in starts.iter().filter(|&&(start, _)| start == at) {
+                nodes.push(html! {
+                    <sup style="color:#a15c00; font-size:9px;">{ format!("[{number}]") }</sup>
+                });
+            }
+            let Some(&next) = cuts.get(i + 1) else {
+                break;
+            };
+            let segment: String = chars[at..next].iter().collect();
+            if ranges.iter().any(|&(from, to)| from <= at && at < to) {
+                nodes.push(html! {
+                    <mark style="background:#fff0a0; outline:1px solid #e0c040;">{ segment }</mark>
+                });
+            } else {
+                nodes.push(html! { { segment } });
+            }
+        }
+        nodes.into_iter().collect()
+    }
+
+    fn view_file(results: &RunResults, index: usize, file: &EditorFile) -> Html {
+        let marks = Self::marks(results, index);
+        let line_count = file.content.lines().count();
+        html! {
+            <section class="print-file">
+                <h2 style="font-size:14px; margin:16px 0 4px;">
+                    { format!("{} ({})", file.name, file.language) }
+                </h2>
+                <table style="border-collapse:collapse; font-family:monospace; font-size:11px; width:100%;">
+                    { for file.content.lines().take(MAX_PRINT_LINES).enumerate().map(|(line, text)| html! {
+                        <tr style="break-inside:avoid;">
+                            <td style="color:#999; text-align:right; padding-right:8px; vertical-align:top; user-select:none; width:1%;">
+                                { line + 1 }
+                            </td>
+                            <td style="white-space:pre-wrap; word-break:break-all;">
+                                { Self::view_line(text, marks.get(&line)) }
+                            </td>
+                        </tr>
+                    })}
+                </table>
+                { if line_count > MAX_PRINT_LINES {
+                    html! {
+                        <p style="color:#666; font-style:italic;">
+                            { tr!("print-more-lines", count = line_count - MAX_PRINT_LINES) }
+                        </p>
+                    }
+                } else {
+                    html! {}
+                }}
+            </section>
+        }
+    }
+
+    fn view_appendix(props: &PrintViewProps) -> Html {
+        let results = &props.results;
+        if results.order().is_empty() {
+            return html! { <p>{ tr!("print-no-results") }</p> };
+        }
+        let cell = "border:1px solid #ccc; padding:2px 4px; vertical-align:top;";
+        html! {
+            <table style="border-collapse:collapse; font-size:11px; width:100%;">
+                <thead>
+                    <tr style="background:#eee;">
+                        <th style={cell}>{ "#" }</th>
+                        <th style={cell}>{ tr!("print-location") }</th>
+                        <th style={cell}>{ tr!("print-rule") }</th>
+                        <th style={cell}>{ tr!("print-captures") }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                { for results.order().iter().enumerate().map(|(row, &index)| {
+                    let m = &results.matches[index];
+                    let file = props.names.get(m.file).map(|f| f.as_str()).unwrap_or("?");
+                    html! {
+                        <tr style="break-inside:avoid;">
+                            <td style={cell}>{ row + 1 }</td>
+                            <td style={cell}>{ format!("{}:{}:{}", file, m.start_line, m.start_col) }</td>
+                            <td style={cell}>{ m.name.clone() }</td>
+                            <td style={format!("{cell} font-family:monospace;")}>
+                                { for m.captures.iter().map(|(k, v)| html! {
+                                    <div>{ format!("{k} = {v}") }</div>
+                                })}
+                                { for results.outputs(index).into_iter().map(|(key, value)| html! {
+                                    <div style="color:#555;">{ format!("{key} → {value}") }</div>
+                                })}
+                            </td>
+                        </tr>
+                    }
+                })}
+                </tbody>
+            </table>
+        }
+    }
+}
+
+impl Component for PrintView {
+    type Message = ();
+    type Properties = PrintViewProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let on_close = props.on_close.reform(|_: MouseEvent| ());
+        let body = html! {
+            <div class="print-view" style="
+                position:fixed;
+                inset:0;
+                z-index:100;
+                overflow:auto;
+                background:white;
+                color:black;
+                font-family:sans-serif;
+                padding:16px 24px;
+            ">
+                <div class="print-toolbar" style="display:flex; gap:8px; margin-bottom:12px;">
+                    <button onclick={Callback::from(|_: MouseEvent| {
+                        let _ = gloo::utils::window().print();
+                    })}>
+                        { tr!("print") }
+                    </button>
+                    <button onclick={on_close}>{ tr!("close-print-view") }</button>
+                </div>
+                <h1 style="font-size:18px; margin:0 0 8px;">{ tr!("print-heading") }</h1>
+                { for props.files.iter().enumerate().map(|(index, file)| {
+                    Self::view_file(&props.results, index, file)
+                })}
+                <section class="print-appendix" style="break-before:page;">
+                    <h2 style="font-size:14px; margin:16px 0 4px;">
+                        { tr!("print-appendix", count = props.results.order().len()) }
+                    </h2>
+                    { Self::view_appendix(props) }
+                </section>
+            </div>
+        };
+        yew::create_portal(body, gloo::utils::body().into())
+    }
+}
@@ -0,0 +1,114 @@
+//! recovery from a panic. wasm panics abort, so the app is dead once the hook returns: the
+//! hook saves the patterns and every subject file to session storage and shows a banner
+//! made of plain DOM whose button reloads the page. the next start loads them back
+
+use std::{cell::RefCell, rc::Rc};
+
+use gloo::storage::{SessionStorage, Storage};
+use monaco::yew::CodeEditorLink;
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::tr, io::EditorFile};
+
+const RECOVERY_KEY: &str = "lexer-search-ui.recovery";
+
+type EditorRef = Rc<RefCell<Option<CodeEditorLink>>>;
+
+/// what the panic hook saved
+#[derive(Serialize, Deserialize)]
+pub struct Recovery {
+    pub message: String,
+    pub lhs: String,
+    pub files: Vec<EditorFile>,
+    /// the file that was shown
+    pub active_file: usize,
+}
+
+thread_local! {
+    /// the pattern and subject editors. the hook must be `Send`, so it can't hold them itself
+    static EDITORS: RefCell<Option<(EditorRef, EditorRef)>> = const { RefCell::new(None) };
+    /// the subject files and the index of the shown one, whose content the hook reads from
+    /// its editor instead
+    static FILES: RefCell<(Vec<EditorFile>, usize)> = const { RefCell::new((Vec::new(), 0)) };
+}
+
+fn editor_text(editor: &EditorRef) -> String {
+    editor
+        .try_borrow()
+        .ok()
+        .and_then(|editor| {
+            editor
+                .as_ref()?
+                .with_editor(|e| e.get_model().map(|m| m.get_value()))
+        })
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// install the panic hook, saving `lhs` and `rhs` when it fires
+pub fn install(lhs: EditorRef, rhs: EditorRef) {
+    EDITORS.with(|editors| *editors.borrow_mut() = Some((lhs, rhs)));
+    std::panic::set_hook(Box::new(|info| {
+        let message = info.to_string();
+        gloo::console::error!(message.clone());
+        let (lhs, subject) = EDITORS
+            .with(|editors| {
+                let editors = editors.try_borrow().ok()?;
+                let (lhs, rhs) = editors.as_ref()?;
+                Some((editor_text(lhs), editor_text(rhs)))
+            })
+            .unwrap_or_default();
+        let (mut files, active_file) = FILES
+            .with(|files| files.try_borrow().map(|files| files.clone()))
+            .unwrap_or_default();
+        if let Some(active) = files.get_mut(active_file) {
+            active.content = subject;
+        }
+        let saved = Recovery {
+            message,
+            lhs,
+            files,
+            active_file,
+        };
+        let _ = SessionStorage::set(RECOVERY_KEY, &saved);
+        show_banner(&saved.message);
+    }));
+}
+
+/// keep the subject files for the hook, `files[active_file]` being the one shown
+pub fn set_files(files: Vec<EditorFile>, active_file: usize) {
+    FILES.with(|f| *f.borrow_mut() = (files, active_file));
+}
+
+/// the editors saved before the last crash, removed from storage
+pub fn take() -> Option<Recovery> {
+    let saved = SessionStorage::get(RECOVERY_KEY).ok();
+    SessionStorage::delete(RECOVERY_KEY);
+    saved
+}
+
+/// an error banner over the dead UI. the button is an inline handler, since nothing in the
+/// wasm instance may be called anymore
+fn show_banner(message: &str) {
+    let document = gloo::utils::document();
+    let (Ok(banner), Ok(text), Ok(button)) = (
+        document.create_element("div"),
+        document.create_element("span"),
+        document.create_element("button"),
+    ) else {
+        return;
+    };
+    let _ = banner.set_attribute("role", "alert");
+    let _ = banner.set_attribute(
+        "style",
+        "position:fixed; top:0; left:0; right:0; z-index:1000; display:flex; gap:12px; \
+         align-items:center; background:#5a1a1a; color:#ffb3b3; padding:8px; \
+         font-family:monospace; white-space:pre-wrap;",
+    );
+    text.set_text_content(Some(&tr!("crash-banner", error = message)));
+    button.set_text_content(Some(&tr!("reload-engine")));
+    let _ = button.set_attribute("onclick", "location.reload()");
+    let _ = banner.append_child(&text);
+    let _ = banner.append_child(&button);
+    let _ = gloo::utils::body().append_child(&banner);
+}
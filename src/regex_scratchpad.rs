@@ -0,0 +1,199 @@
+use std::{collections::BTreeMap, rc::Rc};
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::{Callback, Component, Context, Html, InputEvent, Properties, TargetCast, html};
+
+use crate::i18n::tr;
+
+#[wasm_bindgen(module = "/src/highlight_helper.js")]
+extern "C" {
+    fn regex_preview_js(pattern: &str, sample: &str) -> JsValue;
+}
+
+/// result of running a regex over the sample, computed by the browser's regex engine
+#[derive(Deserialize, Default)]
+struct RegexPreview {
+    error: Option<String>,
+    matched: Option<String>,
+    /// (group name or number, captured text)
+    groups: Vec<(String, Option<String>)>,
+}
+
+fn preview(pattern: &str, sample: &str) -> RegexPreview {
+    serde_wasm_bindgen::from_value(regex_preview_js(pattern, sample)).unwrap_or_default()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RegexScratchpadProps {
+    /// name and transforms of every rule, in order
+    pub units: Rc<Vec<(String, BTreeMap<String, String>)>>,
+    /// rule index, capture name, regex
+    pub on_apply: Callback<(usize, String, String)>,
+    pub on_close: Callback<()>,
+}
+
+pub enum ScratchpadMsg {
+    Sample(String),
+    Regex(String),
+    Unit(usize),
+    Capture(String),
+    Apply,
+}
+
+/// tool panel for developing a `transform` regex against a sample capture value
+pub struct RegexScratchpad {
+    sample: String,
+    regex: String,
+    unit: usize,
+    capture: String,
+}
+
+impl RegexScratchpad {
+    /// start from the regex already in the selected unit's transform, if any
+    fn load_existing(&mut self, ctx: &Context<Self>) {
+        if let Some(regex) = ctx
+            .props()
+            .units
+            .get(self.unit)
+            .and_then(|(_, transform)| transform.get(&self.capture))
+        {
+            self.regex = regex.clone();
+        }
+    }
+}
+
+impl Component for RegexScratchpad {
+    type Message = ScratchpadMsg;
+    type Properties = RegexScratchpadProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            sample: String::new(),
+            regex: String::new(),
+            unit: 0,
+            capture: String::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ScratchpadMsg::Sample(sample) => self.sample = sample,
+            ScratchpadMsg::Regex(regex) => self.regex = regex,
+            ScratchpadMsg::Unit(unit) => {
+                self.unit = unit;
+                self.load_existing(ctx);
+            }
+            ScratchpadMsg::Capture(capture) => {
+                self.capture = capture;
+                self.load_existing(ctx);
+            }
+            ScratchpadMsg::Apply => {
+                ctx.props()
+                    .on_apply
+                    .emit((self.unit, self.capture.clone(), self.regex.clone()));
+                return false;
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let units = &ctx.props().units;
+        let result = if self.regex.is_empty() {
+            RegexPreview::default()
+        } else {
+            preview(&self.regex, &self.sample)
+        };
+        let existing: Vec<String> = units
+            .get(self.unit)
+            .map(|(_, transform)| transform.keys().cloned().collect())
+            .unwrap_or_default();
+
+        html! {
+            <div style="
+                position:absolute;
+                top:100%;
+                right:0;
+                z-index:10;
+                width:420px;
+                background:#2d2d2d;
+                color:#ccc;
+                border:1px solid #444;
+                padding:8px;
+                font-size:13px;
+            ">
+                <div style="display:flex; margin-bottom:6px;">
+                    <strong>{ tr!("scratchpad-title") }</strong>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={ctx.props().on_close.reform(|_| ())}>{"×"}</span>
+                </div>
+                <label style="display:block; margin-bottom:6px;">
+                    { tr!("scratchpad-sample") }
+                    <textarea rows="2" style="width:100%; box-sizing:border-box;"
+                        value={self.sample.clone()}
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                            ScratchpadMsg::Sample(e.target_unchecked_into::<HtmlTextAreaElement>().value())
+                        })} />
+                </label>
+                <label style="display:block; margin-bottom:6px;">
+                    { tr!("scratchpad-regex") }
+                    <input style="width:100%; box-sizing:border-box; font-family:monospace;"
+                        value={self.regex.clone()}
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                            ScratchpadMsg::Regex(e.target_unchecked_into::<HtmlInputElement>().value())
+                        })} />
+                </label>
+
+                <div style="font-family:monospace; margin-bottom:8px;">
+                    { if let Some(error) = &result.error {
+                        html! { <div style="color:#f48771;">{ error }</div> }
+                    } else if let Some(matched) = &result.matched {
+                        html! {
+                            <>
+                                <div style="color:#8fd18f;">{ tr!("scratchpad-match", value = format!("{:?}", matched)) }</div>
+                                { for result.groups.iter().map(|(name, value)| html! {
+                                    <div>{ tr!("scratchpad-group", name = name.as_str(), value = value.as_ref().map(|v| format!("{:?}", v)).unwrap_or_else(|| tr!("scratchpad-unset"))) }</div>
+                                })}
+                            </>
+                        }
+                    } else if !self.regex.is_empty() {
+                        html! { <div style="color:#aaa;">{ tr!("scratchpad-no-match") }</div> }
+                    } else {
+                        html! {}
+                    }}
+                </div>
+
+                <div style="display:flex; gap:4px; align-items:center;">
+                    <select onchange={ctx.link().callback(|e: Event| {
+                        ScratchpadMsg::Unit(e.target_unchecked_into::<HtmlSelectElement>().value().parse().unwrap_or(0))
+                    })}>
+                        { for units.iter().enumerate().map(|(i, (name, _))| html! {
+                            <option value={i.to_string()} selected={i == self.unit}>
+                                { if name.is_empty() { tr!("scratchpad-rule", n = i + 1) } else { name.clone() } }
+                            </option>
+                        })}
+                    </select>
+                    <input placeholder={tr!("scratchpad-capture-placeholder")} list="scratchpad-captures"
+                        style="flex:1; min-width:0;"
+                        value={self.capture.clone()}
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                            ScratchpadMsg::Capture(e.target_unchecked_into::<HtmlInputElement>().value())
+                        })} />
+                    <datalist id="scratchpad-captures">
+                        { for existing.iter().map(|k| html! { <option value={k.clone()} /> }) }
+                    </datalist>
+                    <button disabled={units.is_empty() || self.capture.is_empty() || self.regex.is_empty() || result.error.is_some()}
+                        title={tr!("scratchpad-apply.title")}
+                        onclick={ctx.link().callback(|_| ScratchpadMsg::Apply)}>
+                        { tr!("scratchpad-apply") }
+                    </button>
+                </div>
+                <div style="color:#888; font-size:11px; margin-top:6px;">
+                    { tr!("scratchpad-note") }
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,568 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use crate::{
+    api::SearchMatch,
+    i18n::tr,
+    io::{MatchingUnit, is_web_url},
+};
+
+/// capture values shown inline right after a match
+#[derive(Serialize)]
+pub struct InlayHint {
+    pub line: usize,
+    pub col: usize,
+    pub label: String,
+}
+
+/// the part of a match a capture bound, 1-based and end exclusive like the match
+pub struct CaptureSpan {
+    pub name: String,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+/// the documentation fields of a unit
+#[derive(Clone, Default, Debug)]
+pub struct RuleDoc {
+    pub description: Option<String>,
+    pub docs_url: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl RuleDoc {
+    /// the docs url, if it is safe to link to
+    pub fn link(&self) -> Option<&str> {
+        self.docs_url.as_deref().filter(|url| is_web_url(url))
+    }
+
+    /// markdown appended to the hover of the unit's matches
+    pub fn hover_markdown(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(description) = &self.description {
+            parts.push(description.clone());
+        }
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|t| format!("`{}`", t)).collect();
+            parts.push(tags.join(" "));
+        }
+        if let Some(url) = self.link() {
+            parts.push(format!("[{}]({})", tr!("rule-docs-link"), url));
+        }
+        parts.join("\n\n")
+    }
+}
+
+/// the `RuleDoc`s of the units that have any, keyed by unit name. like `UnitStyles`, the
+/// first unit of a name wins
+#[derive(Clone, Default)]
+pub struct RuleDocs {
+    docs: HashMap<String, RuleDoc>,
+}
+
+impl RuleDocs {
+    pub fn new(units: &[MatchingUnit]) -> Self {
+        let mut docs = HashMap::new();
+        for unit in units {
+            if docs.contains_key(&unit.name)
+                || (unit.description.is_none() && unit.docs_url.is_none() && unit.tags.is_empty())
+            {
+                continue;
+            }
+            docs.insert(
+                unit.name.clone(),
+                RuleDoc {
+                    description: unit.description.clone(),
+                    docs_url: unit.docs_url.clone(),
+                    tags: unit.tags.clone(),
+                },
+            );
+        }
+        Self { docs }
+    }
+
+    pub fn get(&self, unit_name: &str) -> Option<&RuleDoc> {
+        self.docs.get(unit_name)
+    }
+}
+
+/// an `out` template with every `$NAME` of a captured name replaced by its value, the way
+/// the CLI prints it. unknown names are kept as written
+pub fn expand_out(template: &str, captures: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(dollar) = rest.find('$') {
+        out.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        match captures.get(&after[..len]) {
+            Some(value) if len > 0 => out.push_str(value),
+            _ => out.push_str(&rest[dollar..dollar + 1 + len]),
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// what the results area shows
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ResultsTab {
+    /// every match, one per row
+    #[default]
+    Matches,
+    /// distinct captured values and their counts
+    Aggregate,
+}
+
+impl ResultsTab {
+    pub const ALL: [ResultsTab; 2] = [ResultsTab::Matches, ResultsTab::Aggregate];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            ResultsTab::Matches => "matches",
+            ResultsTab::Aggregate => "aggregate",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("tab-{}", self.id()))
+    }
+}
+
+/// how the results list is ordered, which is also the order matches are stepped through
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ResultOrder {
+    /// file, then line and column
+    #[default]
+    Position,
+    Unit,
+    /// the unit's `group`, so units sharing one end up together
+    Group,
+    /// shortest match first
+    Length,
+}
+
+impl ResultOrder {
+    pub const ALL: [ResultOrder; 4] = [
+        ResultOrder::Position,
+        ResultOrder::Unit,
+        ResultOrder::Group,
+        ResultOrder::Length,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            ResultOrder::Position => "position",
+            ResultOrder::Unit => "unit",
+            ResultOrder::Group => "group",
+            ResultOrder::Length => "length",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("order-{}", self.id()))
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|o| o.id() == id)
+    }
+}
+
+/// lines spanned, then columns: how big a match is without its text at hand
+fn extent(m: &SearchMatch) -> (usize, usize) {
+    (m.end_line - m.start_line, m.end_col.abs_diff(m.start_col))
+}
+
+/// the matches of the last run, indexed for lookups from the editor
+#[derive(Clone, Default)]
+pub struct RunResults {
+    pub matches: Vec<SearchMatch>,
+    /// documentation of the units that produced `matches`
+    pub docs: RuleDocs,
+    /// unit name -> its `group` as json, a sort key for `ResultOrder::Group`
+    groups: HashMap<String, String>,
+    /// unit name -> its `out` templates
+    outs: HashMap<String, BTreeMap<String, String>>,
+    /// indices into `matches` in the order they are listed, see `sort`
+    order: Vec<usize>,
+    sorted_by: ResultOrder,
+    /// only matches binding this (capture name, value) are listed
+    filter: Option<(String, String)>,
+    /// position of each match in `order`, `None` if filtered out
+    rows: Vec<Option<usize>>,
+    /// (capture name, captured value) -> indices into `matches`
+    capture_index: HashMap<(String, String), Vec<usize>>,
+    /// the subjects as they were run, for the context lines under each match
+    subjects: Vec<SubjectLines>,
+}
+
+/// a subject split into lines without copying them
+#[derive(Clone, Default)]
+struct SubjectLines {
+    text: String,
+    /// byte offset of each line
+    starts: Vec<usize>,
+}
+
+impl SubjectLines {
+    fn new(text: String) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { text, starts }
+    }
+
+    /// the 1-based line `n`, without its line break
+    fn line(&self, n: usize) -> Option<&str> {
+        let start = *self.starts.get(n.checked_sub(1)?)?;
+        let end = self.starts.get(n).map_or(self.text.len(), |&next| next - 1);
+        Some(self.text[start..end].trim_end_matches('\r'))
+    }
+}
+
+impl RunResults {
+    pub fn new(matches: Vec<SearchMatch>, units: &[MatchingUnit]) -> Self {
+        let mut groups = HashMap::new();
+        let mut outs = HashMap::new();
+        for unit in units {
+            groups
+                .entry(unit.name.clone())
+                .or_insert_with(|| serde_json::to_string(&unit.group).unwrap_or_default());
+            if !unit.out.is_empty() {
+                outs.entry(unit.name.clone())
+                    .or_insert_with(|| unit.out.clone());
+            }
+        }
+        let mut capture_index: HashMap<(String, String), Vec<usize>> = HashMap::new();
+        for (i, m) in matches.iter().enumerate() {
+            for (k, v) in &m.captures {
+                capture_index
+                    .entry((k.clone(), v.clone()))
+                    .or_default()
+                    .push(i);
+            }
+        }
+        let mut results = Self {
+            matches,
+            docs: RuleDocs::new(units),
+            groups,
+            outs,
+            order: Vec::new(),
+            sorted_by: ResultOrder::default(),
+            filter: None,
+            rows: Vec::new(),
+            capture_index,
+            subjects: Vec::new(),
+        };
+        results.sort(ResultOrder::default());
+        results
+    }
+
+    /// keep the subjects the matches were found in, indexed like `SearchMatch::file`
+    pub fn set_subjects(&mut self, subjects: Vec<String>) {
+        self.subjects = subjects.into_iter().map(SubjectLines::new).collect();
+    }
+
+    /// the kept subjects, indexed like `SearchMatch::file`
+    pub fn subjects(&self) -> Vec<&str> {
+        self.subjects.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    /// lines around the start of the match at `index`, `context` before and after, with
+    /// their 1-based numbers. empty if its subject wasn't kept
+    pub fn context(&self, index: usize, context: usize) -> Vec<(usize, &str)> {
+        let m = &self.matches[index];
+        let Some(subject) = self.subjects.get(m.file) else {
+            return Vec::new();
+        };
+        (m.start_line.saturating_sub(context).max(1)..=m.start_line + context)
+            .filter_map(|n| Some((n, subject.line(n)?)))
+            .collect()
+    }
+
+    /// append matches of a run still in progress, keeping the order and filter
+    pub fn extend(&mut self, matches: Vec<SearchMatch>) {
+        for m in matches {
+            let i = self.matches.len();
+            for (k, v) in &m.captures {
+                self.capture_index
+                    .entry((k.clone(), v.clone()))
+                    .or_default()
+                    .push(i);
+            }
+            self.matches.push(m);
+        }
+        self.sort(self.sorted_by);
+    }
+
+    /// indices of the matches in `file` whose range contains the position, smallest first
+    pub fn at(&self, file: usize, line: usize, col: usize) -> Vec<usize> {
+        let mut found: Vec<usize> = self
+            .matches
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| {
+                m.file == file
+                    && (m.start_line, m.start_col) <= (line, col)
+                    && (line, col) <= (m.end_line, m.end_col)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        found.sort_by_key(|&i| extent(&self.matches[i]));
+        found
+    }
+
+    /// reorder the list. ties are in position order
+    pub fn sort(&mut self, by: ResultOrder) {
+        let mut order: Vec<usize> = match &self.filter {
+            Some((name, value)) => self.references(name, value).to_vec(),
+            None => (0..self.matches.len()).collect(),
+        };
+        let position = |m: &SearchMatch| (m.file, m.start_line, m.start_col);
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.matches[a], &self.matches[b]);
+            let primary = match by {
+                ResultOrder::Position => std::cmp::Ordering::Equal,
+                ResultOrder::Unit => a.name.cmp(&b.name),
+                ResultOrder::Group => self.groups.get(&a.name).cmp(&self.groups.get(&b.name)),
+                ResultOrder::Length => extent(a).cmp(&extent(b)),
+            };
+            primary.then_with(|| position(a).cmp(&position(b)))
+        });
+        self.rows = vec![None; self.matches.len()];
+        for (row, &index) in order.iter().enumerate() {
+            self.rows[index] = Some(row);
+        }
+        self.order = order;
+        self.sorted_by = by;
+    }
+
+    /// list only the matches binding capture `name` to `value`, or all with `None`
+    pub fn set_filter(&mut self, filter: Option<(String, String)>) {
+        self.filter = filter;
+        self.sort(self.sorted_by);
+    }
+
+    pub fn filter(&self) -> Option<&(String, String)> {
+        self.filter.as_ref()
+    }
+
+    /// indices into `matches`, in list order
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// where match `index` is in the list, `None` if filtered out
+    pub fn row_of(&self, index: usize) -> Option<usize> {
+        self.rows.get(index).copied().flatten()
+    }
+
+    /// the expanded `out` values of match `index`, by key
+    pub fn outputs(&self, index: usize) -> Vec<(&str, String)> {
+        let m = &self.matches[index];
+        self.outs
+            .get(&m.name)
+            .into_iter()
+            .flatten()
+            .map(|(key, template)| (key.as_str(), expand_out(template, &m.captures)))
+            .collect()
+    }
+
+    /// whether any unit of the run has `out` templates
+    pub fn has_outputs(&self) -> bool {
+        !self.outs.is_empty()
+    }
+
+    /// every expanded `out` value of the listed matches in list order, one per line
+    pub fn outputs_text(&self) -> String {
+        let mut text = String::new();
+        for &index in &self.order {
+            for (_, value) in self.outputs(index) {
+                text.push_str(&value);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// every capture name bound by a match, sorted
+    pub fn capture_names(&self) -> BTreeSet<&str> {
+        self.capture_index.keys().map(|(k, _)| k.as_str()).collect()
+    }
+
+    /// the distinct values captured as `name` with how many matches bound each, most
+    /// frequent first
+    pub fn aggregate(&self, name: &str) -> Vec<(&str, usize)> {
+        let mut values: Vec<(&str, usize)> = self
+            .capture_index
+            .iter()
+            .filter(|((k, _), _)| k == name)
+            .map(|((_, v), matches)| (v.as_str(), matches.len()))
+            .collect();
+        values.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        values
+    }
+
+    /// one hint per match in `file` that captured anything, e.g. " ⇐ _VAR=x"
+    pub fn inlay_hints(&self, file: usize) -> Vec<InlayHint> {
+        self.matches
+            .iter()
+            .filter(|m| m.file == file && !m.captures.is_empty())
+            .map(|m| {
+                let captures: Vec<String> = m
+                    .captures
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect();
+                InlayHint {
+                    line: m.end_line,
+                    col: m.end_col,
+                    label: format!(" ⇐ {}", captures.join(", ")),
+                }
+            })
+            .collect()
+    }
+
+    /// where the captures of the first `limit` matches in `file` sit within them. the engine
+    /// only reports the captured text, so each capture is looked up in the matched text, at
+    /// the first place no other capture of the match took. captures a `transform` changed
+    /// aren't found and are left out
+    pub fn capture_spans(&self, file: usize, limit: usize) -> Vec<CaptureSpan> {
+        let Some(subject) = self.subjects.get(file) else {
+            return Vec::new();
+        };
+        let mut spans = Vec::new();
+        let matches = self
+            .matches
+            .iter()
+            .filter(|m| m.file == file && !m.captures.is_empty())
+            .take(limit);
+        for m in matches {
+            // each char of the match with its line and UTF-16 column
+            let mut chars: Vec<(char, usize, usize)> = Vec::new();
+            for n in m.start_line..=m.end_line {
+                let Some(line) = subject.line(n) else {
+                    break;
+                };
+                let mut col = 1;
+                for c in line.chars().chain(std::iter::once('\n')) {
+                    let at = (n, col);
+                    if (m.start_line, m.start_col) <= at && at < (m.end_line, m.end_col) {
+                        chars.push((c, n, col));
+                    }
+                    col += c.len_utf16();
+                }
+            }
+            let mut taken = Vec::new();
+            for (name, value) in &m.captures {
+                let needle: Vec<char> = value.chars().collect();
+                if needle.is_empty() || needle.len() > chars.len() {
+                    continue;
+                }
+                let found = (0..=chars.len() - needle.len()).find(|&i| {
+                    !taken.contains(&i)
+                        && chars[i..i + needle.len()]
+                            .iter()
+                            .map(|(c, _, _)| *c)
+                            .eq(needle.iter().copied())
+                });
+                let Some(start) = found else {
+                    continue;
+                };
+                taken.push(start);
+                let (_, start_line, start_col) = chars[start];
+                let (end_line, end_col) = match chars.get(start + needle.len()) {
+                    Some(&(_, line, col)) => (line, col),
+                    None => (m.end_line, m.end_col),
+                };
+                spans.push(CaptureSpan {
+                    name: name.clone(),
+                    start_line,
+                    start_col,
+                    end_line,
+                    end_col,
+                });
+            }
+        }
+        spans
+    }
+
+    /// indices of every match binding `name` to `value`
+    pub fn references(&self, name: &str, value: &str) -> &[usize] {
+        self.capture_index
+            .get(&(name.to_string(), value.to_string()))
+            .map(|v| &v[..])
+            .unwrap_or(&[])
+    }
+}
+
+/// match counts of the last run, per unit name
+pub struct RunSummary {
+    pub per_unit: BTreeMap<String, usize>,
+    /// units that had more matches than their `max_matches`
+    pub truncated: BTreeSet<String>,
+    /// the run only counted, nothing was highlighted
+    pub count_only: bool,
+    /// the run timeout in seconds, when the run hit it and these are partial results
+    pub timed_out: Option<u32>,
+}
+
+impl RunSummary {
+    pub fn from_matches(matches: &[SearchMatch], truncated: BTreeSet<String>) -> Self {
+        let mut per_unit: BTreeMap<String, usize> = BTreeMap::new();
+        for m in matches {
+            *per_unit.entry(m.name.clone()).or_default() += 1;
+        }
+        Self {
+            per_unit,
+            truncated,
+            count_only: false,
+            timed_out: None,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.per_unit.values().sum()
+    }
+
+    /// "3 matches — foo: 2, bar: 1"
+    pub fn describe(&self) -> String {
+        let total = self.total();
+        let mut out = tr!("summary-matches", count = total);
+        if !self.per_unit.is_empty() {
+            let units: Vec<String> = self
+                .per_unit
+                .iter()
+                .map(|(name, n)| {
+                    let mut unit = if name.is_empty() {
+                        format!("{}: {}", tr!("unnamed"), n)
+                    } else {
+                        format!("{}: {}", name, n)
+                    };
+                    if self.truncated.contains(name) {
+                        unit.push(' ');
+                        unit.push_str(&tr!("summary-capped"));
+                    }
+                    unit
+                })
+                .collect();
+            out.push_str(" — ");
+            out.push_str(&units.join(", "));
+        }
+        if self.count_only {
+            out.push(' ');
+            out.push_str(&tr!("summary-count-only"));
+        }
+        if let Some(secs) = self.timed_out {
+            out.push(' ');
+            out.push_str(&tr!("summary-timed-out", secs = secs));
+        }
+        out
+    }
+}
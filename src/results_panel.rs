@@ -0,0 +1,350 @@
+use std::rc::Rc;
+
+use web_sys::Element;
+use yew::{
+    Callback, Component, Context, Event, Html, KeyboardEvent, MouseEvent, NodeRef, Properties,
+    TargetCast, html,
+};
+
+use crate::{api::SearchMatch, i18n::tr, positions::char_index, results::RunResults};
+
+/// pixel height of one row. rows are fixed height so the visible window can be computed
+/// from the scroll offset alone
+const ROW_HEIGHT: i32 = 22;
+/// rows rendered above and below the viewport
+const OVERSCAN: usize = 10;
+/// pixel height of each context line under a match
+const CONTEXT_LINE_HEIGHT: i32 = 16;
+
+#[derive(Properties)]
+pub struct ResultsPanelProps {
+    pub results: Rc<RunResults>,
+    /// file names, indexed like `SearchMatch::file`
+    pub files: Vec<String>,
+    /// matches picked from the editor, e.g. by clicking a highlight. the first is scrolled to
+    #[prop_or_default]
+    pub selected: Vec<usize>,
+    /// subject lines shown before and after the line of each match
+    #[prop_or_default]
+    pub context_lines: usize,
+    pub on_select: Callback<usize>,
+    /// told the rows in view, end exclusive, when the user scrolls the list
+    #[prop_or_default]
+    pub on_visible: Option<Callback<(usize, usize)>>,
+}
+
+impl PartialEq for ResultsPanelProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.results, &other.results)
+            && self.files == other.files
+            && self.selected == other.selected
+            && self.context_lines == other.context_lines
+            && self.on_select == other.on_select
+            && self.on_visible == other.on_visible
+    }
+}
+
+pub enum ResultsPanelMsg {
+    Scrolled(i32, i32),
+    KeyDown(KeyboardEvent),
+    Activate(usize),
+}
+
+/// virtualized list of the matches of the last run, in `RunResults::order`: only the rows
+/// in view are in the DOM. it is a listbox for assistive tech, navigated with the arrow keys
+/// or `j`/`k` and Enter to jump
+pub struct ResultsPanel {
+    scroll_top: i32,
+    viewport_height: i32,
+    /// row of the keyboard cursor, announced through `aria-activedescendant`
+    active: Option<usize>,
+    container_ref: NodeRef,
+    /// `ROW_HEIGHT` plus the context lines, the same for every row
+    row_height: i32,
+    /// the list was scrolled by `move_to`, which isn't reported to `on_visible`
+    scrolled_by_cursor: bool,
+}
+
+fn row_height(props: &ResultsPanelProps) -> i32 {
+    match props.context_lines {
+        0 => ROW_HEIGHT,
+        n => ROW_HEIGHT + (2 * n as i32 + 1) * CONTEXT_LINE_HEIGHT + 4,
+    }
+}
+
+/// a line of context, the part inside the match emphasized
+fn view_context_line(line: usize, text: &str, m: &SearchMatch) -> Html {
+    let chars: Vec<char> = text.chars().collect();
+    let (from, to) = if line < m.start_line || line > m.end_line {
+        (0, 0)
+    } else {
+        let from = if line == m.start_line {
+            char_index(text, m.start_col)
+        } else {
+            0
+        };
+        let to = if line == m.end_line {
+            char_index(text, m.end_col)
+        } else {
+            chars.len()
+        };
+        (
+            from.min(chars.len()),
+            to.clamp(from.min(chars.len()), chars.len()),
+        )
+    };
+    let part = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+    html! {
+        <div style={format!("height:{CONTEXT_LINE_HEIGHT}px; line-height:{CONTEXT_LINE_HEIGHT}px; white-space:pre; overflow:hidden; color:#aaa;")}>
+            <span style="display:inline-block; width:48px; text-align:right; padding-right:8px; color:#666;">
+                { line }
+            </span>
+            { part(0..from) }
+            <span style="color:#fff; background:rgba(255, 255, 0, 0.25);">{ part(from..to) }</span>
+            { part(to..chars.len()) }
+        </div>
+    }
+}
+
+impl ResultsPanel {
+    /// move the keyboard cursor, scrolling it into view. the row may not be rendered yet,
+    /// so this scrolls the container rather than the row
+    fn move_to(&mut self, row: usize) {
+        self.active = Some(row);
+        let top = row as i32 * self.row_height;
+        let scroll_top = if top < self.scroll_top {
+            top
+        } else if top + self.row_height > self.scroll_top + self.viewport_height {
+            top + self.row_height - self.viewport_height
+        } else {
+            return;
+        };
+        self.scroll_top = scroll_top.max(0);
+        if let Some(container) = self.container_ref.cast::<Element>() {
+            self.scrolled_by_cursor = container.scroll_top() != self.scroll_top;
+            container.set_scroll_top(self.scroll_top);
+        }
+    }
+
+    /// tell `on_visible` which rows are in view
+    fn report_visible(&self, props: &ResultsPanelProps) {
+        let Some(on_visible) = &props.on_visible else {
+            return;
+        };
+        let len = props.results.order().len();
+        let first = (self.scroll_top / self.row_height).max(0) as usize;
+        let last = ((self.scroll_top + self.viewport_height) / self.row_height).max(0) as usize;
+        if first < len {
+            on_visible.emit((first, (last + 1).min(len)));
+        }
+    }
+}
+
+impl Component for ResultsPanel {
+    type Message = ResultsPanelMsg;
+    type Properties = ResultsPanelProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            scroll_top: 0,
+            viewport_height: 0,
+            active: None,
+            container_ref: NodeRef::default(),
+            row_height: row_height(ctx.props()),
+            scrolled_by_cursor: false,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        self.row_height = row_height(ctx.props());
+        if !Rc::ptr_eq(&ctx.props().results, &old_props.results) {
+            self.active = None;
+        }
+        if ctx.props().selected != old_props.selected {
+            if let Some(row) = first_selected_row(ctx.props()) {
+                self.move_to(row);
+            }
+        }
+        if old_props.on_visible.is_none() {
+            self.report_visible(ctx.props());
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ResultsPanelMsg::Scrolled(scroll_top, viewport_height) => {
+                let first_row = |top: i32| top / self.row_height;
+                let changed = first_row(scroll_top) != first_row(self.scroll_top)
+                    || viewport_height != self.viewport_height;
+                self.scroll_top = scroll_top;
+                self.viewport_height = viewport_height;
+                let by_cursor = std::mem::take(&mut self.scrolled_by_cursor);
+                if changed && !by_cursor {
+                    self.report_visible(ctx.props());
+                }
+                changed
+            }
+            ResultsPanelMsg::KeyDown(e) => {
+                let len = ctx.props().results.order().len();
+                if len == 0 {
+                    return false;
+                }
+                let page = (self.viewport_height / self.row_height).max(1) as usize;
+                let current = self.active;
+                let next = match e.key().as_str() {
+                    "ArrowDown" | "j" => current.map_or(0, |i| (i + 1).min(len - 1)),
+                    "ArrowUp" | "k" => current.map_or(0, |i| i.saturating_sub(1)),
+                    "PageDown" => current.map_or(0, |i| (i + page).min(len - 1)),
+                    "PageUp" => current.map_or(0, |i| i.saturating_sub(page)),
+                    "Home" => 0,
+                    "End" => len - 1,
+                    "Enter" | " " => {
+                        if let Some(row) = current {
+                            e.prevent_default();
+                            ctx.props().on_select.emit(ctx.props().results.order()[row]);
+                        }
+                        return false;
+                    }
+                    _ => return false,
+                };
+                e.prevent_default();
+                self.move_to(next);
+                true
+            }
+            ResultsPanelMsg::Activate(row) => {
+                self.active = Some(row);
+                ctx.props().on_select.emit(ctx.props().results.order()[row]);
+                true
+            }
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            if let Some(container) = self.container_ref.cast::<Element>() {
+                self.viewport_height = container.client_height();
+                if let Some(row) = first_selected_row(ctx.props()) {
+                    self.move_to(row);
+                }
+                ctx.link().send_message(ResultsPanelMsg::Scrolled(
+                    container.scroll_top(),
+                    container.client_height(),
+                ));
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let matches = &props.results.matches;
+        let len = props.results.order().len();
+
+        let row_height = self.row_height;
+        let first = (self.scroll_top / row_height).max(0) as usize;
+        let first = first.saturating_sub(OVERSCAN);
+        let visible = (self.viewport_height / row_height).max(0) as usize + 2 * OVERSCAN;
+        let last = (first + visible).min(len);
+
+        let onscroll = ctx.link().callback(|e: Event| {
+            let container: Element = e.target_unchecked_into();
+            ResultsPanelMsg::Scrolled(container.scroll_top(), container.client_height())
+        });
+
+        html! {
+            <div ref={self.container_ref.clone()} {onscroll}
+                role="listbox"
+                tabindex="0"
+                aria-label={tr!("results-list-label", count = len)}
+                aria-activedescendant={self.active.map(row_id)}
+                onkeydown={ctx.link().callback(ResultsPanelMsg::KeyDown)}
+                style="height:100%; overflow-y:auto; font-family:monospace; font-size:12px;">
+                <div style={format!("position:relative; height:{}px;", len as i32 * row_height)}>
+                    { for (first..last).map(|row| {
+                        let i = props.results.order()[row];
+                        let m = &matches[i];
+                        let file = props.files.get(m.file).map(|f| f.as_str()).unwrap_or("?");
+                        let captures: Vec<String> =
+                            m.captures.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                        let doc = props.results.docs.get(&m.name);
+                        let outputs: Vec<String> = props
+                            .results
+                            .outputs(i)
+                            .into_iter()
+                            .map(|(_, value)| value)
+                            .collect();
+                        let context = match props.context_lines {
+                            0 => Vec::new(),
+                            n => props.results.context(i, n),
+                        };
+                        let active = self.active == Some(row);
+                        let selected = props.selected.contains(&i);
+                        html! {
+                            <div
+                                id={row_id(row)}
+                                role="option"
+                                aria-selected={(active || selected).to_string()}
+                                // only a window of rows exists, so the position is spelled out
+                                aria-setsize={len.to_string()}
+                                aria-posinset={(row + 1).to_string()}
+                                style={format!(
+                                    "position:absolute; top:{}px; left:0; right:0; height:{}px; \
+                                     line-height:{}px; padding:0 8px; cursor:pointer; \
+                                     white-space:nowrap; overflow:hidden; text-overflow:ellipsis; \
+                                     box-sizing:border-box; {}",
+                                    row as i32 * row_height, row_height, ROW_HEIGHT,
+                                    if active {
+                                        "background:#094771; outline:1px solid #007acc;"
+                                    } else if selected {
+                                        "background:#094771;"
+                                    } else {
+                                        ""
+                                    }
+                                )}
+                                title={doc.and_then(|d| d.description.clone())}
+                                onclick={ctx.link().callback(move |_| ResultsPanelMsg::Activate(row))}
+                            >
+                                <span style="color:#888;">{ format!("{}:{}:{} ", file, m.start_line, m.start_col) }</span>
+                                <span style="color:#dcdcaa;">{ &m.name }</span>
+                                { for doc.into_iter().flat_map(|d| &d.tags).map(|tag| html! {
+                                    <span style="margin-left:4px; padding:0 4px; border-radius:6px; background:#3a3d41; color:#ccc;">
+                                        { tag }
+                                    </span>
+                                })}
+                                { doc.and_then(|d| d.link()).map(|url| html! {
+                                    <a href={url.to_string()} target="_blank" rel="noopener noreferrer"
+                                        title={tr!("rule-docs-link")}
+                                        style="margin-left:4px; color:#3794ff;"
+                                        onclick={|e: MouseEvent| e.stop_propagation()}>
+                                        { "?" }
+                                    </a>
+                                }) }
+                                <span style="color:#9cdcfe;">{ format!(" {}", captures.join(" ")) }</span>
+                                { if outputs.is_empty() {
+                                    html! {}
+                                } else {
+                                    html! {
+                                        <span style="color:#ce9178;">{ format!(" → {}", outputs.join(" | ")) }</span>
+                                    }
+                                }}
+                                { for context.into_iter().map(|(line, text)| view_context_line(line, text, m)) }
+                            </div>
+                        }
+                    })}
+                </div>
+            </div>
+        }
+    }
+}
+
+fn row_id(row: usize) -> String {
+    format!("result-row-{}", row)
+}
+
+/// list position of the first match picked from the editor that isn't filtered out
+fn first_selected_row(props: &ResultsPanelProps) -> Option<usize> {
+    props
+        .selected
+        .iter()
+        .find_map(|&index| props.results.row_of(index))
+}
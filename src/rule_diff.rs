@@ -0,0 +1,411 @@
+//! structured diff of the rule sets of two configs, e.g. two share links or a link and the
+//! current rules. units are paired by name; their patterns, transforms and the rest of
+//! their fields are compared line by line and shown side by side
+
+use std::collections::BTreeMap;
+
+use web_sys::HtmlInputElement;
+use yew::{
+    Callback, Component, Context, Html, InputEvent, MouseEvent, Properties, TargetCast, html,
+};
+
+use crate::{
+    i18n::tr,
+    io::{MatchingUnit, PlaygroundConfig, parse_lhs},
+};
+
+/// one row of a side-by-side diff: the old line, the new line, or both
+pub type Row = (Option<String>, Option<String>);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum UnitChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct UnitDiff {
+    pub name: String,
+    pub change: UnitChange,
+    pub patterns: Vec<Row>,
+    /// `capture: regex` lines
+    pub transforms: Vec<Row>,
+    /// every other field, as YAML lines
+    pub other: Vec<Row>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RuleSetDiff {
+    /// in the order of the new rule set, removed units after
+    pub units: Vec<UnitDiff>,
+    pub unchanged: usize,
+}
+
+/// a unit split into the parts that are compared
+struct UnitParts {
+    patterns: Vec<String>,
+    transforms: Vec<String>,
+    other: Vec<String>,
+}
+
+impl UnitParts {
+    fn new(mut unit: MatchingUnit) -> Self {
+        let patterns = std::mem::take(&mut unit.patterns);
+        let transforms = std::mem::take(&mut unit.transform)
+            .into_iter()
+            .map(|(capture, regex)| format!("{}: {}", capture, regex))
+            .collect();
+        unit.name.clear();
+        let other = match serde_yml::to_string(&unit) {
+            Ok(yaml) => yaml
+                .lines()
+                .filter(|l| l.trim() != "{}")
+                .map(str::to_string)
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        Self {
+            patterns,
+            transforms,
+            other,
+        }
+    }
+}
+
+/// units by display name, numbering unnamed and repeated names so each key is unique
+fn keyed(units: Vec<MatchingUnit>) -> Vec<(String, MatchingUnit)> {
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    units
+        .into_iter()
+        .enumerate()
+        .map(|(i, unit)| {
+            let base = if unit.name.is_empty() {
+                format!("rule {}", i + 1)
+            } else {
+                unit.name.clone()
+            };
+            let n = seen.entry(base.clone()).or_default();
+            *n += 1;
+            let key = if *n == 1 {
+                base
+            } else {
+                format!("{} ({})", base, n)
+            };
+            (key, unit)
+        })
+        .collect()
+}
+
+/// line diff of `old` and `new` by longest common subsequence. removed and added lines
+/// between two common ones share rows, so an edited line shows next to its old version
+pub fn align(old: &[String], new: &[String]) -> Vec<Row> {
+    let (n, m) = (old.len(), new.len());
+    // lcs[i][j]: length of the common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut rows = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let flush = |rows: &mut Vec<Row>, removed: &mut Vec<String>, added: &mut Vec<String>| {
+        let len = removed.len().max(added.len());
+        let mut removed = removed.drain(..);
+        let mut added = added.drain(..);
+        for _ in 0..len {
+            rows.push((removed.next(), added.next()));
+        }
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            flush(&mut rows, &mut removed, &mut added);
+            rows.push((Some(old[i].clone()), Some(new[j].clone())));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added.push(new[j].clone());
+            j += 1;
+        } else {
+            removed.push(old[i].clone());
+            i += 1;
+        }
+    }
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+fn one_side(lines: Vec<String>, old: bool) -> Vec<Row> {
+    lines
+        .into_iter()
+        .map(|l| {
+            if old {
+                (Some(l), None)
+            } else {
+                (None, Some(l))
+            }
+        })
+        .collect()
+}
+
+impl RuleSetDiff {
+    pub fn new(old: Vec<MatchingUnit>, new: Vec<MatchingUnit>) -> Self {
+        let mut old: Vec<(String, MatchingUnit)> = keyed(old);
+        let mut diff = Self::default();
+        for (name, unit) in keyed(new) {
+            let new = UnitParts::new(unit);
+            let Some(at) = old.iter().position(|(n, _)| *n == name) else {
+                diff.units.push(UnitDiff {
+                    name,
+                    change: UnitChange::Added,
+                    patterns: one_side(new.patterns, false),
+                    transforms: one_side(new.transforms, false),
+                    other: one_side(new.other, false),
+                });
+                continue;
+            };
+            let old = UnitParts::new(old.remove(at).1);
+            if old.patterns == new.patterns
+                && old.transforms == new.transforms
+                && old.other == new.other
+            {
+                diff.unchanged += 1;
+                continue;
+            }
+            diff.units.push(UnitDiff {
+                name,
+                change: UnitChange::Changed,
+                patterns: align(&old.patterns, &new.patterns),
+                transforms: align(&old.transforms, &new.transforms),
+                other: align(&old.other, &new.other),
+            });
+        }
+        for (name, unit) in old {
+            let old = UnitParts::new(unit);
+            diff.units.push(UnitDiff {
+                name,
+                change: UnitChange::Removed,
+                patterns: one_side(old.patterns, true),
+                transforms: one_side(old.transforms, true),
+                other: one_side(old.other, true),
+            });
+        }
+        diff
+    }
+}
+
+/// the config in a share link, or in the encoded part of one
+pub fn config_from_link(link: &str) -> Result<PlaygroundConfig, String> {
+    let link = link.trim();
+    let encoded = link.split_once("#/").map_or(link, |(_, encoded)| encoded);
+    PlaygroundConfig::from_encoded(encoded)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RuleDiffDialogProps {
+    /// the patterns editor, used for a side left empty
+    pub current_lhs: String,
+    pub on_close: Callback<()>,
+}
+
+pub enum RuleDiffMsg {
+    SetOld(String),
+    SetNew(String),
+    Compare,
+}
+
+/// two link fields and the diff of their rule sets
+pub struct RuleDiffDialog {
+    old_link: String,
+    new_link: String,
+    diff: Option<Result<RuleSetDiff, String>>,
+}
+
+impl RuleDiffDialog {
+    /// the rules behind `link`, or the current ones if it is empty
+    fn rules(link: &str, current_lhs: &str) -> Result<Vec<MatchingUnit>, String> {
+        if link.trim().is_empty() {
+            parse_lhs(current_lhs)
+        } else {
+            config_from_link(link).map(|cfg| cfg.lhs)
+        }
+    }
+
+    fn compare(&self, current_lhs: &str) -> Result<RuleSetDiff, String> {
+        let old = Self::rules(&self.old_link, current_lhs)
+            .map_err(|e| tr!("rule-diff-old-invalid", error = e))?;
+        let new = Self::rules(&self.new_link, current_lhs)
+            .map_err(|e| tr!("rule-diff-new-invalid", error = e))?;
+        Ok(RuleSetDiff::new(old, new))
+    }
+
+    fn view_rows(title: String, rows: &[Row]) -> Html {
+        if rows.is_empty() {
+            return html! {};
+        }
+        let cell = |line: &Option<String>, other: &Option<String>, color: &str| {
+            let background = match (line, other) {
+                (Some(a), Some(b)) if a == b => "",
+                (Some(_), _) => color,
+                (None, _) => "background:#2a2a2a;",
+            };
+            html! {
+                <td style={format!("width:50%; white-space:pre-wrap; word-break:break-all; vertical-align:top; padding:0 4px; {}", background)}>
+                    { line.clone().unwrap_or_default() }
+                </td>
+            }
+        };
+        html! {
+            <>
+                <tr><td colspan="2" style="color:#888; padding-top:4px;">{ title }</td></tr>
+                { for rows.iter().map(|(old, new)| html! {
+                    <tr>
+                        { cell(old, new, "background:rgba(244, 135, 113, 0.2);") }
+                        { cell(new, old, "background:rgba(143, 209, 143, 0.2);") }
+                    </tr>
+                })}
+            </>
+        }
+    }
+
+    fn view_unit(unit: &UnitDiff) -> Html {
+        let (label, color) = match unit.change {
+            UnitChange::Added => (tr!("rule-diff-added"), "#8fd18f"),
+            UnitChange::Removed => (tr!("rule-diff-removed"), "#f48771"),
+            UnitChange::Changed => (tr!("rule-diff-changed"), "#e8c547"),
+        };
+        html! {
+            <section style="margin-top:10px;">
+                <div>
+                    <span style="font-weight:bold;">{ unit.name.clone() }</span>
+                    <span style={format!("margin-left:8px; color:{};", color)}>{ label }</span>
+                </div>
+                <table style="border-collapse:collapse; width:100%; table-layout:fixed; font-family:monospace;">
+                    { Self::view_rows(tr!("rule-diff-patterns"), &unit.patterns) }
+                    { Self::view_rows(tr!("rule-diff-transforms"), &unit.transforms) }
+                    { Self::view_rows(tr!("rule-diff-other"), &unit.other) }
+                </table>
+            </section>
+        }
+    }
+
+    fn view_diff(&self) -> Html {
+        match &self.diff {
+            None => html! {},
+            Some(Err(e)) => html! {
+                <div role="alert" style="color:#f48771; margin-top:8px;">{ e.clone() }</div>
+            },
+            Some(Ok(diff)) if diff.units.is_empty() => html! {
+                <div style="margin-top:8px;">{ tr!("rule-diff-same", count = diff.unchanged) }</div>
+            },
+            Some(Ok(diff)) => html! {
+                <>
+                    <div style="display:flex; margin-top:8px; color:#888;">
+                        <span style="flex:1;">{ tr!("rule-diff-old") }</span>
+                        <span style="flex:1;">{ tr!("rule-diff-new") }</span>
+                    </div>
+                    { for diff.units.iter().map(Self::view_unit) }
+                    { if diff.unchanged > 0 {
+                        html! {
+                            <div style="margin-top:8px; color:#888;">
+                                { tr!("rule-diff-unchanged", count = diff.unchanged) }
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                </>
+            },
+        }
+    }
+}
+
+impl Component for RuleDiffDialog {
+    type Message = RuleDiffMsg;
+    type Properties = RuleDiffDialogProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            old_link: String::new(),
+            new_link: String::new(),
+            diff: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            RuleDiffMsg::SetOld(link) => {
+                self.old_link = link;
+                false
+            }
+            RuleDiffMsg::SetNew(link) => {
+                self.new_link = link;
+                false
+            }
+            RuleDiffMsg::Compare => {
+                self.diff = Some(self.compare(&ctx.props().current_lhs));
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let input = |value: &str, msg: fn(String) -> RuleDiffMsg| {
+            html! {
+                <input value={value.to_string()} placeholder={tr!("rule-diff-placeholder")}
+                    style="width:100%; box-sizing:border-box;"
+                    oninput={ctx.link().callback(move |e: InputEvent| {
+                        msg(e.target_unchecked_into::<HtmlInputElement>().value())
+                    })} />
+            }
+        };
+        html! {
+            <div role="dialog" aria-label={tr!("rule-diff")} style="
+                position:fixed;
+                top:5vh;
+                left:10vw;
+                right:10vw;
+                max-height:85vh;
+                overflow-y:auto;
+                z-index:30;
+                background:#252526;
+                color:#ccc;
+                border:1px solid #444;
+                border-radius:4px;
+                padding:10px 12px;
+                font-size:12px;
+                box-shadow:0 4px 16px rgba(0,0,0,0.5);
+            ">
+                <div style="display:flex;">
+                    <span style="font-weight:bold;">{ tr!("rule-diff") }</span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={props.on_close.reform(|_: MouseEvent| ())}>{"×"}</span>
+                </div>
+                <div style="display:flex; gap:8px; margin-top:8px;">
+                    <label style="flex:1;">
+                        <div>{ tr!("rule-diff-old") }</div>
+                        { input(&self.old_link, RuleDiffMsg::SetOld) }
+                    </label>
+                    <label style="flex:1;">
+                        <div>{ tr!("rule-diff-new") }</div>
+                        { input(&self.new_link, RuleDiffMsg::SetNew) }
+                    </label>
+                </div>
+                <div style="margin-top:8px;">
+                    <button onclick={ctx.link().callback(|_| RuleDiffMsg::Compare)}>
+                        { tr!("rule-diff-compare") }
+                    </button>
+                </div>
+                { self.view_diff() }
+            </div>
+        }
+    }
+}
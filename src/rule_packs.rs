@@ -0,0 +1,79 @@
+//! curated rule packs listed in a JSON index.
+//!
+//! the index is `{"packs": [{"name", "category", "description", "url"}]}`, where `url` points
+//! to a pattern YAML and may be relative to the index
+
+use std::collections::BTreeMap;
+
+use gloo::net::http::Request;
+use serde::Deserialize;
+
+use crate::io::{MatchingUnit, parse_lhs};
+
+pub const DEFAULT_INDEX_URL: &str =
+    "https://raw.githubusercontent.com/thescanner42/LexerSearch/main/rule-packs/index.json";
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct RulePack {
+    pub name: String,
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub description: String,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct Index {
+    packs: Vec<RulePack>,
+}
+
+async fn get_text(url: &str) -> Result<String, String> {
+    let response = Request::get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!(
+            "{} responded {} {}",
+            url,
+            response.status(),
+            response.status_text()
+        ));
+    }
+    response.text().await.map_err(|e| e.to_string())
+}
+
+/// `url` as given if absolute, else next to the index
+fn resolve_url(index_url: &str, url: &str) -> String {
+    if url.contains("://") {
+        return url.to_string();
+    }
+    let base = index_url.rsplit_once('/').map_or("", |(base, _)| base);
+    format!("{}/{}", base, url.trim_start_matches("./"))
+}
+
+/// the packs listed by the index at `index_url`, their urls made absolute
+pub async fn fetch_index(index_url: &str) -> Result<Vec<RulePack>, String> {
+    let index: Index = serde_json::from_str(&get_text(index_url).await?)
+        .map_err(|e| format!("rule pack index: {}", e))?;
+    Ok(index
+        .packs
+        .into_iter()
+        .map(|pack| RulePack {
+            url: resolve_url(index_url, &pack.url),
+            ..pack
+        })
+        .collect())
+}
+
+/// download and parse the rules of `pack`
+pub async fn fetch_pack(pack: &RulePack) -> Result<Vec<MatchingUnit>, String> {
+    parse_lhs(&get_text(&pack.url).await?).map_err(|e| format!("{}: {}", pack.name, e))
+}
+
+/// packs grouped by category, in index order within each
+pub fn by_category(packs: &[RulePack]) -> BTreeMap<&str, Vec<&RulePack>> {
+    let mut categories: BTreeMap<&str, Vec<&RulePack>> = BTreeMap::new();
+    for pack in packs {
+        categories.entry(&pack.category).or_default().push(pack);
+    }
+    categories
+}
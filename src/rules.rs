@@ -0,0 +1,355 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Range,
+};
+
+use serde_yml::Value;
+
+use crate::{io::MatchingUnit, tokens::token_spans};
+
+/// the scaffold inserted by "+ Add rule"
+const PLACEHOLDER_PATTERN: &str = "call(... $_ARG ...)";
+
+fn is_content(line: &str) -> bool {
+    let t = line.trim();
+    !t.is_empty() && !t.starts_with('#')
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// line ranges of the top level rules of a block style lhs, trailing blank lines excluded
+fn rule_blocks(lines: &[&str]) -> Vec<Range<usize>> {
+    let base = match lines
+        .iter()
+        .filter(|l| is_content(l))
+        .map(|l| indent(l))
+        .min()
+    {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+    let starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| is_content(l) && indent(l) == base && l.trim_start().starts_with('-'))
+        .map(|(i, _)| i)
+        .collect();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let mut end = starts.get(n + 1).copied().unwrap_or(lines.len());
+            while end > start + 1 && !is_content(lines[end - 1]) {
+                end -= 1;
+            }
+            start..end
+        })
+        .collect()
+}
+
+/// 0-based line ranges of the top level rules, in lhs order. empty for flow style yaml,
+/// whose rules can't be told apart by line
+pub fn rule_line_ranges(yaml: &str) -> Vec<Range<usize>> {
+    if is_flow_style(yaml) {
+        return Vec::new();
+    }
+    let lines: Vec<&str> = yaml.lines().collect();
+    rule_blocks(&lines)
+}
+
+fn is_flow_style(yaml: &str) -> bool {
+    let t = yaml.trim();
+    t.starts_with('[') && t != "[]"
+}
+
+/// display name and enabled flag of the rules, in order. unnamed rules are numbered
+pub fn rule_list(yaml: &str) -> Vec<(String, bool)> {
+    let units = match serde_yml::from_str::<Value>(yaml) {
+        Ok(Value::Sequence(units)) => units,
+        _ => return Vec::new(),
+    };
+    units
+        .iter()
+        .enumerate()
+        .map(|(i, unit)| {
+            let name = match unit.get("name").and_then(|n| n.as_str()) {
+                Some(name) if !name.is_empty() => name.to_string(),
+                _ => format!("rule {}", i + 1),
+            };
+            let enabled = unit
+                .get("enabled")
+                .and_then(|e| e.as_bool())
+                .unwrap_or(true);
+            (name, enabled)
+        })
+        .collect()
+}
+
+pub fn rule_names(yaml: &str) -> Vec<String> {
+    rule_list(yaml).into_iter().map(|(name, _)| name).collect()
+}
+
+/// every tag used by a rule, sorted
+pub fn rule_tags(yaml: &str) -> BTreeSet<String> {
+    let units = match serde_yml::from_str::<Value>(yaml) {
+        Ok(Value::Sequence(units)) => units,
+        _ => return BTreeSet::new(),
+    };
+    units
+        .iter()
+        .filter_map(|unit| unit.get("tags").and_then(|t| t.as_sequence()))
+        .flatten()
+        .filter_map(|tag| tag.as_str().map(str::to_string))
+        .collect()
+}
+
+/// column of a rule's own keys. the first key usually shares the line with the dash
+fn key_col(rule: &[&str]) -> usize {
+    let first = rule[0];
+    let after_dash = first.trim_start()[1..].trim_start();
+    if after_dash.is_empty() {
+        rule[1..]
+            .iter()
+            .find(|l| is_content(l))
+            .map(|l| indent(l))
+            .unwrap_or(indent(first) + 2)
+    } else {
+        first.len() - after_dash.len()
+    }
+}
+
+/// index within `rule` of the line holding the rule's own `key`
+fn find_key(rule: &[&str], key: &str) -> Option<usize> {
+    let col = key_col(rule);
+    let prefix = format!("{}:", key);
+    rule.iter().enumerate().position(|(i, line)| {
+        let own_level = if i == 0 {
+            line.len() >= col
+        } else {
+            is_content(line) && indent(line) == col
+        };
+        own_level && line[col..].starts_with(&prefix)
+    })
+}
+
+/// the line range of the rule at `index`, checked against the parsed rules
+fn rule_block(yaml: &str, lines: &[&str], index: usize) -> Result<Range<usize>, String> {
+    if is_flow_style(yaml) {
+        return Err("the rules are written in flow style, press \"Format\" first".to_string());
+    }
+    let blocks = rule_blocks(lines);
+    if blocks.len() != rule_list(yaml).len() {
+        return Err("the rules couldn't be parsed, fix the YAML first".to_string());
+    }
+    blocks
+        .get(index)
+        .cloned()
+        .ok_or_else(|| format!("no rule #{}", index + 1))
+}
+
+fn join_lines(lines: Vec<String>, like: &str) -> String {
+    let mut out = lines.join("\n");
+    if like.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// `base`, or `base_2`, `base_3`, ... whichever isn't taken
+pub fn unique_name(base: &str, taken: &[String]) -> String {
+    let mut name = base.to_string();
+    let mut n = 2;
+    while taken.contains(&name) {
+        name = format!("{}_{}", base, n);
+        n += 1;
+    }
+    name
+}
+
+/// words kept literally by `pattern_from_selection`, common to most supported languages
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "case", "catch", "class", "const", "continue", "def",
+    "default", "do", "else", "enum", "false", "finally", "fn", "for", "from", "func", "function",
+    "if", "impl", "import", "in", "let", "match", "mut", "new", "nil", "None", "null", "pub",
+    "return", "self", "static", "struct", "switch", "this", "throw", "true", "True", "False",
+    "try", "var", "while", "with", "yield",
+];
+
+/// turn subject code into a pattern: identifiers and strings become metavariables (repeated
+/// ones back-references), pattern syntax is escaped and whitespace collapsed
+pub fn pattern_from_selection(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut pattern = String::new();
+    let mut previous_end = None;
+    for (start, end) in token_spans(text) {
+        let token: String = chars[start..end].iter().collect();
+        if previous_end.is_some_and(|e| e < start) {
+            pattern.push(' ');
+        }
+        previous_end = Some(end);
+
+        let first = token.chars().next().unwrap_or(' ');
+        let is_word =
+            (first.is_alphabetic() || first == '_') && !KEYWORDS.contains(&token.as_str());
+        let is_string = first == '"' || first == '\'';
+        if is_word || is_string {
+            match names.get(&token) {
+                Some(var) => pattern.push_str(&format!("&{}", var)),
+                None => {
+                    let n = names.len();
+                    let letter = (b'A' + (n % 26) as u8) as char;
+                    let var = match n / 26 {
+                        0 => format!("_{}", letter),
+                        round => format!("_{}{}", letter, round + 1),
+                    };
+                    pattern.push_str(&format!("${}", var));
+                    names.insert(token, var);
+                }
+            }
+        } else if token.starts_with('$') || token == "&" || token == "..." || token == "\\" {
+            pattern.push('\\');
+            pattern.push_str(&token);
+        } else {
+            pattern.push_str(&token);
+        }
+    }
+    pattern
+}
+
+/// append a scaffolded rule. returns the new lhs and the 1-based line the rule starts on
+pub fn add_rule(yaml: &str, name: &str, pattern: Option<&str>) -> Result<(String, usize), String> {
+    if is_flow_style(yaml) {
+        return Err("the rules are written in flow style, press \"Format\" first".to_string());
+    }
+    let lines: Vec<&str> = yaml.lines().collect();
+    let pad = rule_blocks(&lines)
+        .first()
+        .map(|b| " ".repeat(indent(lines[b.start])))
+        .unwrap_or_default();
+
+    let mut out = yaml.trim_end().to_string();
+    if out.is_empty() || out == "[]" {
+        out.clear();
+    } else {
+        out.push('\n');
+    }
+    let line = out.lines().count() + 1;
+    out.push_str(&format!(
+        "{pad}- name: {name}\n{pad}  patterns:\n{pad}    - {pattern}\n",
+        pad = pad,
+        name = name,
+        // json strings are valid double quoted yaml scalars
+        pattern = serde_json::to_string(pattern.unwrap_or(PLACEHOLDER_PATTERN)).unwrap(),
+    ));
+    Ok((out, line))
+}
+
+/// outcome of `merge_rules`
+pub struct Merged {
+    pub lhs: String,
+    /// 1-based line of the first appended rule, `None` if nothing was appended
+    pub line: Option<usize>,
+    pub added: usize,
+    /// incoming rules left out because a rule of the same name exists
+    pub skipped: Vec<String>,
+}
+
+/// append `units` to the rules, keeping the existing text as is. a unit whose name is
+/// already taken is skipped, unnamed ones are always appended
+pub fn merge_rules(yaml: &str, units: Vec<MatchingUnit>) -> Result<Merged, String> {
+    if is_flow_style(yaml) {
+        return Err("the rules are written in flow style, press \"Format\" first".to_string());
+    }
+    let mut taken = rule_names(yaml);
+    let mut skipped = Vec::new();
+    let mut kept = Vec::new();
+    for unit in units {
+        if unit.name.is_empty() {
+            kept.push(unit);
+        } else if taken.contains(&unit.name) {
+            skipped.push(unit.name);
+        } else {
+            taken.push(unit.name.clone());
+            kept.push(unit);
+        }
+    }
+    if kept.is_empty() {
+        return Ok(Merged {
+            lhs: yaml.to_string(),
+            line: None,
+            added: 0,
+            skipped,
+        });
+    }
+
+    let lines: Vec<&str> = yaml.lines().collect();
+    let pad = rule_blocks(&lines)
+        .first()
+        .map(|b| " ".repeat(indent(lines[b.start])))
+        .unwrap_or_default();
+    let appended = serde_yml::to_string(&kept).map_err(|e| e.to_string())?;
+
+    let mut out = yaml.trim_end().to_string();
+    if out.is_empty() || out == "[]" {
+        out.clear();
+    } else {
+        out.push('\n');
+    }
+    let line = out.lines().count() + 1;
+    for l in appended.lines() {
+        if !l.is_empty() {
+            out.push_str(&pad);
+        }
+        out.push_str(l);
+        out.push('\n');
+    }
+    Ok(Merged {
+        lhs: out,
+        line: Some(line),
+        added: kept.len(),
+        skipped,
+    })
+}
+
+/// insert a copy of the rule at `index` right after it, renamed to `new_name`. returns the
+/// new lhs and the 1-based line the copy starts on
+pub fn duplicate_rule(yaml: &str, index: usize, new_name: &str) -> Result<(String, usize), String> {
+    let lines: Vec<&str> = yaml.lines().collect();
+    let block = rule_block(yaml, &lines, index)?;
+    let rule = &lines[block.clone()];
+
+    let mut copy: Vec<String> = rule.iter().map(|l| l.to_string()).collect();
+    if let Some(i) = find_key(rule, "name") {
+        copy[i] = format!("{}name: {}", &rule[i][..key_col(rule)], new_name);
+    }
+
+    let mut out: Vec<String> = lines[..block.end].iter().map(|l| l.to_string()).collect();
+    out.extend(copy);
+    out.extend(lines[block.end..].iter().map(|l| l.to_string()));
+    Ok((join_lines(out, yaml), block.end + 1))
+}
+
+/// set or clear `enabled: false` on the rule at `index`, leaving the rest of the text as is
+pub fn set_enabled(yaml: &str, index: usize, enabled: bool) -> Result<String, String> {
+    let lines: Vec<&str> = yaml.lines().collect();
+    let block = rule_block(yaml, &lines, index)?;
+    let rule = &lines[block.clone()];
+    let col = key_col(rule);
+
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    match find_key(rule, "enabled") {
+        // the dash line can't be removed, flip its value instead
+        Some(0) => out[block.start] = format!("{}enabled: {}", &rule[0][..col], enabled),
+        Some(i) if enabled => {
+            out.remove(block.start + i);
+        }
+        Some(i) => out[block.start + i] = format!("{}enabled: false", &rule[i][..col]),
+        None if enabled => {}
+        // appended so it can't split a multi-line value
+        None => out.insert(block.end, format!("{}enabled: false", " ".repeat(col))),
+    }
+    Ok(join_lines(out, yaml))
+}
@@ -0,0 +1,276 @@
+//! scrubbing the subjects of a share link, so a reproduction can be shared without the code
+//! it came from. the subjects are split by the engine's lexer for their language; words and
+//! string literals become placeholders and comments, which the engine skips, are dropped.
+//! numbers, punctuation and the words and strings the patterns mention stay, or the link
+//! wouldn't reproduce anything. equal names get equal placeholders in every file, so
+//! captures still bind alike
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use lexer_search_lib::io::Language;
+
+use crate::io::{PlaygroundConfig, engine_tokens};
+
+/// a string literal token split into its opening (prefix and quotes), content and closing
+fn literal_parts(token: &str) -> Option<(&str, &str, &str)> {
+    // a prefix such as python's `rb` or rust's `br#`
+    let prefix = token
+        .find(|c: char| !(c.is_alphabetic() || c == '#'))
+        .filter(|&i| token[..i].chars().filter(|c| c.is_alphabetic()).count() <= 2)?;
+    let quote = token[prefix..]
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '"' | '\'' | '`'))?;
+    let quotes = token[prefix..]
+        .chars()
+        .take_while(|&c| c == quote)
+        .count()
+        .min(3);
+    // `""` is an empty string, not the opening of `"""`
+    let quotes = if quotes == 2 { 1 } else { quotes };
+    let hashes = token[..prefix].chars().filter(|&c| c == '#').count();
+    let opening = prefix + quotes;
+    let closing = quotes + hashes;
+    // a rust lifetime or a literal the lexer cut short
+    if token.len() < opening + closing
+        || !token[token.len() - closing..]
+            .trim_end_matches('#')
+            .chars()
+            .all(|c| c == quote)
+    {
+        return None;
+    }
+    Some((
+        &token[..opening],
+        &token[opening..token.len() - closing],
+        &token[token.len() - closing..],
+    ))
+}
+
+fn is_word(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || matches!(c, '_' | '$'))
+        && token
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '$'))
+}
+
+/// placeholders handed out so far, shared by all subjects of a config
+#[derive(Default)]
+struct Scrubber {
+    /// words and literals the patterns mention
+    keep: BTreeSet<String>,
+    names: BTreeMap<String, String>,
+    strings: BTreeMap<String, String>,
+    next: usize,
+}
+
+impl Scrubber {
+    fn placeholder(&mut self, prefix: &str) -> String {
+        loop {
+            self.next += 1;
+            let name = format!("{}{}", prefix, self.next);
+            if !self.keep.contains(&name) {
+                return name;
+            }
+        }
+    }
+
+    fn name(&mut self, word: &str) -> String {
+        if self.keep.contains(word) {
+            return word.to_string();
+        }
+        if let Some(name) = self.names.get(word) {
+            return name.clone();
+        }
+        let name = self.placeholder("id");
+        self.names.insert(word.to_string(), name.clone());
+        name
+    }
+
+    fn string(&mut self, content: &str) -> String {
+        if let Some(name) = self.strings.get(content) {
+            return name.clone();
+        }
+        let name = self.placeholder("str");
+        self.strings.insert(content.to_string(), name.clone());
+        name
+    }
+
+    fn scrub(&mut self, text: &str, language: &Language) -> Result<String, String> {
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, end) in engine_tokens(language, text, false)? {
+            // between tokens only whitespace and comments; the line breaks stay, so the
+            // lines after a comment still line up
+            let gap = text.get(last..start).unwrap_or_default();
+            if gap.chars().all(char::is_whitespace) {
+                out.push_str(gap);
+            } else {
+                out.push(' ');
+                out.extend(gap.chars().filter(|&c| c == '\n'));
+            }
+            last = end;
+            let token = text.get(start..end).unwrap_or_default();
+            if self.keep.contains(token) {
+                out.push_str(token);
+            } else if let Some((opening, content, closing)) = literal_parts(token) {
+                // a single char or escape gives nothing away, and chars stay valid
+                let short = match content.strip_prefix('\\') {
+                    Some(escaped) => escaped.chars().count() <= 1,
+                    None => content.chars().count() <= 1,
+                };
+                if short {
+                    out.push_str(token);
+                } else {
+                    out.push_str(opening);
+                    out.push_str(&self.string(content));
+                    out.extend(content.chars().filter(|&c| c == '\n'));
+                    out.push_str(closing);
+                }
+            } else if is_word(token) {
+                out.push_str(&self.name(token));
+            } else {
+                // numbers and punctuation
+                out.push_str(token);
+            }
+        }
+        let gap = text.get(last..).unwrap_or_default();
+        if gap.chars().all(char::is_whitespace) {
+            out.push_str(gap);
+        } else {
+            out.extend(gap.chars().filter(|&c| c == '\n'));
+        }
+        Ok(out)
+    }
+}
+
+/// scrub every subject of `cfg`, see the module docs
+pub fn scrub_config(cfg: &mut PlaygroundConfig) -> Result<(), String> {
+    let patterns: Vec<&String> = cfg
+        .lhs
+        .iter()
+        .flat_map(|u| u.patterns.iter().chain(u.templates.values().flatten()))
+        .collect();
+    let mut keep = BTreeSet::new();
+    // the patterns are lexed the way each subject's language will read them
+    for language in std::iter::once(&cfg.language).chain(cfg.files.iter().map(|f| &f.language)) {
+        for pattern in &patterns {
+            // a pattern the lexer rejects doesn't compile either, so it keeps nothing
+            if let Ok(spans) = engine_tokens(language, pattern, true) {
+                keep.extend(
+                    spans
+                        .into_iter()
+                        .filter_map(|(s, e)| pattern.get(s..e))
+                        .map(str::to_string),
+                );
+            }
+        }
+    }
+    let mut scrubber = Scrubber {
+        keep,
+        ..Default::default()
+    };
+    cfg.subject = scrubber.scrub(&cfg.subject, &cfg.language)?;
+    for file in &mut cfg.files {
+        file.content = scrubber.scrub(&file.content, &file.language)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_raw_strings() {
+        assert_eq!(
+            literal_parts("br#\"a \"b\"#"),
+            Some(("br#\"", "a \"b", "\"#"))
+        );
+        assert_eq!(literal_parts("r##\"x\"##"), Some(("r##\"", "x", "\"##")));
+        assert_eq!(literal_parts("r\"\""), Some(("r\"", "", "\"")));
+    }
+
+    #[test]
+    fn python_prefixed_and_triple_quoted_strings() {
+        assert_eq!(literal_parts("rb'x y'"), Some(("rb'", "x y", "'")));
+        assert_eq!(
+            literal_parts("f\"\"\"doc\nmore\"\"\""),
+            Some(("f\"\"\"", "doc\nmore", "\"\"\""))
+        );
+        // an empty string, not the opening of a triple-quoted one
+        assert_eq!(literal_parts("\"\""), Some(("\"", "", "\"")));
+        assert_eq!(literal_parts("''''''"), Some(("'''", "", "'''")));
+    }
+
+    #[test]
+    fn not_literals() {
+        // a lifetime, a char stays one
+        assert_eq!(literal_parts("'a"), None);
+        assert_eq!(literal_parts("'a'"), Some(("'", "a", "'")));
+        // words, including ones starting like a prefix
+        assert_eq!(literal_parts("rb"), None);
+        assert_eq!(literal_parts("secret"), None);
+        assert_eq!(literal_parts("fr_x"), None);
+        // cut short by the lexer
+        assert_eq!(literal_parts("\""), None);
+        assert_eq!(literal_parts("\"abc"), None);
+    }
+
+    fn scrubbed(language: &str, subject: &str, lhs: &str) -> String {
+        let mut cfg = PlaygroundConfig::from_editor_parts(subject, language, lhs).unwrap();
+        scrub_config(&mut cfg).unwrap();
+        cfg.subject
+    }
+
+    #[test]
+    fn pattern_words_survive() {
+        let out = scrubbed(
+            "python",
+            "import os\nos.system(secret_cmd)\nif ready:\n    leak(\"token\")\n",
+            "- name: os-system\n  patterns:\n    - 'if $X:'\n    - os.system($CMD)\n",
+        );
+        assert!(out.contains("os.system("), "{}", out);
+        assert!(out.contains("if "), "{}", out);
+        for gone in ["secret_cmd", "ready", "leak", "token", "import"] {
+            assert!(!out.contains(gone), "{} in {}", gone, out);
+        }
+        assert_eq!(out.lines().count(), 4);
+    }
+
+    #[test]
+    fn comments_go_and_lines_stay() {
+        let out = scrubbed(
+            "rust",
+            "/* secret\nstuff */ call(1);\n// more secret\ncall(2);\n",
+            "- name: call\n  patterns:\n    - call($X)\n",
+        );
+        assert!(!out.contains("secret"), "{}", out);
+        assert_eq!(out.lines().count(), 4);
+        assert_eq!(out.lines().nth(1).map(str::trim), Some("call(1);"));
+        assert_eq!(out.lines().nth(3), Some("call(2);"));
+    }
+
+    #[test]
+    fn equal_names_equal_placeholders() {
+        let mut cfg = PlaygroundConfig::from_editor_parts(
+            "let user = user_name;\n",
+            "rust",
+            "- name: hi\n  patterns:\n    - hello_world($X)\n",
+        )
+        .unwrap();
+        cfg.add_file("second", "rust", "print(user);\n").unwrap();
+        scrub_config(&mut cfg).unwrap();
+        let placeholder = cfg.subject.split_whitespace().nth(1).unwrap().to_string();
+        assert!(placeholder.starts_with("id"), "{}", cfg.subject);
+        assert!(
+            cfg.files[0].content.contains(&format!("({})", placeholder)),
+            "{}",
+            cfg.files[0].content
+        );
+        assert!(!cfg.subject.contains("user_name"));
+    }
+}
@@ -0,0 +1,343 @@
+//! a built-in suite of rules, subjects and the matches they must give, opened with the
+//! `#/self-test` fragment. deployers run it to check that a build of the UI and its bundled
+//! engine behave in the browser they target
+
+use yew::{Callback, Component, Context, Html, MouseEvent, Properties, html};
+
+use crate::{
+    api::{SearchMatch, search},
+    i18n::tr,
+    io::PlaygroundConfig,
+};
+
+/// the fragment that opens the self-test, after the public url's `#/`
+const FRAGMENT: &str = "self-test";
+
+/// whether `s` is a link to the self-test
+pub fn is_self_test_url(s: &str) -> bool {
+    s.split_once('#')
+        .is_some_and(|(_, fragment)| fragment.strip_prefix('/').unwrap_or(fragment) == FRAGMENT)
+}
+
+/// a match a case must give
+struct Expected {
+    file: usize,
+    name: &'static str,
+    /// 1-based
+    start_line: usize,
+    /// the value of one of its captures
+    capture: Option<&'static str>,
+}
+
+struct Case {
+    name: &'static str,
+    /// monaco id
+    language: &'static str,
+    /// the rules' YAML, as typed into the patterns editor
+    lhs: &'static str,
+    subject: &'static str,
+    /// additional subjects in the same language
+    files: &'static [(&'static str, &'static str)],
+    /// ordered by file and line
+    expected: &'static [Expected],
+}
+
+const CASES: &[Case] = &[
+    Case {
+        name: "rust call",
+        language: "rust",
+        lhs: "- name: hi\n  patterns:\n    - hello_world(... $CAPTURE ...)\n",
+        subject: "// a comment\nhello_world(\"test\");\n",
+        files: &[],
+        expected: &[Expected {
+            file: 0,
+            name: "hi",
+            start_line: 2,
+            capture: Some("\"test\""),
+        }],
+    },
+    Case {
+        name: "rust no match",
+        language: "rust",
+        lhs: "- name: bye\n  patterns:\n    - goodbye_world($X)\n",
+        subject: "hello_world(\"test\");\n",
+        files: &[],
+        expected: &[],
+    },
+    Case {
+        name: "disabled rule",
+        language: "rust",
+        lhs: "- name: hi\n  enabled: false\n  patterns:\n    - hello_world(... $CAPTURE ...)\n",
+        subject: "hello_world(\"test\");\n",
+        files: &[],
+        expected: &[],
+    },
+    Case {
+        name: "several files",
+        language: "rust",
+        lhs: "- name: hi\n  patterns:\n    - hello_world(... $CAPTURE ...)\n",
+        subject: "fn main() {}\nhello_world(1);\n",
+        files: &[("second", "hello_world(2);\n")],
+        expected: &[
+            Expected {
+                file: 0,
+                name: "hi",
+                start_line: 2,
+                capture: Some("1"),
+            },
+            Expected {
+                file: 1,
+                name: "hi",
+                start_line: 1,
+                capture: Some("2"),
+            },
+        ],
+    },
+    Case {
+        name: "python call",
+        language: "python",
+        lhs: "- name: os-system\n  patterns:\n    - os.system($CMD)\n",
+        subject: "import os\n\nos.system(cmd)\n",
+        files: &[],
+        expected: &[Expected {
+            file: 0,
+            name: "os-system",
+            start_line: 3,
+            capture: Some("cmd"),
+        }],
+    },
+    Case {
+        name: "javascript assignment",
+        language: "javascript",
+        lhs: "- name: inner-html\n  patterns:\n    - $EL.innerHTML = $VALUE;\n",
+        subject: "const el = document.body;\nel.innerHTML = name;\n",
+        files: &[],
+        expected: &[Expected {
+            file: 0,
+            name: "inner-html",
+            start_line: 2,
+            capture: Some("name"),
+        }],
+    },
+    Case {
+        name: "c call",
+        language: "cpp",
+        lhs: "- name: gets\n  patterns:\n    - gets($BUF)\n",
+        subject: "char buf[16];\ngets(buf);\n",
+        files: &[],
+        expected: &[Expected {
+            file: 0,
+            name: "gets",
+            start_line: 2,
+            capture: Some("buf"),
+        }],
+    },
+    Case {
+        name: "two rules",
+        language: "java",
+        lhs: "- name: exec\n  patterns:\n    - Runtime.getRuntime().exec($CMD)\n- name: load\n  patterns:\n    - System.loadLibrary($LIB)\n",
+        subject: "System.loadLibrary(lib);\nRuntime.getRuntime().exec(cmd);\n",
+        files: &[],
+        expected: &[
+            Expected {
+                file: 0,
+                name: "load",
+                start_line: 1,
+                capture: Some("lib"),
+            },
+            Expected {
+                file: 0,
+                name: "exec",
+                start_line: 2,
+                capture: Some("cmd"),
+            },
+        ],
+    },
+];
+
+/// how one case went
+pub struct Outcome {
+    pub name: &'static str,
+    pub language: &'static str,
+    /// what went wrong, if anything
+    pub result: Result<(), String>,
+    pub ms: f64,
+}
+
+fn describe(m: &SearchMatch) -> String {
+    format!("{}@{}:{}", m.name, m.file, m.start_line)
+}
+
+fn check(case: &Case) -> Result<(), String> {
+    let mut cfg = PlaygroundConfig::from_editor_parts(case.subject, case.language, case.lhs)?;
+    for (name, content) in case.files {
+        cfg.add_file(name, case.language, content)?;
+    }
+    let mut matches = search(cfg)?;
+    matches.sort_by_key(|m| (m.file, m.start_line, m.start_col));
+    if matches.len() != case.expected.len() {
+        return Err(tr!(
+            "self-test-count",
+            expected = case.expected.len(),
+            got = matches.len(),
+            matches = matches.iter().map(describe).collect::<Vec<_>>().join(", ")
+        ));
+    }
+    for (m, expected) in matches.iter().zip(case.expected) {
+        let captured = expected
+            .capture
+            .is_none_or(|value| m.captures.values().any(|v| v == value));
+        if m.file != expected.file
+            || m.name != expected.name
+            || m.start_line != expected.start_line
+            || !captured
+        {
+            return Err(tr!(
+                "self-test-mismatch",
+                expected = format!(
+                    "{}@{}:{} {}",
+                    expected.name,
+                    expected.file,
+                    expected.start_line,
+                    expected.capture.unwrap_or_default()
+                ),
+                got = format!(
+                    "{} {}",
+                    describe(m),
+                    m.captures.values().cloned().collect::<Vec<_>>().join(" ")
+                )
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// run every case
+pub fn run_all() -> Vec<Outcome> {
+    CASES
+        .iter()
+        .map(|case| {
+            let started = js_sys::Date::now();
+            let result = check(case);
+            Outcome {
+                name: case.name,
+                language: case.language,
+                result,
+                ms: js_sys::Date::now() - started,
+            }
+        })
+        .collect()
+}
+
+/// the outcomes as plain text, to paste into a bug report
+fn report(outcomes: &[Outcome]) -> String {
+    let passed = outcomes.iter().filter(|o| o.result.is_ok()).count();
+    let mut text = format!(
+        "LexerSearch v{}: {}/{}\n",
+        env!("CARGO_PKG_VERSION"),
+        passed,
+        outcomes.len()
+    );
+    for o in outcomes {
+        text += &format!(
+            "{} {} ({}, {:.0} ms){}\n",
+            if o.result.is_ok() { "ok  " } else { "FAIL" },
+            o.name,
+            o.language,
+            o.ms,
+            o.result
+                .as_ref()
+                .err()
+                .map(|e| format!(": {}", e))
+                .unwrap_or_default()
+        );
+    }
+    text
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SelfTestDialogProps {
+    pub on_close: Callback<()>,
+}
+
+pub enum SelfTestMsg {
+    Run,
+}
+
+/// the outcome of each case, run when opened and on request
+pub struct SelfTestDialog {
+    outcomes: Vec<Outcome>,
+}
+
+impl Component for SelfTestDialog {
+    type Message = SelfTestMsg;
+    type Properties = SelfTestDialogProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            outcomes: run_all(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SelfTestMsg::Run => {
+                self.outcomes = run_all();
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let passed = self.outcomes.iter().filter(|o| o.result.is_ok()).count();
+        let all_passed = passed == self.outcomes.len();
+        html! {
+            <div role="dialog" aria-label={tr!("self-test")} style="
+                position:fixed;
+                top:5vh;
+                left:10vw;
+                right:10vw;
+                max-height:85vh;
+                overflow-y:auto;
+                z-index:30;
+                background:#252526;
+                color:#ccc;
+                border:1px solid #444;
+                border-radius:4px;
+                padding:10px 12px;
+                font-size:12px;
+                box-shadow:0 4px 16px rgba(0,0,0,0.5);
+            ">
+                <div style="display:flex;">
+                    <span style="font-weight:bold;">{ tr!("self-test") }</span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={props.on_close.reform(|_: MouseEvent| ())}>{"×"}</span>
+                </div>
+                <div style="display:flex; gap:8px; align-items:center; margin-top:8px;">
+                    <button onclick={ctx.link().callback(|_| SelfTestMsg::Run)}>
+                        { tr!("self-test-run") }
+                    </button>
+                    <span role="status" style={if all_passed { "color:#8fd18f;" } else { "color:#f48771;" }}>
+                        { tr!("self-test-summary", passed = passed, total = self.outcomes.len()) }
+                    </span>
+                </div>
+                <table style="margin-top:8px; border-collapse:collapse; width:100%;">
+                    { for self.outcomes.iter().map(|o| html! {
+                        <tr>
+                            <td style={if o.result.is_ok() { "color:#8fd18f; padding-right:8px;" } else { "color:#f48771; padding-right:8px;" }}>
+                                { if o.result.is_ok() { "✓" } else { "✗" } }
+                            </td>
+                            <td style="padding-right:8px;">{ o.name }</td>
+                            <td style="color:#888; padding-right:8px;">{ o.language }</td>
+                            <td style="color:#888; padding-right:8px;">{ format!("{:.0} ms", o.ms) }</td>
+                            <td style="color:#f48771;">{ o.result.as_ref().err().cloned().unwrap_or_default() }</td>
+                        </tr>
+                    })}
+                </table>
+                <div style="color:#888; margin-top:8px;">{ tr!("self-test-report") }</div>
+                <pre style="margin:4px 0 0; white-space:pre-wrap; user-select:all;">{ report(&self.outcomes) }</pre>
+            </div>
+        }
+    }
+}
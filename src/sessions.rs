@@ -0,0 +1,142 @@
+//! several independent sessions in one browser tab, shown as tabs above the app. each
+//! session's rules and subjects are kept in local storage, so they survive a reload
+
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::tr, io::EditorFile};
+
+const STORAGE_KEY: &str = "lexer-search-ui.sessions";
+
+/// the editors of one tab
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Session {
+    pub name: String,
+    pub lhs: String,
+    pub files: Vec<EditorFile>,
+    #[serde(default)]
+    pub active_file: usize,
+}
+
+/// the sessions and which one is shown
+#[derive(Serialize, Deserialize, Default)]
+pub struct Sessions {
+    sessions: Vec<Session>,
+    active: usize,
+}
+
+impl Sessions {
+    /// read the sessions from local storage. missing or corrupt data yields none
+    pub fn load() -> Self {
+        let mut sessions: Self = LocalStorage::get(STORAGE_KEY).unwrap_or_default();
+        sessions.sessions.retain(|s| !s.files.is_empty());
+        sessions.active = sessions
+            .active
+            .min(sessions.sessions.len().saturating_sub(1));
+        sessions
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        LocalStorage::set(STORAGE_KEY, self).map_err(|e| e.to_string())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Session> {
+        self.sessions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// index of the shown session
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub fn active_session(&self) -> Option<&Session> {
+        self.sessions.get(self.active)
+    }
+
+    /// the first "Workspace N" that no session is called yet
+    pub fn free_name(&self) -> String {
+        (1..)
+            .map(|n: usize| tr!("session-name", number = n))
+            .find(|name| self.sessions.iter().all(|s| s.name != *name))
+            .unwrap_or_default()
+    }
+
+    /// overwrite the shown session's editors. the first save creates it
+    pub fn save_active(
+        &mut self,
+        lhs: String,
+        files: Vec<EditorFile>,
+        active_file: usize,
+    ) -> Result<(), String> {
+        match self.sessions.get_mut(self.active) {
+            Some(session) => {
+                session.lhs = lhs;
+                session.files = files;
+                session.active_file = active_file;
+            }
+            None => {
+                let name = self.free_name();
+                self.sessions.push(Session {
+                    name,
+                    lhs,
+                    files,
+                    active_file,
+                });
+                self.active = self.sessions.len() - 1;
+            }
+        }
+        self.persist()
+    }
+
+    /// append a session and show it
+    pub fn add(&mut self, session: Session) -> Result<(), String> {
+        self.sessions.push(session);
+        self.active = self.sessions.len() - 1;
+        self.persist()
+    }
+
+    /// show another session
+    pub fn select(&mut self, index: usize) -> Result<Session, String> {
+        let session = self
+            .sessions
+            .get(index)
+            .cloned()
+            .ok_or_else(|| format!("no session {}", index))?;
+        self.active = index;
+        self.persist()?;
+        Ok(session)
+    }
+
+    pub fn rename(&mut self, index: usize, to: String) -> Result<(), String> {
+        if self.sessions.iter().any(|s| s.name == to) {
+            return Err(tr!("session-name-taken", name = to));
+        }
+        let session = self
+            .sessions
+            .get_mut(index)
+            .ok_or_else(|| format!("no session {}", index))?;
+        session.name = to;
+        self.persist()
+    }
+
+    /// remove a session. the one after it is shown if it was, the last one can't be closed
+    pub fn close(&mut self, index: usize) -> Result<(), String> {
+        if self.sessions.len() <= 1 || index >= self.sessions.len() {
+            return Err(format!("can't close session {}", index));
+        }
+        self.sessions.remove(index);
+        if self.active > index {
+            self.active -= 1;
+        }
+        self.active = self.active.min(self.sessions.len() - 1);
+        self.persist()
+    }
+}
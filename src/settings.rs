@@ -0,0 +1,376 @@
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::{Callback, Component, Context, Event, Html, InputEvent, Properties, TargetCast, html};
+
+use crate::{
+    i18n::{Locale, tr},
+    io::{CompressionLevel, LinkEncoding},
+    overlap::OverlapMode,
+    rule_packs::DEFAULT_INDEX_URL,
+};
+
+const STORAGE_KEY: &str = "lexer-search-ui.settings";
+const DEFAULT_MAX_HIGHLIGHTS: usize = 2000;
+const MAX_CONTEXT_LINES: usize = 2;
+const DEFAULT_RUN_TIMEOUT_SECS: u32 = 10;
+
+/// user preferences, persisted in local storage
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Settings {
+    /// endpoint of an optional link shortener. empty keeps sharing fully client-side
+    pub shortener_url: String,
+    /// how overlapping matches are reduced before highlighting
+    pub overlap_mode: OverlapMode,
+    /// how share links are encoded
+    pub link_encoding: LinkEncoding,
+    /// replace identifiers and strings in the subjects of a share link, see `scrub`
+    pub scrub_shared_links: bool,
+    /// zstd effort when building share links
+    pub compression: CompressionLevel,
+    /// language of the UI
+    pub locale: Locale,
+    /// run the patterns that compile instead of failing on the first broken one
+    pub skip_invalid_patterns: bool,
+    /// run again when the rules, subjects or language change, instead of only clearing the
+    /// results on a language change
+    pub auto_run: bool,
+    /// edits that only change trailing whitespace don't trigger an auto-run
+    pub ignore_trailing_whitespace: bool,
+    /// decorations applied to the subject at once. more are added on request
+    pub max_highlights: usize,
+    /// subject lines listed before and after each match in the results
+    pub context_lines: usize,
+    /// a run stops with what it found after this long. 0 lets it finish
+    pub run_timeout_secs: u32,
+    /// tokens matched per frame, so a long run keeps the page responsive. 0 matches
+    /// everything at once
+    pub run_slice_tokens: usize,
+    /// JSON index listing the rule packs offered by "Rule packs"
+    pub rule_pack_index: String,
+    /// body of a JS function called with each match, see `hook`. empty for none
+    pub result_hook: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            shortener_url: String::new(),
+            overlap_mode: OverlapMode::default(),
+            link_encoding: LinkEncoding::default(),
+            scrub_shared_links: false,
+            compression: CompressionLevel::default(),
+            locale: Locale::default(),
+            skip_invalid_patterns: false,
+            auto_run: false,
+            ignore_trailing_whitespace: false,
+            max_highlights: DEFAULT_MAX_HIGHLIGHTS,
+            context_lines: 0,
+            run_timeout_secs: DEFAULT_RUN_TIMEOUT_SECS,
+            run_slice_tokens: 0,
+            rule_pack_index: DEFAULT_INDEX_URL.to_string(),
+            result_hook: String::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// read the settings from local storage. missing or corrupt data yields the defaults,
+    /// in the browser's language
+    pub fn load() -> Self {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_else(|_| Settings {
+            locale: Locale::detect(),
+            ..Default::default()
+        })
+    }
+
+    /// whether the user has saved settings of their own
+    pub fn saved() -> bool {
+        LocalStorage::get::<Settings>(STORAGE_KEY).is_ok()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        LocalStorage::set(STORAGE_KEY, self).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SettingsPanelProps {
+    pub settings: Settings,
+    pub on_change: Callback<Settings>,
+}
+
+/// form editing a copy of the settings, emitting the whole updated value on each change
+pub struct SettingsPanel;
+
+impl SettingsPanel {
+    fn text_field(
+        ctx: &Context<Self>,
+        label: &str,
+        placeholder: &str,
+        value: &str,
+        apply: fn(&mut Settings, String),
+    ) -> Html {
+        let settings = ctx.props().settings.clone();
+        let on_change = ctx.props().on_change.clone();
+        let oninput = Callback::from(move |e: InputEvent| {
+            let mut settings = settings.clone();
+            apply(
+                &mut settings,
+                e.target_unchecked_into::<HtmlInputElement>().value(),
+            );
+            on_change.emit(settings);
+        });
+
+        html! {
+            <label style="display:flex; flex-direction:column; gap:2px; margin-bottom:8px;">
+                <span>{label}</span>
+                <input value={value.to_string()} placeholder={placeholder.to_string()} {oninput} />
+            </label>
+        }
+    }
+}
+
+impl Component for SettingsPanel {
+    type Message = ();
+    type Properties = SettingsPanelProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let settings = &ctx.props().settings;
+        let on_encoding_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |e: Event| {
+                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                on_change.emit(Settings {
+                    link_encoding: LinkEncoding::from_id(&select.value()).unwrap_or_default(),
+                    ..settings.clone()
+                });
+            })
+        };
+
+        let on_locale_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |e: Event| {
+                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                on_change.emit(Settings {
+                    locale: Locale::from_id(&select.value()).unwrap_or_default(),
+                    ..settings.clone()
+                });
+            })
+        };
+        let on_skip_invalid_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |_: Event| {
+                on_change.emit(Settings {
+                    skip_invalid_patterns: !settings.skip_invalid_patterns,
+                    ..settings.clone()
+                });
+            })
+        };
+        let on_auto_run_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |_: Event| {
+                on_change.emit(Settings {
+                    auto_run: !settings.auto_run,
+                    ..settings.clone()
+                });
+            })
+        };
+        let on_trailing_whitespace_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |_: Event| {
+                on_change.emit(Settings {
+                    ignore_trailing_whitespace: !settings.ignore_trailing_whitespace,
+                    ..settings.clone()
+                });
+            })
+        };
+        let on_scrub_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |_: Event| {
+                on_change.emit(Settings {
+                    scrub_shared_links: !settings.scrub_shared_links,
+                    ..settings.clone()
+                });
+            })
+        };
+        let on_hook_input = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |e: InputEvent| {
+                on_change.emit(Settings {
+                    result_hook: e.target_unchecked_into::<HtmlTextAreaElement>().value(),
+                    ..settings.clone()
+                });
+            })
+        };
+        let on_context_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |e: Event| {
+                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                on_change.emit(Settings {
+                    context_lines: select.value().parse().unwrap_or_default(),
+                    ..settings.clone()
+                });
+            })
+        };
+        let on_compression_change = {
+            let settings = settings.clone();
+            let on_change = ctx.props().on_change.clone();
+            Callback::from(move |e: Event| {
+                let select = e.target_unchecked_into::<HtmlSelectElement>();
+                on_change.emit(Settings {
+                    compression: CompressionLevel::from_id(&select.value()).unwrap_or_default(),
+                    ..settings.clone()
+                });
+            })
+        };
+
+        html! {
+            <div style="
+                position:absolute;
+                top:100%;
+                right:0;
+                z-index:10;
+                width:360px;
+                background:#2d2d2d;
+                border:1px solid #444;
+                padding:8px;
+            ">
+                <label style="display:flex; flex-direction:column; gap:2px; margin-bottom:8px;">
+                    <span>{ tr!("ui-language") }</span>
+                    <select onchange={on_locale_change}>
+                        { for Locale::ALL.into_iter().map(|locale| html! {
+                            <option value={locale.id()} selected={settings.locale == locale}>
+                                { locale.label() }
+                            </option>
+                        })}
+                    </select>
+                </label>
+                { Self::text_field(
+                    ctx,
+                    &tr!("shortener-endpoint"),
+                    &tr!("shortener-placeholder"),
+                    &settings.shortener_url,
+                    |s, v| s.shortener_url = v,
+                ) }
+                { Self::text_field(
+                    ctx,
+                    &tr!("rule-pack-index"),
+                    DEFAULT_INDEX_URL,
+                    &settings.rule_pack_index,
+                    |s, v| s.rule_pack_index = v,
+                ) }
+                { Self::text_field(
+                    ctx,
+                    &tr!("max-highlights"),
+                    &DEFAULT_MAX_HIGHLIGHTS.to_string(),
+                    &settings.max_highlights.to_string(),
+                    |s, v| {
+                        if let Ok(n) = v.trim().parse::<usize>() {
+                            s.max_highlights = n.max(1);
+                        }
+                    },
+                ) }
+                { Self::text_field(
+                    ctx,
+                    &tr!("run-timeout"),
+                    &DEFAULT_RUN_TIMEOUT_SECS.to_string(),
+                    &settings.run_timeout_secs.to_string(),
+                    |s, v| {
+                        if let Ok(n) = v.trim().parse::<u32>() {
+                            s.run_timeout_secs = n;
+                        }
+                    },
+                ) }
+                { Self::text_field(
+                    ctx,
+                    &tr!("run-slice-tokens"),
+                    "0",
+                    &settings.run_slice_tokens.to_string(),
+                    |s, v| {
+                        if let Ok(n) = v.trim().parse::<usize>() {
+                            s.run_slice_tokens = n;
+                        }
+                    },
+                ) }
+                <label style="display:flex; flex-direction:column; gap:2px; margin-bottom:8px;"
+                    title={tr!("context-lines.title")}>
+                    <span>{ tr!("context-lines") }</span>
+                    <select onchange={on_context_change}>
+                        { for (0..=MAX_CONTEXT_LINES).map(|n| html! {
+                            <option value={n.to_string()} selected={settings.context_lines == n}>
+                                { n }
+                            </option>
+                        })}
+                    </select>
+                </label>
+                <label style="display:flex; gap:4px; align-items:center; margin-bottom:8px;"
+                    title={tr!("skip-invalid-patterns.title")}>
+                    <input type="checkbox" checked={settings.skip_invalid_patterns}
+                        onchange={on_skip_invalid_change} />
+                    { tr!("skip-invalid-patterns") }
+                </label>
+                <label style="display:flex; gap:4px; align-items:center; margin-bottom:8px;"
+                    title={tr!("auto-run.title")}>
+                    <input type="checkbox" checked={settings.auto_run}
+                        onchange={on_auto_run_change} />
+                    { tr!("auto-run") }
+                </label>
+                <label style="display:flex; gap:4px; align-items:center; margin:0 0 8px 20px;"
+                    title={tr!("ignore-trailing-whitespace.title")}>
+                    <input type="checkbox" checked={settings.ignore_trailing_whitespace}
+                        disabled={!settings.auto_run}
+                        onchange={on_trailing_whitespace_change} />
+                    { tr!("ignore-trailing-whitespace") }
+                </label>
+                <label style="display:flex; flex-direction:column; gap:2px; margin-bottom:8px;">
+                    <span>{ tr!("link-encoding") }</span>
+                    <select onchange={on_encoding_change}>
+                        { for LinkEncoding::ALL.into_iter().map(|encoding| html! {
+                            <option value={encoding.id()} selected={settings.link_encoding == encoding}>
+                                { encoding.label() }
+                            </option>
+                        })}
+                    </select>
+                </label>
+                <label style="display:flex; gap:4px; align-items:center; margin-bottom:8px;"
+                    title={tr!("scrub-shared-links.title")}>
+                    <input type="checkbox" checked={settings.scrub_shared_links}
+                        onchange={on_scrub_change} />
+                    { tr!("scrub-shared-links") }
+                </label>
+                <label style="display:flex; flex-direction:column; gap:2px; margin-bottom:8px;">
+                    <span>{ tr!("link-compression") }</span>
+                    <select onchange={on_compression_change}>
+                        { for CompressionLevel::ALL.into_iter().map(|level| html! {
+                            <option value={level.id()} selected={settings.compression == level}>
+                                { level.label() }
+                            </option>
+                        })}
+                    </select>
+                </label>
+                <label style="display:flex; flex-direction:column; gap:2px;"
+                    title={tr!("result-hook.title")}>
+                    <span>{ tr!("result-hook") }</span>
+                    <textarea rows="3" spellcheck="false" style="font-family:monospace;"
+                        placeholder={tr!("result-hook-placeholder")}
+                        value={settings.result_hook.clone()}
+                        oninput={on_hook_input} />
+                </label>
+            </div>
+        }
+    }
+}
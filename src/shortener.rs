@@ -0,0 +1,65 @@
+//! client for an optional link shortening backend.
+//!
+//! `POST <endpoint>` with the encoded config as a plain-text body must answer
+//! `{"id": "<id>"}`, and `GET <endpoint>/<id>` must return the same encoded config. ids are
+//! made of `[A-Za-z0-9_-]`, since they go into the url path as they are.
+
+use gloo::net::http::Request;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct ShortenResponse {
+    id: String,
+}
+
+/// whether `id` can be a short id, which keeps it within `<endpoint>/`
+pub fn is_short_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// store the encoded config, returning its short id
+pub async fn shorten(endpoint: &str, encoded: String) -> Result<String, String> {
+    let response = Request::post(endpoint)
+        .header("Content-Type", "text/plain")
+        .body(encoded)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!(
+            "shortener responded {} {}",
+            response.status(),
+            response.status_text()
+        ));
+    }
+    let response: ShortenResponse = response.json().await.map_err(|e| e.to_string())?;
+    if !is_short_id(&response.id) {
+        return Err(format!(
+            "shortener answered an invalid id {:?}",
+            response.id
+        ));
+    }
+    Ok(response.id)
+}
+
+/// fetch the encoded config stored under the short id
+pub async fn resolve(endpoint: &str, id: &str) -> Result<String, String> {
+    if !is_short_id(id) {
+        return Err(format!("invalid short id {:?}", id));
+    }
+    let url = format!("{}/{}", endpoint.trim_end_matches('/'), id);
+    let response = Request::get(&url).send().await.map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!(
+            "shortener responded {} {}",
+            response.status(),
+            response.status_text()
+        ));
+    }
+    let encoded = response.text().await.map_err(|e| e.to_string())?;
+    Ok(encoded.trim().to_string())
+}
@@ -0,0 +1,107 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::SearchMatch;
+
+/// bumped if the snapshot layout changes
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// one match in a snapshot. fields are ordered so the derived `Ord` sorts by position first
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct SnapshotMatch {
+    pub file: String,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+    pub name: String,
+    pub captures: BTreeMap<String, String>,
+}
+
+/// every match of a run in a deterministic order, meant to be committed as a golden file
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Snapshot {
+    pub version: u32,
+    pub matches: Vec<SnapshotMatch>,
+}
+
+impl Snapshot {
+    /// `files` are the file names, indexed like `SearchMatch::file`
+    pub fn new(matches: &[SearchMatch], files: &[String]) -> Self {
+        let mut matches: Vec<SnapshotMatch> = matches
+            .iter()
+            .map(|m| SnapshotMatch {
+                file: files.get(m.file).cloned().unwrap_or_default(),
+                start_line: m.start_line,
+                start_col: m.start_col,
+                end_line: m.end_line,
+                end_col: m.end_col,
+                name: m.name.clone(),
+                captures: m.captures.clone(),
+            })
+            .collect();
+        matches.sort();
+        Self {
+            version: SNAPSHOT_VERSION,
+            matches,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut s = serde_json::to_string_pretty(self).expect("snapshot serializes");
+        s.push('\n');
+        s
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, String> {
+        let mut snapshot: Snapshot =
+            serde_json::from_str(s).map_err(|e| format!("invalid snapshot: {}", e))?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(format!(
+                "unsupported snapshot version {} (expected {})",
+                snapshot.version, SNAPSHOT_VERSION
+            ));
+        }
+        // hand edited snapshots may be out of order
+        snapshot.matches.sort();
+        Ok(snapshot)
+    }
+}
+
+/// the difference between a golden snapshot and the current run
+pub struct SnapshotDiff {
+    /// in the golden snapshot but not produced anymore
+    pub missing: Vec<SnapshotMatch>,
+    /// produced now but not in the golden snapshot
+    pub added: Vec<SnapshotMatch>,
+}
+
+impl SnapshotDiff {
+    pub fn new(golden: &Snapshot, current: &Snapshot) -> Self {
+        let mut missing = Vec::new();
+        let mut added = Vec::new();
+        let mut golden = golden.matches.iter().peekable();
+        let mut current = current.matches.iter().peekable();
+        loop {
+            match (golden.peek(), current.peek()) {
+                (Some(g), Some(c)) => match g.cmp(c) {
+                    Ordering::Equal => {
+                        golden.next();
+                        current.next();
+                    }
+                    Ordering::Less => missing.push(golden.next().unwrap().clone()),
+                    Ordering::Greater => added.push(current.next().unwrap().clone()),
+                },
+                (Some(_), None) => missing.push(golden.next().unwrap().clone()),
+                (None, Some(_)) => added.push(current.next().unwrap().clone()),
+                (None, None) => break,
+            }
+        }
+        Self { missing, added }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.added.is_empty()
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use gloo::storage::{LocalStorage, Storage};
+
+const STORAGE_KEY: &str = "lexer-search-ui.snippets";
+
+/// the user's saved snippets, name -> encoded `PlaygroundConfig`
+#[derive(Default)]
+pub struct SnippetLibrary {
+    snippets: BTreeMap<String, String>,
+}
+
+impl SnippetLibrary {
+    /// read the library from local storage. missing or corrupt data yields an empty library
+    pub fn load() -> Self {
+        Self {
+            snippets: LocalStorage::get(STORAGE_KEY).unwrap_or_default(),
+        }
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        LocalStorage::set(STORAGE_KEY, &self.snippets).map_err(|e| e.to_string())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.snippets.keys()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snippets.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.snippets.get(name).map(|s| s.as_str())
+    }
+
+    /// insert or overwrite a snippet
+    pub fn save(&mut self, name: String, encoded: String) -> Result<(), String> {
+        self.snippets.insert(name, encoded);
+        self.persist()
+    }
+
+    pub fn rename(&mut self, from: &str, to: String) -> Result<(), String> {
+        if self.snippets.contains_key(&to) {
+            return Err(format!("a snippet named \"{}\" already exists", to));
+        }
+        let encoded = self
+            .snippets
+            .remove(from)
+            .ok_or_else(|| format!("no snippet named \"{}\"", from))?;
+        self.snippets.insert(to, encoded);
+        self.persist()
+    }
+
+    /// copy a snippet under the first free "<name> (copy N)" name
+    pub fn duplicate(&mut self, name: &str) -> Result<(), String> {
+        let encoded = self
+            .snippets
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("no snippet named \"{}\"", name))?;
+        let mut copy_name = format!("{} (copy)", name);
+        let mut n = 2;
+        while self.snippets.contains_key(&copy_name) {
+            copy_name = format!("{} (copy {})", name, n);
+            n += 1;
+        }
+        self.snippets.insert(copy_name, encoded);
+        self.persist()
+    }
+
+    pub fn delete(&mut self, name: &str) -> Result<(), String> {
+        self.snippets.remove(name);
+        self.persist()
+    }
+}
@@ -0,0 +1,485 @@
+//! scanning a local file too large for the editor. the file is read in chunks through the
+//! File API and each chunk is matched on its own, so only one chunk is ever in the wasm
+//! heap. chunks end at a line break, which means a match can't span two of them. a line
+//! longer than a chunk is cut between tokens instead
+//!
+//! the editor subjects can be matched the same way, a slice per frame, so a long run
+//! doesn't freeze the page. slices end at a line break outside comments and strings and
+//! are matched with the lines after them, so a match starting in one is found whole unless
+//! it is longer than `SLICE_OVERLAP_LINES`
+
+use std::{
+    cell::Cell,
+    collections::{BTreeSet, VecDeque},
+    rc::Rc,
+    time::Duration,
+};
+
+use gloo::file::{Blob, File};
+use lexer_search_lib::io::Language;
+
+use crate::{
+    api::SearchMatch,
+    io::{
+        CompiledPatterns, Deadline, MatchCaps, MatchingUnit, PlaygroundConfig, PlaygroundError,
+        engine_tokens,
+    },
+    positions::ColumnMap,
+};
+
+/// bytes read per step
+pub const CHUNK_BYTES: u64 = 8 * 1024 * 1024;
+
+/// lines a slice is matched past its end. a match is reported by the slice it starts in,
+/// so the ones starting in the lines after it are left for the next slice
+const SLICE_OVERLAP_LINES: usize = 200;
+
+/// what a chunk produced, with the progress so far
+pub struct ScanProgress {
+    pub matches: Vec<SearchMatch>,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// units that reached their `max_matches`, over the whole file
+    pub truncated: BTreeSet<String>,
+}
+
+/// how much of `carry` to match now: up to its last line break, or all of it when `last`.
+/// without a line break, more than `max` bytes are cut after the last whitespace in the
+/// last `max` bytes, or else at the last character boundary. none to wait for more
+fn cut_point(carry: &[u8], last: bool, max: usize) -> Option<usize> {
+    if last {
+        return Some(carry.len());
+    }
+    if let Some(i) = carry.iter().rposition(|&b| b == b'\n') {
+        return Some(i + 1);
+    }
+    if carry.len() <= max {
+        return None;
+    }
+    let tail = carry.len() - max;
+    match carry[tail..].iter().rposition(u8::is_ascii_whitespace) {
+        Some(i) => Some(tail + i + 1),
+        // not within a UTF-8 sequence
+        None => (tail..carry.len())
+            .rev()
+            .find(|&i| carry[i] & 0xc0 != 0x80)
+            .or(Some(carry.len())),
+    }
+}
+
+/// matches chunks of one subject, carrying the unfinished last line of each chunk over to
+/// the next and numbering lines across chunks
+pub struct ChunkScanner {
+    language: Language,
+    compiled: CompiledPatterns,
+    /// reported as `SearchMatch::file`
+    file: usize,
+    carry: Vec<u8>,
+    lines_done: usize,
+    /// UTF-16 columns of the current line matched in earlier chunks, when one was cut
+    /// inside a line
+    columns_done: usize,
+    /// `max_matches` over the whole file
+    caps: MatchCaps,
+}
+
+impl ChunkScanner {
+    /// compile the lhs of `cfg` for its language; its subject and files are ignored
+    pub fn new(
+        mut cfg: PlaygroundConfig,
+        skip_invalid: bool,
+        file: usize,
+    ) -> Result<Self, PlaygroundError> {
+        cfg.subject.clear();
+        cfg.files.clear();
+        let compiled = cfg.compile(skip_invalid)?;
+        Ok(Self::with_compiled(&cfg.lhs, cfg.language, compiled, file))
+    }
+
+    /// scan with patterns compiled earlier, which have to cover `language`
+    fn with_compiled(
+        lhs: &[MatchingUnit],
+        language: Language,
+        compiled: CompiledPatterns,
+        file: usize,
+    ) -> Self {
+        Self {
+            language,
+            compiled,
+            file,
+            carry: Vec::new(),
+            lines_done: 0,
+            columns_done: 0,
+            caps: MatchCaps::new(lhs),
+        }
+    }
+
+    /// go on with the next file, starting at its first line. `max_matches` keeps counting
+    /// across files, as in a run
+    fn next_file(&mut self, language: Language) {
+        self.language = language;
+        self.file += 1;
+        self.carry.clear();
+        self.lines_done = 0;
+        self.columns_done = 0;
+    }
+
+    /// match the complete lines of `bytes` (everything when `last`), keeping the rest for
+    /// the next call. once what is kept exceeds `CHUNK_BYTES` it is cut at the last
+    /// whitespace instead, or inside a token if there is none in the last `CHUNK_BYTES`.
+    /// matching stops where `deadline` passes
+    pub fn feed(
+        &mut self,
+        bytes: &[u8],
+        last: bool,
+        deadline: &Deadline,
+    ) -> Result<Vec<SearchMatch>, PlaygroundError> {
+        self.carry.extend_from_slice(bytes);
+        let Some(end) = cut_point(&self.carry, last, CHUNK_BYTES as usize) else {
+            return Ok(Vec::new());
+        };
+        let rest = self.carry.split_off(end);
+        let chunk = std::mem::replace(&mut self.carry, rest);
+        let text = match String::from_utf8(chunk) {
+            Ok(text) => text,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        };
+        self.scan_text(text, None, deadline)
+    }
+
+    /// match `text`, going on from the lines before it. with `own_lines`, only matches
+    /// starting in its first `own_lines` lines are reported and the next call starts after
+    /// them, the rest being matched again then
+    fn scan_text(
+        &mut self,
+        text: String,
+        own_lines: Option<usize>,
+        deadline: &Deadline,
+    ) -> Result<Vec<SearchMatch>, PlaygroundError> {
+        let lines = own_lines.unwrap_or_else(|| text.matches('\n').count());
+        let columns = ColumnMap::new(&text);
+        let columns_after = match (own_lines, text.rsplit_once('\n')) {
+            (Some(_), _) => 0,
+            (None, Some((_, tail))) => tail.encode_utf16().count(),
+            (None, None) => self.columns_done + text.encode_utf16().count(),
+        };
+
+        let mut matches = Vec::new();
+        let (file, lines_done, columns_done) = (self.file, self.lines_done, self.columns_done);
+        let caps = &mut self.caps;
+        self.compiled
+            .scan(&self.language, text, deadline, |unit, m| {
+                if own_lines.is_some_and(|own| m.start.line > own) {
+                    return;
+                }
+                if let Some(m) = caps.admit(unit, m) {
+                    let mut m = SearchMatch::from_postprocessed(file, m);
+                    columns.convert(&mut m);
+                    if m.start_line == 1 {
+                        m.start_col += columns_done;
+                    }
+                    if m.end_line == 1 {
+                        m.end_col += columns_done;
+                    }
+                    m.start_line += lines_done;
+                    m.end_line += lines_done;
+                    matches.push(m);
+                }
+            })?;
+        self.lines_done += lines;
+        self.columns_done = columns_after;
+        Ok(matches)
+    }
+
+    pub fn truncated(&self) -> &BTreeSet<String> {
+        self.caps.truncated()
+    }
+}
+
+/// about how many tokens the lexer makes of `text`: each run of word characters, and every
+/// other character that isn't whitespace on its own
+fn rough_tokens(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        let word = c.is_alphanumeric() || c == '_';
+        if (word && !in_word) || (!word && !c.is_whitespace()) {
+            count += 1;
+        }
+        in_word = word;
+    }
+    count
+}
+
+/// the bytes of the first `SLICE_OVERLAP_LINES` lines of `text`
+fn overlap_len(text: &str) -> usize {
+    text.split_inclusive('\n')
+        .take(SLICE_OVERLAP_LINES)
+        .map(str::len)
+        .sum()
+}
+
+/// the line end in `text` nearest before `target`, or else after it, where the lexer is
+/// between tokens and not inside a comment. `text` starts at such a point. none if there is
+/// no such line end or `text` doesn't lex
+fn top_level_line_end(language: &Language, text: &str, target: usize) -> Option<usize> {
+    let tokens = engine_tokens(language, text, false).ok()?;
+    // what lies between tokens is whitespace and comments. the last gap may go on past
+    // `text`, so it is only known to be whitespace up to its first comment
+    let mut gaps = Vec::with_capacity(tokens.len() + 1);
+    let mut prev = 0;
+    for (start, end) in tokens {
+        gaps.push((prev, start, true));
+        prev = end;
+    }
+    gaps.push((prev, text.len(), false));
+    let blank = |s: &str| s.chars().all(char::is_whitespace);
+    let top_level = |p: usize| {
+        gaps.iter().any(|&(start, end, closed)| {
+            (start..=end).contains(&p)
+                && (blank(&text[start..p]) || (closed && blank(&text[p..end])))
+        })
+    };
+    let line_ends: Vec<usize> = text
+        .match_indices('\n')
+        .map(|(i, _)| i + 1)
+        .filter(|&p| p < text.len() && top_level(p))
+        .collect();
+    line_ends
+        .iter()
+        .rev()
+        .find(|&&p| p <= target)
+        .or_else(|| line_ends.iter().find(|&&p| p > target))
+        .copied()
+}
+
+/// a run over the subjects of a config that matches about `budget` tokens per `step`, so
+/// the caller can yield to the browser in between. slices end at a line break outside
+/// comments and strings, and are matched together with the `SLICE_OVERLAP_LINES` lines
+/// after them
+pub struct SlicedRun {
+    scanner: ChunkScanner,
+    /// the subject being matched
+    current: String,
+    /// bytes of `current` matched so far
+    offset: usize,
+    /// language and text of the subjects after it
+    pending: VecDeque<(Language, String)>,
+    budget: usize,
+    finished: bool,
+    pub tokens_done: usize,
+    pub tokens_total: usize,
+}
+
+impl SlicedRun {
+    /// `compiled` has to come from `cfg`'s lhs
+    pub fn new(cfg: PlaygroundConfig, compiled: CompiledPatterns, budget: usize) -> Self {
+        let tokens_total = std::iter::once(cfg.subject.as_str())
+            .chain(cfg.files.iter().map(|f| f.content.as_str()))
+            .map(rough_tokens)
+            .sum();
+        let scanner = ChunkScanner::with_compiled(&cfg.lhs, cfg.language, compiled, 0);
+        Self {
+            scanner,
+            current: cfg.subject,
+            offset: 0,
+            pending: cfg
+                .files
+                .into_iter()
+                .map(|f| (f.language, f.content))
+                .collect(),
+            budget: budget.max(1),
+            finished: false,
+            tokens_done: 0,
+            tokens_total,
+        }
+    }
+
+    /// whether every subject has been matched
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// match the next slice, returning its matches with UTF-16 columns
+    pub fn step(&mut self, deadline: &Deadline) -> Result<Vec<SearchMatch>, PlaygroundError> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        let rest = &self.current[self.offset..];
+        let (mut len, mut tokens) = (0, 0);
+        for line in rest.split_inclusive('\n') {
+            len += line.len();
+            tokens += rough_tokens(line);
+            if tokens >= self.budget {
+                break;
+            }
+        }
+        if len < rest.len() {
+            // the next slice starts a fresh lexer, which would read the tail of a comment or
+            // string as code
+            let window = len + overlap_len(&rest[len..]);
+            len = top_level_line_end(&self.scanner.language, &rest[..window], len)
+                .unwrap_or(rest.len());
+            tokens = rough_tokens(&rest[..len]);
+        }
+        let last = len == rest.len();
+        let matches = if last {
+            self.scanner.scan_text(rest.to_string(), None, deadline)?
+        } else {
+            let overlap = overlap_len(&rest[len..]);
+            let own_lines = rest[..len].matches('\n').count();
+            let text = rest[..len + overlap].to_string();
+            self.scanner.scan_text(text, Some(own_lines), deadline)?
+        };
+        self.offset += len;
+        self.tokens_done += tokens;
+        if last {
+            match self.pending.pop_front() {
+                Some((language, text)) => {
+                    self.scanner.next_file(language);
+                    self.current = text;
+                    self.offset = 0;
+                }
+                None => self.finished = true,
+            }
+        }
+        Ok(matches)
+    }
+
+    /// the compiled patterns back, and the units that reached their `max_matches`
+    pub fn into_parts(self) -> (CompiledPatterns, BTreeSet<String>) {
+        (self.scanner.compiled, self.scanner.caps.into_truncated())
+    }
+}
+
+/// read `file` chunk by chunk into `scanner`, handing each chunk's matches to `on_progress`.
+/// the event loop gets a turn between chunks; setting `cancel` stops after the current one.
+/// resolves to whether `deadline` passed before the end of the file
+pub async fn scan_file(
+    file: File,
+    mut scanner: ChunkScanner,
+    cancel: Rc<Cell<bool>>,
+    deadline: Deadline,
+    mut on_progress: impl FnMut(ScanProgress),
+) -> Result<bool, PlaygroundError> {
+    let total = file.size();
+    let blob: &Blob = &file;
+    let mut done = 0;
+    loop {
+        if cancel.get() {
+            return Ok(false);
+        }
+        let end = (done + CHUNK_BYTES).min(total);
+        let bytes = gloo::file::futures::read_as_bytes(&blob.slice(done, end))
+            .await
+            .map_err(|e| e.to_string())?;
+        let last = end >= total;
+        let matches = scanner.feed(&bytes, last, &deadline)?;
+        drop(bytes);
+        done = end;
+        on_progress(ScanProgress {
+            matches,
+            bytes_done: done,
+            bytes_total: total,
+            truncated: scanner.truncated().clone(),
+        });
+        if last || deadline.check() {
+            return Ok(deadline.expired());
+        }
+        yew::platform::time::sleep(Duration::ZERO).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::search_compiled;
+
+    const LHS: &str = "- name: hi\n  patterns:\n    - hello_world($X)\n";
+
+    /// where the matches are, in a stable order
+    fn positions(matches: &[SearchMatch]) -> Vec<(usize, String, usize, usize, usize, usize)> {
+        let mut positions: Vec<_> = matches
+            .iter()
+            .map(|m| {
+                (
+                    m.file,
+                    m.name.clone(),
+                    m.start_line,
+                    m.start_col,
+                    m.end_line,
+                    m.end_col,
+                )
+            })
+            .collect();
+        positions.sort();
+        positions
+    }
+
+    #[test]
+    fn slices_match_like_a_run_across_a_block_comment() {
+        let subject = "hello_world(1);\n/* a comment\nhello_world(2);\nstill the comment */\nhello_world(3);\n";
+        let cfg = || PlaygroundConfig::from_editor_parts(subject, "rust", LHS).unwrap();
+        let compiled = cfg().compile(false).unwrap();
+        let (expected, _) = search_compiled(cfg(), &compiled, &Deadline::none()).unwrap();
+        assert_eq!(expected.len(), 2);
+
+        // about a line per slice, so one would start inside the comment
+        let mut run = SlicedRun::new(cfg(), compiled, 1);
+        let mut sliced = Vec::new();
+        while !run.finished() {
+            sliced.extend(run.step(&Deadline::none()).unwrap());
+        }
+        assert_eq!(positions(&sliced), positions(&expected));
+    }
+
+    #[test]
+    fn cut_at_line_breaks() {
+        assert_eq!(cut_point(b"a\nb\nc", false, 4), Some(4));
+        assert_eq!(cut_point(b"abc", false, 4), None);
+        assert_eq!(cut_point(b"abc", true, 4), Some(3));
+    }
+
+    #[test]
+    fn long_lines_cut_between_tokens() {
+        assert_eq!(cut_point(b"ab cd ef", false, 4), Some(6));
+        // whitespace only before the last `max` bytes doesn't count
+        assert_eq!(cut_point(b"a bcdefg", false, 4), Some(7));
+        // before the last character, not inside it
+        let text = "aaéé".as_bytes();
+        assert_eq!(cut_point(text, false, 4), Some(4));
+        assert!(std::str::from_utf8(&text[..4]).is_ok());
+    }
+
+    #[test]
+    fn columns_go_on_across_a_cut_line() {
+        let cfg = PlaygroundConfig::from_editor_parts("", "rust", LHS).unwrap();
+        let mut scanner = ChunkScanner::new(cfg, false, 0).unwrap();
+        let padding = CHUNK_BYTES as usize + 10;
+        let deadline = Deadline::none();
+        // whitespace alone, so matching it is quick; the carry has to be cut
+        let mut matches = scanner
+            .feed(" ".repeat(padding).as_bytes(), false, &deadline)
+            .unwrap();
+        matches.extend(
+            scanner
+                .feed(b"  hello_world(1);\nhello_world(2);\n", true, &deadline)
+                .unwrap(),
+        );
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.start_line, m.start_col, m.end_line))
+            .collect();
+        assert_eq!(found, [(1, padding + 3, 1), (2, 1, 2)]);
+    }
+
+    #[test]
+    fn slice_ends_outside_comments() {
+        let language = serde_yml::from_str("rust").unwrap();
+        let text = "a;\n/* b\nc;\n*/\nd;\n";
+        // the line ends at 8 and 11 are inside the comment
+        assert_eq!(top_level_line_end(&language, text, 11), Some(3));
+        assert_eq!(top_level_line_end(&language, text, 14), Some(14));
+        assert_eq!(top_level_line_end(&language, text, 2), Some(3));
+    }
+}
@@ -0,0 +1,80 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::io::{HighlightStyle, MatchingUnit};
+
+#[wasm_bindgen(module = "/src/highlight_helper.js")]
+extern "C" {
+    fn ensure_style_class_js(class_name: &str, properties: &JsValue);
+}
+
+/// css classes for the units that define a `style`, keyed by unit name
+#[derive(Default)]
+pub struct UnitStyles {
+    classes: HashMap<String, String>,
+}
+
+impl UnitStyles {
+    /// register a css class per distinct style. matches only carry their unit's name, so
+    /// when several units share a name the first one's style wins
+    pub fn new(units: &[MatchingUnit]) -> Self {
+        let mut classes = HashMap::new();
+        for unit in units.iter().filter(|u| !u.style.is_default()) {
+            if classes.contains_key(&unit.name) {
+                continue;
+            }
+            let class_name = style_class(&unit.style);
+            classes.insert(unit.name.clone(), class_name);
+        }
+        Self { classes }
+    }
+
+    /// `base` plus the unit's own class, if it has one
+    pub fn class_for(&self, base: &str, unit_name: &str) -> String {
+        match self.classes.get(unit_name) {
+            Some(class) => format!("{} {}", base, class),
+            None => base.to_owned(),
+        }
+    }
+}
+
+/// class for the left border marking a unit in the pattern editor, colored like its matches
+pub fn unit_border_class(style: &HighlightStyle) -> String {
+    let border = if !style.background.is_empty() {
+        format!("3px solid {}", style.background)
+    } else if !style.border.is_empty() {
+        style.border.clone()
+    } else {
+        // unstyled, or a text decoration only: the default highlight color
+        return "unit-border".to_owned();
+    };
+
+    let mut hasher = DefaultHasher::new();
+    border.hash(&mut hasher);
+    let class_name = format!("unit-border-{:x}", hasher.finish());
+    let properties = Object::new();
+    let _ = Reflect::set(&properties, &"border-left".into(), &border.into());
+    ensure_style_class_js(&class_name, &properties);
+    class_name
+}
+
+/// the class for a style, registering its css rule on first use
+fn style_class(style: &HighlightStyle) -> String {
+    let mut hasher = DefaultHasher::new();
+    style.hash(&mut hasher);
+    let class_name = format!("match-style-{:x}", hasher.finish());
+
+    // values are applied through the CSSOM rather than as css text, so a shared link can
+    // only set these properties and not inject arbitrary rules
+    let properties = Object::new();
+    for (property, value) in style.properties() {
+        let _ = Reflect::set(&properties, &property.into(), &value.into());
+    }
+    ensure_style_class_js(&class_name, &properties);
+    class_name
+}
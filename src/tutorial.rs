@@ -0,0 +1,208 @@
+//! guided tour for writing a first rule. each step checks the editors before moving on
+
+use yew::{Callback, Component, Context, Html, MouseEvent, Properties, html};
+
+use crate::{
+    i18n::tr,
+    io::{MatchingUnit, parse_lhs},
+    results::RunResults,
+};
+
+/// lhs the tour starts from
+pub const TUTORIAL_LHS: &str = "# your first rule goes here\n";
+/// rust subject the tour starts from
+pub const TUTORIAL_SUBJECT: &str = r#"fn main() {
+    let key = read_key("secret.pem");
+    let legacy = Cipher::new("des", &key);
+    let modern = Cipher::new("aes-256-gcm", &key);
+}
+"#;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TutorialStep {
+    Rule,
+    Capture,
+    Transform,
+    Run,
+    Done,
+}
+
+impl TutorialStep {
+    pub const ALL: [Self; 5] = [
+        Self::Rule,
+        Self::Capture,
+        Self::Transform,
+        Self::Run,
+        Self::Done,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Rule => "rule",
+            Self::Capture => "capture",
+            Self::Transform => "transform",
+            Self::Run => "run",
+            Self::Done => "done",
+        }
+    }
+
+    pub fn label(self) -> String {
+        tr!(&format!("tutorial-{}", self.id()))
+    }
+
+    /// what to do in this step
+    pub fn instructions(self) -> String {
+        tr!(&format!("tutorial-{}-help", self.id()))
+    }
+
+    pub fn next(self) -> Option<Self> {
+        let index = Self::ALL.iter().position(|&s| s == self)?;
+        Self::ALL.get(index + 1).copied()
+    }
+
+    /// whether the editors show this step done, with a hint if not
+    pub fn check(self, lhs: &str, results: &RunResults) -> Result<(), String> {
+        if self == Self::Done {
+            return Ok(());
+        }
+        let units = parse_lhs(lhs).map_err(|e| tr!("tutorial-invalid-yaml", error = e))?;
+        let rules: Vec<&MatchingUnit> = units
+            .iter()
+            .filter(|u| !u.name.is_empty() && u.patterns.iter().any(|p| !p.trim().is_empty()))
+            .collect();
+        if rules.is_empty() {
+            return Err(tr!("tutorial-rule-missing"));
+        }
+        let (done, missing) = match self {
+            Self::Rule | Self::Done => (true, ""),
+            Self::Capture => (
+                rules.iter().any(|u| !unit_metavariables(u).is_empty()),
+                "tutorial-capture-missing",
+            ),
+            Self::Transform => (
+                rules.iter().any(|u| {
+                    unit_metavariables(u)
+                        .into_iter()
+                        .any(|c| u.transform.get(c).is_some_and(|r| !r.is_empty()))
+                }),
+                "tutorial-transform-missing",
+            ),
+            Self::Run => (
+                results
+                    .matches
+                    .iter()
+                    .any(|m| rules.iter().any(|u| u.name == m.name)),
+                "tutorial-run-missing",
+            ),
+        };
+        if done { Ok(()) } else { Err(tr!(missing)) }
+    }
+}
+
+fn unit_metavariables(unit: &MatchingUnit) -> Vec<&str> {
+    unit.patterns
+        .iter()
+        .flat_map(|p| metavariables(p))
+        .collect()
+}
+
+/// names of the `$NAME` metavariables in `pattern`, without the `$`. `\$` is a literal
+fn metavariables(pattern: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'$' => {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len()
+                    && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_')
+                {
+                    end += 1;
+                }
+                if end > start && !bytes[start].is_ascii_digit() {
+                    names.push(&pattern[start..end]);
+                }
+                i = end.max(start);
+            }
+            _ => i += 1,
+        }
+    }
+    names
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TutorialPanelProps {
+    pub step: TutorialStep,
+    /// why the last check failed
+    #[prop_or_default]
+    pub hint: Option<String>,
+    pub on_next: Callback<()>,
+    pub on_close: Callback<()>,
+}
+
+/// card over the editors with the current step
+pub struct TutorialPanel;
+
+impl Component for TutorialPanel {
+    type Message = ();
+    type Properties = TutorialPanelProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let number = TutorialStep::ALL
+            .iter()
+            .position(|&s| s == props.step)
+            .unwrap_or(0)
+            + 1;
+        let done = props.step == TutorialStep::Done;
+        html! {
+            <div role="dialog" aria-label={tr!("tutorial")} style="
+                position:absolute;
+                right:16px;
+                bottom:40px;
+                z-index:20;
+                width:340px;
+                background:#252526;
+                color:#ccc;
+                border:1px solid #007acc;
+                border-radius:4px;
+                padding:10px 12px;
+                font-size:13px;
+                box-shadow:0 4px 16px rgba(0,0,0,0.5);
+            ">
+                <div style="display:flex; color:#888; font-size:11px;">
+                    <span>{ tr!("tutorial-progress", step = number, total = TutorialStep::ALL.len()) }</span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={props.on_close.reform(|_: MouseEvent| ())}>{"×"}</span>
+                </div>
+                <div style="font-weight:bold; margin:4px 0;">{ props.step.label() }</div>
+                <div style="white-space:pre-wrap;">{ props.step.instructions() }</div>
+                { props.hint.as_ref().map(|hint| html! {
+                    <div role="alert" style="color:#f48771; margin-top:6px;">{ hint.clone() }</div>
+                }) }
+                <div style="display:flex; justify-content:flex-end; margin-top:8px;">
+                    { if done {
+                        html! {
+                            <button onclick={props.on_close.reform(|_: MouseEvent| ())}>
+                                { tr!("tutorial-finish") }
+                            </button>
+                        }
+                    } else {
+                        html! {
+                            <button onclick={props.on_next.reform(|_: MouseEvent| ())}>
+                                { tr!("tutorial-next") }
+                            </button>
+                        }
+                    }}
+                </div>
+            </div>
+        }
+    }
+}
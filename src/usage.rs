@@ -0,0 +1,243 @@
+//! a log of the user's runs kept in local storage only, charted in the "Usage" panel. it
+//! is never sent anywhere
+
+use std::{collections::BTreeMap, rc::Rc};
+
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+use yew::{Callback, Component, Context, Html, MouseEvent, Properties, html};
+
+use crate::{i18n::tr, language_picker::language_label};
+
+const STORAGE_KEY: &str = "lexer-search-ui.usage";
+/// runs kept, the oldest are dropped first
+const MAX_ENTRIES: usize = 500;
+/// days shown in the runs per day chart
+const CHART_DAYS: i64 = 14;
+/// bars shown in the other charts
+const CHART_ROWS: usize = 10;
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// one run
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct UsageEntry {
+    /// `Date::now()` when it finished
+    pub at: f64,
+    /// monaco language id of the subject
+    pub language: String,
+    /// enabled rules that ran
+    pub rules: usize,
+    pub matches: usize,
+    pub duration_ms: f64,
+    /// matches by rule name, rules without any left out
+    #[serde(default)]
+    pub per_rule: BTreeMap<String, usize>,
+}
+
+#[derive(Default, Clone, PartialEq)]
+pub struct UsageLog {
+    entries: Vec<UsageEntry>,
+}
+
+impl UsageLog {
+    /// read the log from local storage. missing or corrupt data yields an empty log
+    pub fn load() -> Self {
+        Self {
+            entries: LocalStorage::get(STORAGE_KEY).unwrap_or_default(),
+        }
+    }
+
+    /// append a run and save. a full storage only loses the entry
+    pub fn record(&mut self, entry: UsageEntry) {
+        self.entries.push(entry);
+        let over = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..over);
+        let _ = LocalStorage::set(STORAGE_KEY, &self.entries);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        LocalStorage::delete(STORAGE_KEY);
+    }
+
+    /// runs by language, most first
+    fn runs_by_language(&self) -> Vec<(String, usize)> {
+        let mut runs: BTreeMap<&str, usize> = BTreeMap::new();
+        for entry in &self.entries {
+            *runs.entry(&entry.language).or_default() += 1;
+        }
+        most_first(
+            runs.into_iter()
+                .map(|(l, n)| (language_label(l).to_string(), n)),
+        )
+    }
+
+    /// matches by rule over all runs, most first
+    fn matches_by_rule(&self) -> Vec<(String, usize)> {
+        let mut matches: BTreeMap<&str, usize> = BTreeMap::new();
+        for (name, n) in self.entries.iter().flat_map(|e| &e.per_rule) {
+            *matches.entry(name).or_default() += n;
+        }
+        most_first(matches.into_iter().map(|(r, n)| (r.to_string(), n)))
+    }
+
+    /// runs on each of the last `CHART_DAYS` days, oldest first, labelled by day of month
+    fn runs_by_day(&self) -> Vec<(String, usize)> {
+        let today = local_day(js_sys::Date::now());
+        let mut runs = vec![0; CHART_DAYS as usize];
+        for entry in &self.entries {
+            let ago = today - local_day(entry.at);
+            if (0..CHART_DAYS).contains(&ago) {
+                runs[(CHART_DAYS - 1 - ago) as usize] += 1;
+            }
+        }
+        runs.into_iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let at = js_sys::Date::now() - (CHART_DAYS - 1 - i as i64) as f64 * DAY_MS;
+                let date = js_sys::Date::new(&JsValue::from_f64(at));
+                (date.get_date().to_string(), n)
+            })
+            .collect()
+    }
+}
+
+fn most_first(counts: impl Iterator<Item = (String, usize)>) -> Vec<(String, usize)> {
+    let mut counts: Vec<_> = counts.collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// days since the epoch in the user's time zone
+fn local_day(at: f64) -> i64 {
+    let offset_ms = js_sys::Date::new(&JsValue::from_f64(at)).get_timezone_offset() * 60_000.0;
+    ((at - offset_ms) / DAY_MS).floor() as i64
+}
+
+#[derive(Properties, PartialEq)]
+pub struct UsagePanelProps {
+    pub log: Rc<UsageLog>,
+    pub on_clear: Callback<()>,
+    pub on_close: Callback<()>,
+}
+
+/// charts of the usage log
+pub struct UsagePanel;
+
+impl UsagePanel {
+    fn view_bars(title: String, rows: &[(String, usize)]) -> Html {
+        let most = rows.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+        html! {
+            <section style="margin-top:10px;">
+                <div style="font-weight:bold; margin-bottom:4px;">{ title }</div>
+                <table style="border-collapse:collapse; width:100%;">
+                    { for rows.iter().take(CHART_ROWS).map(|(label, n)| html! {
+                        <tr>
+                            <td style="padding:0 8px 0 0; max-width:140px; overflow:hidden; text-overflow:ellipsis; white-space:nowrap;"
+                                title={label.clone()}>
+                                { label.clone() }
+                            </td>
+                            <td style="width:100%;">
+                                <div style={format!("height:8px; width:{}%; background:#007acc;", n * 100 / most)} />
+                            </td>
+                            <td style="padding-left:8px; text-align:right; color:#e8c547;">{ *n }</td>
+                        </tr>
+                    })}
+                </table>
+            </section>
+        }
+    }
+
+    fn view_days(rows: &[(String, usize)]) -> Html {
+        let most = rows.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+        html! {
+            <section style="margin-top:10px;">
+                <div style="font-weight:bold; margin-bottom:4px;">
+                    { tr!("usage-runs-by-day", days = CHART_DAYS) }
+                </div>
+                <div style="display:flex; align-items:flex-end; gap:2px; height:60px;">
+                    { for rows.iter().map(|(day, n)| html! {
+                        <div style="flex:1; display:flex; flex-direction:column; justify-content:flex-end; height:100%;"
+                            title={tr!("usage-day-runs", day = day.clone(), count = *n)}>
+                            <div style={format!("height:{}%; min-height:1px; background:#007acc;", n * 100 / most)} />
+                        </div>
+                    })}
+                </div>
+                <div style="display:flex; gap:2px; color:#888; font-size:10px;">
+                    { for rows.iter().map(|(day, _)| html! {
+                        <div style="flex:1; text-align:center;">{ day.clone() }</div>
+                    })}
+                </div>
+            </section>
+        }
+    }
+}
+
+impl Component for UsagePanel {
+    type Message = ();
+    type Properties = UsagePanelProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let entries = &props.log.entries;
+        let matches: usize = entries.iter().map(|e| e.matches).sum();
+        let average_ms = match entries.len() {
+            0 => 0.0,
+            n => entries.iter().map(|e| e.duration_ms).sum::<f64>() / n as f64,
+        };
+        html! {
+            <div role="dialog" aria-label={tr!("usage-heading")} style="
+                position:absolute;
+                right:16px;
+                bottom:40px;
+                z-index:20;
+                width:420px;
+                max-height:70vh;
+                overflow-y:auto;
+                background:#252526;
+                color:#ccc;
+                border:1px solid #444;
+                border-radius:4px;
+                padding:10px 12px;
+                font-size:12px;
+                box-shadow:0 4px 16px rgba(0,0,0,0.5);
+            ">
+                <div style="display:flex; align-items:center;">
+                    <span style="font-weight:bold;">{ tr!("usage-heading") }</span>
+                    <span style="margin-left:auto; cursor:pointer;" title={tr!("close")}
+                        onclick={props.on_close.reform(|_: MouseEvent| ())}>{"×"}</span>
+                </div>
+                <div style="color:#888;">{ tr!("usage-local-only") }</div>
+                { if entries.is_empty() {
+                    html! { <div style="margin-top:10px;">{ tr!("usage-empty") }</div> }
+                } else {
+                    html! {
+                        <>
+                            <div style="margin-top:10px;">
+                                { tr!(
+                                    "usage-totals",
+                                    runs = entries.len(),
+                                    matches = matches,
+                                    ms = format!("{:.0}", average_ms),
+                                ) }
+                            </div>
+                            { Self::view_days(&props.log.runs_by_day()) }
+                            { Self::view_bars(tr!("usage-runs-by-language"), &props.log.runs_by_language()) }
+                            { Self::view_bars(tr!("usage-matches-by-rule"), &props.log.matches_by_rule()) }
+                            <div style="display:flex; justify-content:flex-end; margin-top:10px;">
+                                <button onclick={props.on_clear.reform(|_: MouseEvent| ())}>
+                                    { tr!("usage-clear") }
+                                </button>
+                            </div>
+                        </>
+                    }
+                }}
+            </div>
+        }
+    }
+}
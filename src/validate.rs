@@ -0,0 +1,428 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use lexer_search_lib::lexer::{
+    DEFAULT_MAX_DISTINCT_GROUPS, DEFAULT_MAX_EXPANSIONS, DEFAULT_MAX_TOKEN_LENGTH,
+};
+use serde::Serialize;
+use serde_yml::Value;
+
+use crate::{io::is_web_url, tokens::tokenize};
+
+/// a step in the path to a YAML node
+#[derive(Clone)]
+enum Segment {
+    Index(usize),
+    Key(String),
+}
+
+fn path_string(path: &[Segment]) -> String {
+    let mut out = "lhs".to_string();
+    for segment in path {
+        match segment {
+            Segment::Index(i) => out.push_str(&format!("[{}]", i)),
+            Segment::Key(k) => {
+                out.push('.');
+                out.push_str(k);
+            }
+        }
+    }
+    out
+}
+
+/// a problem in the lhs YAML, at the 1-based position of the offending node
+#[derive(Serialize, PartialEq, Debug)]
+pub struct SchemaError {
+    pub path: String,
+    pub message: String,
+    pub line: usize,
+    pub col: usize,
+    /// accepted by the parser but likely a mistake, e.g. an unknown field
+    pub warning: bool,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Sequence(_) => "sequence",
+        Value::Mapping(_) => "map",
+        Value::Tagged(_) => "tagged value",
+    }
+}
+
+/// check the lhs YAML against the `MatchingUnit` schema field by field, unlike serde which
+/// stops at the first error and doesn't say where in the structure it is
+pub fn validate_lhs(yaml: &str) -> Vec<SchemaError> {
+    let root: Value = match serde_yml::from_str(yaml) {
+        Ok(v) => v,
+        Err(e) => {
+            let (line, col) = e
+                .location()
+                .map(|l| (l.line(), l.column()))
+                .unwrap_or((1, 1));
+            return vec![SchemaError {
+                path: "lhs".to_string(),
+                message: e.to_string(),
+                line,
+                col,
+                warning: false,
+            }];
+        }
+    };
+
+    let mut validator = Validator {
+        yaml,
+        errors: Vec::new(),
+    };
+    match &root {
+        Value::Sequence(units) => {
+            for (i, unit) in units.iter().enumerate() {
+                validator.unit(&[Segment::Index(i)], unit);
+            }
+            validator.cross_references(units);
+        }
+        other => validator.expected(&[], "sequence of rules", other),
+    }
+    validator.errors
+}
+
+struct Validator<'a> {
+    yaml: &'a str,
+    errors: Vec<SchemaError>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, path: &[Segment], message: String, warning: bool) {
+        let (line, col) = locate(self.yaml, path);
+        self.errors.push(SchemaError {
+            path: path_string(path),
+            message,
+            line,
+            col,
+            warning,
+        });
+    }
+
+    fn expected(&mut self, path: &[Segment], expected: &str, found: &Value) {
+        self.report(
+            path,
+            format!("expected {}, found {}", expected, kind(found)),
+            false,
+        );
+    }
+
+    /// the entries of a map with string keys
+    fn entries<'v>(&mut self, path: &[Segment], value: &'v Value) -> Vec<(String, &'v Value)> {
+        let map = match value {
+            Value::Mapping(map) => map,
+            other => {
+                self.expected(path, "map", other);
+                return Vec::new();
+            }
+        };
+        let mut out = Vec::new();
+        for (k, v) in map {
+            match k {
+                Value::String(k) => out.push((k.clone(), v)),
+                other => self.expected(path, "string keys", other),
+            }
+        }
+        out
+    }
+
+    fn string(&mut self, path: &[Segment], value: &Value) {
+        if !matches!(value, Value::String(_)) {
+            self.expected(path, "string", value);
+        }
+    }
+
+    fn strings(&mut self, path: &[Segment], value: &Value) {
+        match value {
+            Value::Sequence(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.string(&child(path, Segment::Index(i)), item);
+                }
+            }
+            other => self.expected(path, "sequence of strings", other),
+        }
+    }
+
+    fn string_map(
+        &mut self,
+        path: &[Segment],
+        value: &Value,
+        values: fn(&mut Self, &[Segment], &Value),
+    ) {
+        for (k, v) in self.entries(path, value) {
+            values(self, &child(path, Segment::Key(k)), v);
+        }
+    }
+
+    /// limits the engine enforces on each pattern, checked up front so a run doesn't fail
+    /// on them halfway. `templates` are the unit's template names with their alternatives
+    fn pattern_limits(&mut self, path: &[Segment], value: &Value, templates: &[(String, usize)]) {
+        let patterns = match value {
+            Value::Sequence(items) => items,
+            _ => return,
+        };
+        for (i, pattern) in patterns.iter().enumerate() {
+            let pattern = match pattern.as_str() {
+                Some(v) => v,
+                None => continue,
+            };
+            let path = child(path, Segment::Index(i));
+            let tokens = tokenize(pattern);
+
+            if let Some(token) = tokens.iter().find(|t| t.len() > DEFAULT_MAX_TOKEN_LENGTH) {
+                let start: String = token.chars().take(20).collect();
+                self.report(
+                    &path,
+                    format!(
+                        "pattern {} has a {}-byte token (`{}…`), over the engine's {}-byte \
+                         limit, and will be rejected",
+                        i + 1,
+                        token.len(),
+                        start,
+                        DEFAULT_MAX_TOKEN_LENGTH
+                    ),
+                    false,
+                );
+            }
+
+            let captures: BTreeSet<&str> = tokens
+                .iter()
+                .filter(|t| t.len() > 1 && t.starts_with('$'))
+                .map(|t| t.as_str())
+                .collect();
+            if captures.len() > DEFAULT_MAX_DISTINCT_GROUPS {
+                self.report(
+                    &path,
+                    format!(
+                        "pattern {} has {} captures, more than the {} groups the engine \
+                         tracks; merge or drop some",
+                        i + 1,
+                        captures.len(),
+                        DEFAULT_MAX_DISTINCT_GROUPS
+                    ),
+                    true,
+                );
+            }
+
+            // a rough count: every template named in the pattern multiplies it
+            let expansions = templates
+                .iter()
+                .filter(|(name, _)| pattern.contains(name.as_str()))
+                .fold(1usize, |n, (_, alternatives)| {
+                    n.saturating_mul(*alternatives)
+                });
+            if expansions > DEFAULT_MAX_EXPANSIONS {
+                self.report(
+                    &path,
+                    format!(
+                        "pattern {} expands to about {} patterns through its templates, over \
+                         the engine's limit of {}; split it or trim the templates",
+                        i + 1,
+                        expansions,
+                        DEFAULT_MAX_EXPANSIONS
+                    ),
+                    true,
+                );
+            }
+        }
+    }
+
+    fn unit(&mut self, path: &[Segment], value: &Value) {
+        let entries = self.entries(path, value);
+        if matches!(value, Value::Mapping(_)) && !entries.iter().any(|(k, _)| k == "patterns") {
+            self.report(path, "missing field `patterns`".to_string(), false);
+        }
+        let templates: Vec<(String, usize)> = match value.get("templates") {
+            Some(Value::Mapping(templates)) => templates
+                .iter()
+                .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.as_sequence()?.len())))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (key, v) in entries {
+            let path = child(path, Segment::Key(key.clone()));
+            match key.as_str() {
+                "patterns" => {
+                    self.strings(&path, v);
+                    self.pattern_limits(&path, v, &templates);
+                }
+                "tags" => self.strings(&path, v),
+                "name" | "description" => self.string(&path, v),
+                "docs_url" => match v {
+                    Value::String(url) if !is_web_url(url) => {
+                        self.report(&path, "only http(s) links are shown".to_string(), true)
+                    }
+                    other => self.string(&path, other),
+                },
+                "enabled" => {
+                    if !matches!(v, Value::Bool(_)) {
+                        self.expected(&path, "bool", v);
+                    }
+                }
+                "max_matches" => match v.as_u64() {
+                    Some(0) => self.report(
+                        &path,
+                        "a cap of 0 hides every match, disable the rule instead".to_string(),
+                        true,
+                    ),
+                    Some(_) => {}
+                    None => self.expected(&path, "positive integer", v),
+                },
+                // validated by the engine
+                "group" => {}
+                "out" | "transform" => self.string_map(&path, v, Self::string),
+                "templates" => self.string_map(&path, v, Self::strings),
+                "style" => {
+                    for (k, v) in self.entries(&path, v) {
+                        let path = child(&path, Segment::Key(k.clone()));
+                        match k.as_str() {
+                            "background" | "border" | "text_decoration" => self.string(&path, v),
+                            _ => {
+                                self.report(&path, format!("unknown style property `{}`", k), true)
+                            }
+                        }
+                    }
+                }
+                _ => self.report(&path, format!("unknown field `{}`", key), true),
+            }
+        }
+    }
+
+    /// checks across units: a repeated name makes the results ambiguous, and a group no
+    /// other unit is in is likely a misspelling of another unit's
+    fn cross_references(&mut self, units: &[Value]) {
+        let mut names: BTreeMap<&str, usize> = BTreeMap::new();
+        for (i, unit) in units.iter().enumerate() {
+            let name = match unit.get("name").and_then(Value::as_str) {
+                Some(v) if !v.is_empty() => v,
+                _ => continue,
+            };
+            match names.get(name) {
+                Some(first) => self.report(
+                    &[Segment::Index(i), Segment::Key("name".to_string())],
+                    format!(
+                        "rule {} is also called `{}`, the results can't tell their matches apart",
+                        first + 1,
+                        name
+                    ),
+                    true,
+                ),
+                None => {
+                    names.insert(name, i);
+                }
+            }
+        }
+
+        let groups: Vec<(usize, &Value)> = units
+            .iter()
+            .enumerate()
+            .filter_map(|(i, unit)| Some((i, unit.get("group")?)))
+            .filter(|(_, group)| !group.is_null())
+            .collect();
+        for (i, group) in &groups {
+            if groups.iter().all(|(j, other)| j == i || other != group) {
+                self.report(
+                    &[Segment::Index(*i), Segment::Key("group".to_string())],
+                    "no other rule is in this group".to_string(),
+                    true,
+                );
+            }
+        }
+    }
+}
+
+fn child(path: &[Segment], segment: Segment) -> Vec<Segment> {
+    let mut out = path.to_vec();
+    out.push(segment);
+    out
+}
+
+/// the key of a `key: value` line, unquoted
+fn key_of(text: &str) -> Option<&str> {
+    let end = text
+        .find(": ")
+        .or_else(|| text.ends_with(':').then(|| text.len() - 1))?;
+    Some(text[..end].trim().trim_matches(|c| c == '"' || c == '\''))
+}
+
+/// best effort position of the node at `path`, assuming block style YAML. falls back to the
+/// deepest node that could be found
+fn locate(yaml: &str, path: &[Segment]) -> (usize, usize) {
+    // (line number, indent, trimmed text) of the lines that hold content
+    let mut lines: Vec<(usize, usize, String)> = yaml
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| {
+            let t = l.trim();
+            !t.is_empty() && !t.starts_with('#') && t != "---"
+        })
+        .map(|(i, l)| (i + 1, l.len() - l.trim_start().len(), l.trim().to_string()))
+        .collect();
+
+    let mut pos = (1, 1);
+    let (mut start, mut end) = (0, lines.len());
+    for segment in path {
+        let indent = match lines[start..end].iter().map(|l| l.1).min() {
+            Some(v) => v,
+            None => break,
+        };
+        match segment {
+            Segment::Index(n) => {
+                let items: Vec<usize> = (start..end)
+                    .filter(|&i| {
+                        lines[i].1 == indent && (lines[i].2 == "-" || lines[i].2.starts_with("- "))
+                    })
+                    .collect();
+                let item = match items.get(*n) {
+                    Some(&v) => v,
+                    None => break,
+                };
+                pos = (lines[item].0, indent + 1);
+                end = items.get(n + 1).copied().unwrap_or(end);
+                // the item's first key shares the line with the dash
+                let text = lines[item].2.clone();
+                let rest = text[1..].trim_start();
+                if rest.is_empty() {
+                    start = item + 1;
+                } else {
+                    lines[item].1 = indent + text.len() - rest.len();
+                    lines[item].2 = rest.to_string();
+                    start = item;
+                }
+            }
+            Segment::Key(key) => {
+                let found = (start..end)
+                    .find(|&i| lines[i].1 == indent && key_of(&lines[i].2) == Some(key));
+                let line = match found {
+                    Some(v) => v,
+                    None => break,
+                };
+                pos = (lines[line].0, lines[line].1 + 1);
+                // a sequence value may sit at the key's own indent
+                end = (line + 1..end)
+                    .find(|&i| {
+                        lines[i].1 < indent
+                            || (lines[i].1 == indent && !lines[i].2.starts_with('-'))
+                    })
+                    .unwrap_or(end);
+                start = line + 1;
+            }
+        }
+    }
+    pos
+}
@@ -0,0 +1,125 @@
+//! the whole workspace as a zip archive: the pattern YAML, every subject file and a manifest.
+//! unlike a share link it has no size limit, and the files stay readable after unzipping
+
+use std::io::{Cursor, Read, Write};
+
+use serde::{Deserialize, Serialize};
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::io::{EditorFile, resolve_language};
+
+/// bumped if the archive layout changes
+const WORKSPACE_VERSION: u32 = 1;
+const MANIFEST_PATH: &str = "manifest.json";
+const PATTERNS_PATH: &str = "patterns.yaml";
+/// an entry bigger than this is refused on import rather than inflated
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize)]
+struct ManifestFile {
+    name: String,
+    /// monaco language id
+    language: String,
+    /// entry holding the content
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    #[serde(default)]
+    active_file: usize,
+    files: Vec<ManifestFile>,
+}
+
+/// entry path of a subject. names may contain anything, so separators are replaced and
+/// a clash gets the file's index
+fn entry_path(index: usize, name: &str, taken: &[ManifestFile]) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\') { '_' } else { c })
+        .collect();
+    let path = format!("files/{safe}");
+    if safe.is_empty() || safe.starts_with('.') || taken.iter().any(|f| f.path == path) {
+        format!("files/{index}_{safe}")
+    } else {
+        path
+    }
+}
+
+/// zip `lhs` and `files`, the active file index is kept in the manifest
+pub fn export_zip(lhs: &str, files: &[EditorFile], active_file: usize) -> Result<Vec<u8>, String> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut manifest = Manifest {
+        version: WORKSPACE_VERSION,
+        active_file,
+        files: Vec::with_capacity(files.len()),
+    };
+
+    zip.start_file(PATTERNS_PATH, options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(lhs.as_bytes()).map_err(|e| e.to_string())?;
+
+    for (index, file) in files.iter().enumerate() {
+        let path = entry_path(index, &file.name, &manifest.files);
+        zip.start_file(path.as_str(), options)
+            .map_err(|e| e.to_string())?;
+        zip.write_all(file.content.as_bytes())
+            .map_err(|e| e.to_string())?;
+        manifest.files.push(ManifestFile {
+            name: file.name.clone(),
+            language: file.language.clone(),
+            path,
+        });
+    }
+
+    zip.start_file(MANIFEST_PATH, options)
+        .map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.write_all(json.as_bytes()).map_err(|e| e.to_string())?;
+
+    Ok(zip.finish().map_err(|e| e.to_string())?.into_inner())
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<&[u8]>>, path: &str) -> Result<String, String> {
+    let entry = archive.by_name(path).map_err(|e| format!("{path}: {e}"))?;
+    if entry.size() > MAX_ENTRY_BYTES {
+        return Err(format!("{path}: too large ({} bytes)", entry.size()));
+    }
+    let mut content = String::new();
+    entry
+        .take(MAX_ENTRY_BYTES)
+        .read_to_string(&mut content)
+        .map_err(|e| format!("{path}: {e}"))?;
+    Ok(content)
+}
+
+/// the lhs, the subject files and the active file index of an exported archive
+pub fn import_zip(bytes: &[u8]) -> Result<(String, Vec<EditorFile>, usize), String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    let manifest: Manifest = serde_json::from_str(&read_entry(&mut archive, MANIFEST_PATH)?)
+        .map_err(|e| format!("{MANIFEST_PATH}: {e}"))?;
+    if manifest.version > WORKSPACE_VERSION {
+        return Err(format!(
+            "workspace version {} is newer than this playground supports ({WORKSPACE_VERSION})",
+            manifest.version
+        ));
+    }
+    if manifest.files.is_empty() {
+        return Err(format!("{MANIFEST_PATH}: no files"));
+    }
+
+    let lhs = read_entry(&mut archive, PATTERNS_PATH)?;
+    let mut files = Vec::with_capacity(manifest.files.len());
+    for file in manifest.files {
+        let (language, _) =
+            resolve_language(&file.language).map_err(|e| format!("{}: {e}", file.name))?;
+        files.push(EditorFile {
+            content: read_entry(&mut archive, &file.path)?,
+            name: file.name,
+            language: language.to_string(),
+        });
+    }
+    Ok((lhs, files, manifest.active_file))
+}
@@ -0,0 +1,96 @@
+// offline support. trunk copies this next to index.html and a post_build hook
+// writes precache-manifest.json listing every file of the build
+const CACHE = "lexer-search-ui";
+const MANIFEST = "precache-manifest.json";
+// files that keep their name across deploys, so a cached copy could go stale
+const UNHASHED = ["playground-config.json", MANIFEST];
+
+// the network's response, kept in the cache under `key` for when it is unreachable
+function networkFirst(request, key) {
+  return fetch(request)
+    .then((response) => {
+      if (response.ok) {
+        const copy = response.clone();
+        caches.open(CACHE).then((cache) => cache.put(key, copy));
+      }
+      return response;
+    })
+    .catch(() => caches.match(key).then((cached) => cached || Response.error()));
+}
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    (async () => {
+      const cache = await caches.open(CACHE);
+      try {
+        const response = await fetch(MANIFEST, { cache: "no-store" });
+        const files = await response.json();
+        await cache.addAll(["./", ...files]);
+      } catch (e) {
+        // e.g. `trunk serve` without the hook. pages are still cached as they load
+        console.warn("could not precache the playground", e);
+      }
+      await self.skipWaiting();
+    })()
+  );
+});
+
+// drop what the current build doesn't list, e.g. the hashed assets of earlier deploys
+async function pruneCache() {
+  let files;
+  try {
+    const response = await fetch(MANIFEST, { cache: "no-store" });
+    files = await response.json();
+  } catch (e) {
+    // without the list there is no telling what is stale
+    return;
+  }
+  const keep = new Set(
+    ["./", ...UNHASHED, ...files].map((file) => new URL(file, self.registration.scope).href)
+  );
+  const cache = await caches.open(CACHE);
+  for (const request of await cache.keys()) {
+    if (!keep.has(request.url)) {
+      await cache.delete(request);
+    }
+  }
+}
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(Promise.all([pruneCache(), self.clients.claim()]));
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  if (request.method !== "GET" || new URL(request.url).origin !== self.location.origin) {
+    // e.g. the optional link shortener
+    return;
+  }
+
+  if (request.mode === "navigate") {
+    // network first so a new deploy is picked up, the cached shell when offline
+    event.respondWith(networkFirst(request, "./"));
+    return;
+  }
+
+  const name = new URL(request.url).pathname.split("/").pop();
+  if (UNHASHED.includes(name)) {
+    event.respondWith(networkFirst(request, request));
+    return;
+  }
+
+  // trunk hashes the other asset names, so a cached asset never goes stale
+  event.respondWith(
+    caches.match(request).then(
+      (cached) =>
+        cached ||
+        fetch(request).then((response) => {
+          if (response.ok) {
+            const copy = response.clone();
+            caches.open(CACHE).then((cache) => cache.put(request, copy));
+          }
+          return response;
+        })
+    )
+  );
+});